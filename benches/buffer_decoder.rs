@@ -0,0 +1,80 @@
+use bytes::BytesMut;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rustis::{
+    resp::{BufferDecoder, RespBuf, RespDeserializer},
+    Error, Result,
+};
+use serde::{de::IgnoredAny, Deserialize};
+use tokio_util::codec::Decoder;
+
+/// Size of the chunks read from the network
+const READ_SIZE: usize = 16 * 1024;
+
+/// RESP3 reply of an `HGETALL` on a hash of `len` fields
+fn hgetall_reply(len: usize) -> Vec<u8> {
+    let mut reply = format!("%{len}\r\n").into_bytes();
+    for i in 0..len {
+        let field = format!("field:{i}");
+        let value = format!("value:{i}:{}", "x".repeat(64));
+        reply.extend_from_slice(format!("${}\r\n{field}\r\n", field.len()).as_bytes());
+        reply.extend_from_slice(format!("${}\r\n{value}\r\n", value.len()).as_bytes());
+    }
+    reply
+}
+
+/// Previous decoder, parsing the whole buffer again each time bytes are received
+///
+/// The normalization of streamed values is left out since the benchmarked replies have none.
+struct PreviousDecoder;
+
+impl Decoder for PreviousDecoder {
+    type Item = RespBuf;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let mut deserializer = RespDeserializer::new(src);
+        match IgnoredAny::deserialize(&mut deserializer) {
+            Ok(_) => Ok(Some(RespBuf::new(
+                src.split_to(deserializer.get_pos()).freeze(),
+            ))),
+            Err(Error::EOF) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Feed the reply by chunks to a decoder
+fn decode(mut decoder: impl Decoder<Item = RespBuf, Error = Error>, reply: &[u8]) -> usize {
+    let mut buf = BytesMut::new();
+    for chunk in reply.chunks(READ_SIZE) {
+        buf.extend_from_slice(chunk);
+        if let Some(frame) = decoder.decode(&mut buf).unwrap() {
+            return frame.len();
+        }
+    }
+    unreachable!()
+}
+
+fn bench_decode_large_reply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_large_reply");
+
+    for len in [1_000, 10_000, 50_000] {
+        let reply = hgetall_reply(len);
+        group.throughput(Throughput::Bytes(reply.len() as u64));
+        group.bench_with_input(BenchmarkId::new("resumable", len), &reply, |b, reply| {
+            b.iter(|| decode(BufferDecoder::default(), reply))
+        });
+        group.bench_with_input(BenchmarkId::new("previous", len), &reply, |b, reply| {
+            b.iter(|| decode(PreviousDecoder, reply))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(bench, bench_decode_large_reply);
+criterion_main!(bench);
@@ -0,0 +1,29 @@
+//! Run with `cargo run --example cbor_typed --features cbor`
+
+use rustis::{
+    client::Client,
+    commands::{GenericCommands, StringCommands},
+    resp::{BulkString, Cbor},
+    Result,
+};
+use std::collections::HashMap;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = Client::connect("127.0.0.1:6379").await?;
+
+    let key = "test_key";
+    let value = HashMap::from([("foo".to_owned(), "bar".to_owned())]);
+
+    client.set(key, Cbor::new(&value)?).await?;
+
+    // {"foo": "bar"} in CBOR
+    let raw: BulkString = client.get(key).await?;
+    assert_eq!(b"\xa1\x63\x66\x6F\x6F\x63\x62\x61\x72", raw.as_bytes());
+
+    let typed: Cbor<HashMap<String, String>> = client.get(key).await?;
+    assert_eq!(value, typed.into_inner());
+    client.del(key).await?;
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "fault-injection")]
+use crate::client::FaultPlan;
 #[cfg(test)]
 use crate::commands::DebugCommands;
 #[cfg(feature = "redis-graph")]
@@ -14,8 +16,10 @@ use crate::commands::{
 };
 use crate::{
     client::{
-        ClientState, ClientTrackingInvalidationStream, IntoConfig, Message, MonitorStream,
-        Pipeline, PreparedCommand, PubSubStream, Transaction,
+        CacheLookup, ClientSideCache, ClientSideCacheMode, ClientSideCacheStats, ClientState,
+        ClientTrackingInvalidationStream, Durability, IntoConfig, KeyspaceEventStream,
+        KeyspaceEventsFilter, Message, MonitorStream, Pipeline, PreparedCommand,
+        PubSubOverflowPolicy, PubSubStream, SentinelEvent, SentinelEventStream, Transaction,
     },
     commands::{
         BitmapCommands, BlockingCommands, ClusterCommands, ConnectionCommands, GenericCommands,
@@ -24,22 +28,23 @@ use crate::{
         SortedSetCommands, StreamCommands, StringCommands, TransactionCommands,
     },
     network::{
-        timeout, JoinHandle, MsgSender, NetworkHandler, PubSubReceiver, PubSubSender, PushReceiver,
-        PushSender, ReconnectReceiver, ReconnectSender, ResultReceiver, ResultSender,
-        ResultsReceiver, ResultsSender,
+        push_channel, timeout, JoinHandle, MsgSender, NetworkHandler, PubSubReceiver, PubSubSender,
+        PushReceiver, PushSender, ReconnectReceiver, ReconnectSender, ResultReceiver, ResultSender,
+        ResultsReceiver, ResultsSender, SentinelEventReceiver, SentinelEventSender,
     },
     resp::{cmd, Command, CommandArgs, RespBuf, Response, SingleArg, SingleArgCollection},
-    Error, Future, Result,
+    spawn, Error, Future, Result,
 };
 use futures_channel::{mpsc, oneshot};
-use futures_util::Stream;
-use log::{info, trace};
+use futures_util::{select, FutureExt, Stream};
+use log::{debug, info, trace, warn};
 use serde::de::DeserializeOwned;
 use std::{
     future::IntoFuture,
     sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
     time::Duration,
 };
+use tokio::sync::broadcast::error::RecvError;
 
 /// Client with a unique connection to a Redis server.
 #[derive(Clone)]
@@ -50,6 +55,12 @@ pub struct Client {
     client_state: Arc<RwLock<ClientState>>,
     command_timeout: Duration,
     retry_on_error: bool,
+    pub(crate) pub_sub_capacity: usize,
+    pub(crate) pub_sub_overflow_policy: PubSubOverflowPolicy,
+    database: usize,
+    client_side_cache: Option<Arc<ClientSideCache>>,
+    #[cfg(feature = "fault-injection")]
+    fault_plan: FaultPlan,
 }
 
 impl Drop for Client {
@@ -85,8 +96,17 @@ impl Client {
         let config = config.into_config()?;
         let command_timeout = config.command_timeout;
         let retry_on_error = config.retry_on_error;
+        let pub_sub_capacity = config.pub_sub_capacity;
+        let pub_sub_overflow_policy = config.pub_sub_overflow_policy;
+        let database = config.database;
+        #[cfg(feature = "fault-injection")]
+        let fault_plan = config.fault_plan.clone();
+        let client_side_cache = config
+            .client_side_cache
+            .clone()
+            .map(|cache_config| Arc::new(ClientSideCache::new(cache_config, database)));
         let (msg_sender, network_task_join_handle, reconnect_sender) =
-            NetworkHandler::connect(config.into_config()?).await?;
+            NetworkHandler::connect(config.into_config()?, client_side_cache.clone()).await?;
 
         Ok(Self {
             msg_sender: Arc::new(Some(msg_sender)),
@@ -95,6 +115,12 @@ impl Client {
             client_state: Arc::new(RwLock::new(ClientState::new())),
             command_timeout,
             retry_on_error,
+            pub_sub_capacity,
+            pub_sub_overflow_policy,
+            database,
+            client_side_cache,
+            #[cfg(feature = "fault-injection")]
+            fault_plan,
         })
     }
 
@@ -132,13 +158,50 @@ impl Client {
         self.reconnect_sender.subscribe()
     }
 
+    /// Spawn a task running `action` each time the client reconnects,
+    /// to restore a server state which may have been lost (e.g. after a restart or a failover).
+    ///
+    /// The task stops when the returned sender is dropped or when the client is closed.
+    pub(crate) fn spawn_on_reconnect<F>(
+        &self,
+        name: &'static str,
+        mut action: F,
+    ) -> oneshot::Sender<()>
+    where
+        F: FnMut(Client) -> Future<'static, ()> + Send + 'static,
+    {
+        let (stop_sender, stop_receiver) = oneshot::channel::<()>();
+        let mut reconnect_receiver = self.on_reconnect();
+        let client = self.clone();
+
+        spawn(async move {
+            let mut stop_receiver = stop_receiver.fuse();
+            loop {
+                select! {
+                    _ = stop_receiver => break,
+                    result = reconnect_receiver.recv().fuse() => {
+                        if let Err(RecvError::Closed) = result {
+                            break;
+                        }
+                        debug!("Restoring {name} after reconnection");
+                        if let Err(e) = action(client.clone()).await {
+                            warn!("Cannot restore {name} after reconnection: {e}");
+                        }
+                    }
+                }
+            }
+        });
+
+        stop_sender
+    }
+
     /// Give an immutable generic access to attach any state to a client instance
-    pub fn get_client_state(&self) -> RwLockReadGuard<ClientState> {
+    pub fn get_client_state(&self) -> RwLockReadGuard<'_, ClientState> {
         self.client_state.read().unwrap()
     }
 
     /// Give a mutable generic access to attach any state to a client instance
-    pub fn get_client_state_mut(&self) -> RwLockWriteGuard<ClientState> {
+    pub fn get_client_state_mut(&self) -> RwLockWriteGuard<'_, ClientState> {
         self.client_state.write().unwrap()
     }
 
@@ -197,6 +260,62 @@ impl Client {
     /// ```
     #[inline]
     pub async fn send(&self, command: Command, retry_on_error: Option<bool>) -> Result<RespBuf> {
+        let Some(client_side_cache) = &self.client_side_cache else {
+            return self.send_uncached(command, retry_on_error).await;
+        };
+
+        let pending_entry = match client_side_cache.get(&command) {
+            CacheLookup::Hit(resp_buf) => return Ok(resp_buf),
+            CacheLookup::NotCacheable => return self.send_uncached(command, retry_on_error).await,
+            CacheLookup::Miss(pending_entry) => pending_entry,
+        };
+
+        let result = if let ClientSideCacheMode::OptIn = client_side_cache.config().mode {
+            self.send_batch(
+                vec![cmd("CLIENT").arg("CACHING").arg("YES"), command],
+                retry_on_error,
+            )
+            .await
+            .and_then(|mut results| {
+                results
+                    .pop()
+                    .ok_or_else(|| Error::Client("Missing reply to the command".to_owned()))
+            })
+        } else {
+            self.send_uncached(command, retry_on_error).await
+        };
+
+        match &result {
+            Ok(resp_buf) => client_side_cache.insert(pending_entry, resp_buf),
+            Err(_) => client_side_cache.cancel(pending_entry),
+        }
+
+        result
+    }
+
+    /// Statistics of the client side cache,
+    /// or `None` if [`Config::client_side_cache`](crate::client::Config::client_side_cache) is not set
+    pub fn client_side_cache_stats(&self) -> Option<ClientSideCacheStats> {
+        self.client_side_cache
+            .as_ref()
+            .map(|client_side_cache| client_side_cache.stats())
+    }
+
+    /// Plan of the faults injected in the connections of the client,
+    /// shared with [`Config::fault_plan`](crate::client::Config::fault_plan)
+    ///
+    /// This function is only available with the feature `fault-injection`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "fault-injection")))]
+    #[cfg(feature = "fault-injection")]
+    pub fn fault_plan(&self) -> &FaultPlan {
+        &self.fault_plan
+    }
+
+    async fn send_uncached(
+        &self,
+        command: Command,
+        retry_on_error: Option<bool>,
+    ) -> Result<RespBuf> {
         let (result_sender, result_receiver): (ResultSender, ResultReceiver) = oneshot::channel();
         let message = Message::single(
             command,
@@ -269,7 +388,7 @@ impl Client {
         if let Some(msg_sender) = &self.msg_sender as &Option<MsgSender> {
             trace!("Will enqueue message: {message:?}");
             Ok(msg_sender.unbounded_send(message).map_err(|e| {
-                info!("{}", e.to_string());
+                info!("{}", e);
                 Error::Client("Disconnected from server".to_string())
             })?)
         } else {
@@ -287,26 +406,114 @@ impl Client {
 
     /// Create a new pipeline
     #[inline]
-    pub fn create_pipeline(&self) -> Pipeline {
+    pub fn create_pipeline(&self) -> Pipeline<'_> {
         Pipeline::new(self)
     }
 
     /// Create a new pub sub stream with no upfront subscription
     #[inline]
     pub fn create_pub_sub(&self) -> PubSubStream {
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) =
+            push_channel(self.pub_sub_capacity, self.pub_sub_overflow_policy);
         PubSubStream::new(pub_sub_sender, pub_sub_receiver, self.clone())
     }
 
+    /// Create a stream of the keys invalidated by the server
+    /// when the [`client tracking`](crate::commands::ConnectionCommands::client_tracking) is on.
+    ///
+    /// An empty list of keys means that all the keys must be invalidated:
+    /// the server has been flushed or the client has reconnected
+    /// and invalidation messages may have been lost.
+    ///
+    /// The stream ends if the client tracking cannot be restored after a reconnection.
     pub fn create_client_tracking_invalidation_stream(
         &self,
     ) -> Result<impl Stream<Item = Vec<String>>> {
-        let (push_sender, push_receiver): (PushSender, PushReceiver) = mpsc::unbounded();
+        // invalidation messages are never dropped
+        let (push_sender, push_receiver): (PushSender, PushReceiver) =
+            push_channel(0, PubSubOverflowPolicy::default());
         let message = Message::client_tracking_invalidation(push_sender);
         self.send_message(message)?;
         Ok(ClientTrackingInvalidationStream::new(push_receiver))
     }
 
+    /// Create a stream of the events published by Sentinel about the master of the service
+    /// this client is connected to (`+switch-master`, `-odown`, `+sdown`).
+    ///
+    /// When Sentinel announces a new master, the client reconnects automatically to it.
+    ///
+    /// The stream ends immediately if the client is not connected through
+    /// [`Sentinel`](crate::client::ServerConfig::Sentinel).
+    ///
+    /// # Errors
+    /// Any Redis driver [`Error`](crate::Error) that occurs during the send operation
+    pub fn create_sentinel_event_stream(&self) -> Result<impl Stream<Item = SentinelEvent>> {
+        let (sentinel_event_sender, sentinel_event_receiver): (
+            SentinelEventSender,
+            SentinelEventReceiver,
+        ) = mpsc::unbounded();
+        let message = Message::sentinel_events(sentinel_event_sender);
+        self.send_message(message)?;
+        Ok(SentinelEventStream::new(sentinel_event_receiver))
+    }
+
+    /// Create a stream of [keyspace events](https://redis.io/docs/manual/keyspace-notifications/)
+    ///
+    /// Events of the keys matching the filter are streamed from the channels `__keyspace@<db>__:<key>`.
+    /// The subscription is restored after a reconnection
+    /// if [`auto_resubscribe`](crate::client::Config::auto_resubscribe) is enabled.
+    ///
+    /// In a cluster, only the events of the node the subscription is sent to are received.
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::{Client, KeyEventKind, KeyspaceEventsFilter},
+    ///     commands::StringCommands,
+    ///     Result,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     let mut events = client
+    ///         .keyspace_events(
+    ///             KeyspaceEventsFilter::default()
+    ///                 .key_pattern("user:*")
+    ///                 .notify_keyspace_events("KA"),
+    ///         )
+    ///         .await?;
+    ///
+    ///     client.set("user:1", "John").await?;
+    ///
+    ///     let event = events.next().await.unwrap()?;
+    ///     assert_eq!(b"user:1".to_vec(), event.key);
+    ///     assert_eq!(KeyEventKind::Set, event.kind);
+    ///
+    ///     events.close().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Any Redis driver [`Error`](crate::Error) that occurs during the configuration
+    /// or the subscription
+    pub async fn keyspace_events(
+        &self,
+        filter: KeyspaceEventsFilter,
+    ) -> Result<KeyspaceEventStream> {
+        KeyspaceEventStream::new(self, filter).await
+    }
+
+    /// Database selected in the configuration of the client
+    pub(crate) fn database(&self) -> usize {
+        self.database
+    }
+
     pub(crate) async fn subscribe_from_pub_sub_sender(
         &self,
         channels: &CommandArgs,
@@ -398,6 +605,92 @@ impl<'a, R: Response> ClientPreparedCommand<'a, R> for PreparedCommand<'a, &'a C
     }
 }
 
+impl<'a, R: Response + 'a> PreparedCommand<'a, &'a Client, R> {
+    /// Send command and return its reply as a RESP buffer, without deserializing it
+    ///
+    /// The buffer is owned by the caller and can be deserialized
+    /// with [`RespBuf::to`](crate::resp::RespBuf::to) into types borrowing from it
+    /// (`&str`, `&[u8]`, structs with borrowed fields), so that large payloads are not copied.
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::Client,
+    ///     commands::StringCommands,
+    ///     Result,
+    /// };
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     client.set("key", "value").await?;
+    ///     let resp_buf = client.get::<_, ()>("key").into_resp_buf().await?;
+    ///     let value: &str = resp_buf.to()?;
+    ///     assert_eq!("value", value);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Any Redis driver [`Error`](crate::Error) that occur during the send operation,
+    /// the error returned by the Redis server,
+    /// or an error if the reply of the command needs a custom conversion (e.g. `GRAPH.QUERY`)
+    pub fn into_resp_buf(self) -> Future<'a, RespBuf> {
+        Box::pin(async move {
+            if self.custom_converter.is_some() {
+                return Err(Error::Client(format!(
+                    "The reply of the command `{}` cannot be returned as a RESP buffer",
+                    self.command.name
+                )));
+            }
+
+            let result = self
+                .executor
+                .send_prepared(self.command, self.durability, self.retry_on_error)
+                .await?;
+
+            if result.is_error() {
+                // returns the Redis error
+                result.to::<()>()?;
+            }
+
+            Ok(result)
+        })
+    }
+}
+
+impl Client {
+    /// Send a prepared command, followed by its durability command if any
+    async fn send_prepared(
+        &self,
+        command: Command,
+        durability: Option<Durability>,
+        retry_on_error: Option<bool>,
+    ) -> Result<RespBuf> {
+        match durability {
+            Some(durability) => {
+                let mut results = self
+                    .send_batch(vec![command, durability.command()], retry_on_error)
+                    .await?;
+                let durability_result = results.pop().ok_or_else(|| {
+                    Error::Client("Missing reply to the durability command".to_owned())
+                })?;
+                let result = results
+                    .pop()
+                    .ok_or_else(|| Error::Client("Missing reply to the command".to_owned()))?;
+                if !result.is_error() {
+                    durability.check(&durability_result)?;
+                }
+                Ok(result)
+            }
+            None => self.send(command, retry_on_error).await,
+        }
+    }
+}
+
 impl<'a, R> IntoFuture for PreparedCommand<'a, &'a Client, R>
 where
     R: DeserializeOwned + Send + 'a,
@@ -407,19 +700,18 @@ where
 
     fn into_future(self) -> Self::IntoFuture {
         Box::pin(async move {
-            if let Some(custom_converter) = self.custom_converter {
-                let command_for_result = self.command.clone();
-                let result = self
-                    .executor
-                    .send(self.command, self.retry_on_error)
-                    .await?;
-                custom_converter(result, command_for_result, self.executor).await
-            } else {
-                let result = self
-                    .executor
-                    .send(self.command, self.retry_on_error)
-                    .await?;
-                result.to()
+            let command_for_result = self.custom_converter.as_ref().map(|_| self.command.clone());
+
+            let result = self
+                .executor
+                .send_prepared(self.command, self.durability, self.retry_on_error)
+                .await?;
+
+            match (self.custom_converter, command_for_result) {
+                (Some(custom_converter), Some(command_for_result)) => {
+                    custom_converter(result, command_for_result, self.executor).await
+                }
+                _ => result.to(),
             }
         })
     }
@@ -481,7 +773,8 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let channels = CommandArgs::default().arg(channels).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) =
+            push_channel(self.pub_sub_capacity, self.pub_sub_overflow_policy);
 
         self.subscribe_from_pub_sub_sender(&channels, &pub_sub_sender)
             .await?;
@@ -502,7 +795,8 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let patterns = CommandArgs::default().arg(patterns).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) =
+            push_channel(self.pub_sub_capacity, self.pub_sub_overflow_policy);
 
         self.psubscribe_from_pub_sub_sender(&patterns, &pub_sub_sender)
             .await?;
@@ -523,7 +817,8 @@ impl<'a> PubSubCommands<'a> for &'a Client {
     {
         let shardchannels = CommandArgs::default().arg(shardchannels).build();
 
-        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) = mpsc::unbounded();
+        let (pub_sub_sender, pub_sub_receiver): (PubSubSender, PubSubReceiver) =
+            push_channel(self.pub_sub_capacity, self.pub_sub_overflow_policy);
 
         self.ssubscribe_from_pub_sub_sender(&shardchannels, &pub_sub_sender)
             .await?;
@@ -540,7 +835,8 @@ impl<'a> PubSubCommands<'a> for &'a Client {
 impl<'a> BlockingCommands<'a> for &'a Client {
     async fn monitor(self) -> Result<MonitorStream> {
         let (result_sender, result_receiver): (ResultSender, ResultReceiver) = oneshot::channel();
-        let (push_sender, push_receiver): (PushSender, PushReceiver) = mpsc::unbounded();
+        let (push_sender, push_receiver): (PushSender, PushReceiver) =
+            push_channel(self.pub_sub_capacity, self.pub_sub_overflow_policy);
 
         let message = Message::monitor(cmd("MONITOR"), result_sender, push_sender);
 
@@ -0,0 +1,455 @@
+use crate::{
+    commands::ClientTrackingOptions,
+    resp::{BulkString, Command, RespBuf},
+};
+use log::debug;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// Tracking mode of the [client side cache](https://redis.io/docs/manual/client-side-caching/)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ClientSideCacheMode {
+    /// The server remembers the keys read by the client
+    /// and sends invalidation messages only for these keys
+    #[default]
+    Default,
+    /// Same as `Default` but the server only remembers the keys
+    /// of the commands sent through the cache.
+    ///
+    /// Each cached command is preceded by `CLIENT CACHING YES`,
+    /// sent to the same node when reading from replicas
+    OptIn,
+    /// The server sends invalidation messages for all the keys starting with the given prefixes,
+    /// or for all the keys if no prefix is given.
+    ///
+    /// Only the commands on keys matching the prefixes are cached
+    Broadcast {
+        /// Prefixes of the keys to cache
+        prefixes: Vec<String>,
+    },
+}
+
+/// Configuration of the [client side cache](https://redis.io/docs/manual/client-side-caching/)
+///
+/// See [`Config::client_side_cache`](crate::client::Config::client_side_cache)
+#[derive(Debug, Clone)]
+pub struct ClientSideCacheConfig {
+    /// Tracking mode (default [`Default`](ClientSideCacheMode::Default))
+    pub mode: ClientSideCacheMode,
+    /// Maximum number of cached replies (default `10,000`)
+    ///
+    /// When the limit is reached, the oldest replies are evicted first
+    pub max_entries: usize,
+    /// Maximum size in bytes of the cached replies. If set to 0, the size is unbounded (default `0`)
+    ///
+    /// When the limit is reached, the oldest replies are evicted first
+    pub max_bytes: usize,
+    /// Time to live of a cached reply, even if not invalidated by the server (default `None`)
+    pub ttl: Option<Duration>,
+}
+
+impl Default for ClientSideCacheConfig {
+    fn default() -> Self {
+        Self {
+            mode: Default::default(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_bytes: 0,
+            ttl: None,
+        }
+    }
+}
+
+impl ClientSideCacheConfig {
+    /// Options of the [`client_tracking`](crate::commands::ConnectionCommands::client_tracking)
+    /// command enabling the invalidation messages for this configuration
+    pub(crate) fn tracking_options(&self) -> ClientTrackingOptions {
+        let options = ClientTrackingOptions::default();
+
+        match &self.mode {
+            ClientSideCacheMode::Default => options,
+            ClientSideCacheMode::OptIn => options.optin(),
+            ClientSideCacheMode::Broadcast { prefixes } => prefixes
+                .iter()
+                .fold(options.broadcasting(), |options, prefix| {
+                    options.prefix(prefix.as_str())
+                }),
+        }
+    }
+}
+
+/// Statistics of the client side cache
+///
+/// See [`Client::client_side_cache_stats`](crate::client::Client::client_side_cache_stats)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientSideCacheStats {
+    /// Number of replies served from the cache
+    pub hits: u64,
+    /// Number of cacheable commands sent to the server
+    pub misses: u64,
+    /// Number of replies removed because of an invalidation message or a flush
+    pub invalidations: u64,
+    /// Number of replies removed because of the size limits or the TTL
+    pub evictions: u64,
+    /// Current number of cached replies
+    pub entries: usize,
+    /// Current size in bytes of the cached replies
+    pub bytes: usize,
+}
+
+/// Positions of the keys in the arguments of a cacheable command
+enum KeySpec {
+    First,
+    All,
+}
+
+/// Read-only commands which can be cached
+fn key_spec(command: &Command) -> Option<KeySpec> {
+    match command.name {
+        "GET" | "GETRANGE" | "STRLEN" | "HGET" | "HGETALL" | "HMGET" | "HKEYS" | "HVALS"
+        | "HLEN" | "HEXISTS" | "HSTRLEN" | "LRANGE" | "LLEN" | "LINDEX" | "SMEMBERS"
+        | "SISMEMBER" | "SMISMEMBER" | "SCARD" | "ZRANGE" | "ZSCORE" | "ZMSCORE" | "ZCARD"
+        | "ZRANK" | "ZCOUNT" | "JSON.GET" | "JSON.TYPE" | "JSON.STRLEN" | "JSON.ARRLEN"
+        | "JSON.OBJKEYS" | "JSON.OBJLEN" => Some(KeySpec::First),
+        "MGET" => Some(KeySpec::All),
+        _ => None,
+    }
+}
+
+struct Entry {
+    value: RespBuf,
+    keys: Vec<Vec<u8>>,
+    expires_at: Option<Instant>,
+    /// insertion sequence, position of the entry in the eviction order
+    seq: u64,
+}
+
+impl Entry {
+    fn size(&self, cache_key: &[u8]) -> usize {
+        self.value.as_bytes().len() + cache_key.len()
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<Vec<u8>, Entry>,
+    /// commands sent to the server and waiting for their reply, by token
+    pending: HashMap<Vec<u8>, u64>,
+    /// cache keys (cached or pending) of each Redis key
+    keys: HashMap<Vec<u8>, HashSet<Vec<u8>>>,
+    /// cache keys of the entries by insertion sequence, oldest first, for eviction
+    eviction_order: BTreeMap<u64, Vec<u8>>,
+    bytes: usize,
+    next_seq: u64,
+    /// database selected on the connection, part of the cache keys
+    database: usize,
+}
+
+impl CacheState {
+    fn remove_entry(&mut self, cache_key: &[u8]) -> bool {
+        let Some(entry) = self.entries.remove(cache_key) else {
+            return false;
+        };
+
+        self.bytes -= entry.size(cache_key);
+        self.eviction_order.remove(&entry.seq);
+        for key in &entry.keys {
+            self.unlink(key, cache_key);
+        }
+
+        true
+    }
+
+    fn unlink(&mut self, key: &[u8], cache_key: &[u8]) {
+        if let Some(cache_keys) = self.keys.get_mut(key) {
+            cache_keys.remove(cache_key);
+            if cache_keys.is_empty() {
+                self.keys.remove(key);
+            }
+        }
+    }
+}
+
+/// Local cache of the replies of read-only commands,
+/// invalidated by the messages of the server-assisted
+/// [client side caching](https://redis.io/docs/manual/client-side-caching/)
+pub(crate) struct ClientSideCache {
+    config: ClientSideCacheConfig,
+    /// database selected by the connection after each reconnection
+    initial_database: usize,
+    state: Mutex<CacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+    evictions: AtomicU64,
+}
+
+/// Result of a cache lookup
+pub(crate) enum CacheLookup {
+    Hit(RespBuf),
+    /// the command must be sent to the server,
+    /// then its reply can be stored with [`ClientSideCache::insert`]
+    Miss(PendingCacheEntry),
+    NotCacheable,
+}
+
+/// Ticket of a command sent to the server, to store its reply in the cache
+pub(crate) struct PendingCacheEntry {
+    cache_key: Vec<u8>,
+    keys: Vec<Vec<u8>>,
+    token: u64,
+}
+
+impl ClientSideCache {
+    pub fn new(config: ClientSideCacheConfig, database: usize) -> Self {
+        Self {
+            config,
+            initial_database: database,
+            state: Mutex::new(CacheState {
+                database,
+                ..Default::default()
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &ClientSideCacheConfig {
+        &self.config
+    }
+
+    /// Look for the reply of a command in the cache
+    pub fn get(&self, command: &Command) -> CacheLookup {
+        let Some(keys) = self.cacheable_keys(command) else {
+            return CacheLookup::NotCacheable;
+        };
+
+        let mut state = self.state.lock().unwrap();
+        let cache_key = Self::cache_key(state.database, command);
+
+        if let Some(entry) = state.entries.get(&cache_key) {
+            match entry.expires_at {
+                Some(expires_at) if expires_at <= Instant::now() => {
+                    state.remove_entry(&cache_key);
+                    self.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return CacheLookup::Hit(entry.value.clone());
+                }
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let token = state.next_seq;
+        state.next_seq += 1;
+        state.pending.insert(cache_key.clone(), token);
+        for key in &keys {
+            state
+                .keys
+                .entry(key.clone())
+                .or_default()
+                .insert(cache_key.clone());
+        }
+
+        CacheLookup::Miss(PendingCacheEntry {
+            cache_key,
+            keys,
+            token,
+        })
+    }
+
+    /// Store the reply of a command sent to the server,
+    /// unless it has been invalidated in the meantime
+    pub fn insert(&self, pending_entry: PendingCacheEntry, value: &RespBuf) {
+        let PendingCacheEntry {
+            cache_key,
+            keys,
+            token,
+        } = pending_entry;
+        let mut state = self.state.lock().unwrap();
+
+        // invalidated or flushed while waiting for the reply
+        if state.pending.get(&cache_key) != Some(&token) {
+            return;
+        }
+        state.pending.remove(&cache_key);
+
+        if value.is_error() {
+            for key in &keys {
+                state.unlink(key, &cache_key);
+            }
+            return;
+        }
+
+        // a previous reply of the same command, while it was pending concurrently
+        if let Some(previous) = state.entries.remove(&cache_key) {
+            state.bytes -= previous.size(&cache_key);
+            state.eviction_order.remove(&previous.seq);
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        let entry = Entry {
+            value: value.clone(),
+            keys,
+            expires_at: self.config.ttl.map(|ttl| Instant::now() + ttl),
+            seq,
+        };
+        state.bytes += entry.size(&cache_key);
+        state.eviction_order.insert(seq, cache_key.clone());
+        state.entries.insert(cache_key, entry);
+
+        self.evict(&mut state);
+    }
+
+    /// Forget a command sent to the server without a reply
+    pub fn cancel(&self, pending_entry: PendingCacheEntry) {
+        let mut state = self.state.lock().unwrap();
+        if state.pending.get(&pending_entry.cache_key) == Some(&pending_entry.token) {
+            state.pending.remove(&pending_entry.cache_key);
+            if !state.entries.contains_key(&pending_entry.cache_key) {
+                for key in &pending_entry.keys {
+                    state.unlink(key, &pending_entry.cache_key);
+                }
+            }
+        }
+    }
+
+    /// Handle an invalidation message pushed by the server.
+    ///
+    /// Returns `false` if the message is not an invalidation message
+    pub fn handle_push_message(&self, resp_buf: &RespBuf) -> bool {
+        match resp_buf.to::<(&str, Option<Vec<BulkString>>)>() {
+            Ok(("invalidate", Some(keys))) => {
+                for key in keys {
+                    self.invalidate(&key);
+                }
+                true
+            }
+            Ok(("invalidate", None)) => {
+                self.flush();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the replies of the commands on a Redis key
+    pub fn invalidate(&self, key: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        let Some(cache_keys) = state.keys.remove(key) else {
+            return;
+        };
+
+        for cache_key in cache_keys {
+            state.pending.remove(&cache_key);
+            if state.remove_entry(&cache_key) {
+                self.invalidations.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Remove all the replies, e.g. after a reconnection
+    pub fn flush(&self) {
+        let mut state = self.state.lock().unwrap();
+        debug!("Flushing client side cache");
+        self.invalidations
+            .fetch_add(state.entries.len() as u64, Ordering::Relaxed);
+        *state = CacheState {
+            next_seq: state.next_seq,
+            database: self.initial_database,
+            ..Default::default()
+        };
+    }
+
+    /// Change the database of the next cached commands, when a `SELECT` is sent to the server
+    ///
+    /// Replies of the pending commands may come from either database and are not stored.
+    pub fn select(&self, database: usize) {
+        let mut state = self.state.lock().unwrap();
+        if state.database == database {
+            return;
+        }
+
+        debug!("Selecting database {database} in client side cache");
+        state.database = database;
+        let pending = std::mem::take(&mut state.pending);
+        let CacheState { entries, keys, .. } = &mut *state;
+        keys.retain(|_, cache_keys| {
+            cache_keys.retain(|cache_key| {
+                entries.contains_key(cache_key) || !pending.contains_key(cache_key)
+            });
+            !cache_keys.is_empty()
+        });
+    }
+
+    pub fn stats(&self) -> ClientSideCacheStats {
+        let state = self.state.lock().unwrap();
+        ClientSideCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: state.entries.len(),
+            bytes: state.bytes,
+        }
+    }
+
+    fn evict(&self, state: &mut CacheState) {
+        while state.entries.len() > self.config.max_entries
+            || (self.config.max_bytes > 0 && state.bytes > self.config.max_bytes)
+        {
+            let Some((_seq, cache_key)) = state.eviction_order.pop_first() else {
+                break;
+            };
+
+            state.remove_entry(&cache_key);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Keys of a command if the command can be cached
+    fn cacheable_keys(&self, command: &Command) -> Option<Vec<Vec<u8>>> {
+        let keys: Vec<Vec<u8>> = match key_spec(command)? {
+            KeySpec::First => vec![(&command.args).into_iter().next()?.to_vec()],
+            KeySpec::All => (&command.args).into_iter().map(|k| k.to_vec()).collect(),
+        };
+
+        if let ClientSideCacheMode::Broadcast { prefixes } = &self.config.mode {
+            if !prefixes.is_empty()
+                && !keys
+                    .iter()
+                    .all(|k| prefixes.iter().any(|p| k.starts_with(p.as_bytes())))
+            {
+                return None;
+            }
+        }
+
+        Some(keys)
+    }
+
+    /// Database, name and length-prefixed arguments of a command
+    ///
+    /// Invalidation messages do not carry the database of a key:
+    /// all the cache keys of the same Redis key are invalidated together, whatever their database.
+    fn cache_key(database: usize, command: &Command) -> Vec<u8> {
+        let mut cache_key = (database as u32).to_le_bytes().to_vec();
+        cache_key.extend_from_slice(command.name.as_bytes());
+        for arg in &command.args {
+            cache_key.extend_from_slice(&(arg.len() as u32).to_le_bytes());
+            cache_key.extend_from_slice(arg);
+        }
+        cache_key
+    }
+}
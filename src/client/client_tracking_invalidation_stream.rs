@@ -22,8 +22,9 @@ impl Stream for ClientTrackingInvalidationStream {
         match self.get_mut().receiver.poll_next_unpin(cx) {
             Poll::Ready(resp_buffer) => match resp_buffer {
                 Some(resp_buffer) => match resp_buffer {
-                    Ok(resp_buffer) => match resp_buffer.to::<(&str, Vec<String>)>() {
-                        Ok((_invalidate, keys)) => Poll::Ready(Some(keys)),
+                    Ok(resp_buffer) => match resp_buffer.to::<(&str, Option<Vec<String>>)>() {
+                        // null means that all the keys must be invalidated
+                        Ok((_invalidate, keys)) => Poll::Ready(Some(keys.unwrap_or_default())),
                         Err(_) => Poll::Ready(None),
                     },
                     Err(_) => Poll::Ready(None),
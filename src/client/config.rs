@@ -1,4 +1,8 @@
-use crate::{Error, Result};
+#[cfg(feature = "fault-injection")]
+use crate::client::FaultPlan;
+#[cfg(feature = "record-replay")]
+use crate::client::TrafficRecorder;
+use crate::{client::ClientSideCacheConfig, Error, Result};
 #[cfg(feature = "tls")]
 use native_tls::{Certificate, Identity, Protocol, TlsConnector, TlsConnectorBuilder};
 use std::{
@@ -19,6 +23,11 @@ const DEFAULT_AUTO_REMONITOR: bool = true;
 const DEFAULT_KEEP_ALIVE: Option<Duration> = None;
 const DEFAULT_NO_DELAY: bool = true;
 const DEFAULT_RETRY_ON_ERROR: bool = false;
+const DEFAULT_PUB_SUB_CAPACITY: usize = 0;
+const DEFAULT_MAX_BULK_SIZE: usize = 512 * 1024 * 1024;
+const DEFAULT_MAX_AGGREGATE_LEN: usize = 0;
+const DEFAULT_MAX_DEPTH: usize = 128;
+const DEFAULT_MAX_REPLY_SIZE: usize = 0;
 
 type Uri<'a> = (
     &'a str,
@@ -103,6 +112,52 @@ pub struct Config {
     pub retry_on_error: bool,
     /// Reconnection policy configuration (Constant, Linear or Exponential)
     pub reconnection: ReconnectionConfig,
+    /// Maximum number of messages buffered by each [`PubSubStream`](crate::client::PubSubStream)
+    /// or [`MonitorStream`](crate::client::MonitorStream), when the stream is consumed
+    /// slower than messages are received.
+    ///
+    /// If set to 0, the buffer is unbounded
+    ///
+    /// The default is 0
+    pub pub_sub_capacity: usize,
+    /// What to do when the buffer of a stream reaches [`pub_sub_capacity`](Config::pub_sub_capacity)
+    ///
+    /// The default is [`DropOldest`](PubSubOverflowPolicy::DropOldest)
+    pub pub_sub_overflow_policy: PubSubOverflowPolicy,
+    /// Enable a local cache of the replies of read-only commands like `GET`, `HGETALL` or `MGET`,
+    /// based on the server-assisted [client side caching](https://redis.io/docs/manual/client-side-caching/)
+    ///
+    /// The cache is invalidated by the messages of the server and flushed after each reconnection.
+    /// Only the commands sent with [`Client::send`](crate::client::Client::send)
+    /// are cached, not those of pipelines and transactions.
+    ///
+    /// The client tracking of the connection is enabled with the options of the cache:
+    /// [`client_tracking`](crate::commands::ConnectionCommands::client_tracking) commands
+    /// sent by the caller are not replayed after a reconnection.
+    ///
+    /// The default is `None`
+    pub client_side_cache: Option<ClientSideCacheConfig>,
+    /// Limits on the replies received from the server,
+    /// protecting the client against a malicious or buggy server or proxy.
+    ///
+    /// A reply exceeding these limits fails with an [`Error::Protocol`](crate::Error::Protocol)
+    /// and the connection is reset.
+    pub reply_limits: ReplyLimits,
+    /// Faults to inject in the connections, to test retry and failover code paths
+    ///
+    /// The plan is shared by all the clones of the config,
+    /// so faults can be added after the client is connected.
+    ///
+    /// This field is only available with the feature `fault-injection`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "fault-injection")))]
+    #[cfg(feature = "fault-injection")]
+    pub fault_plan: FaultPlan,
+    /// Records the command/reply stream of the connections to a file (default `None`)
+    ///
+    /// This field is only available with the feature `record-replay`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "record-replay")))]
+    #[cfg(feature = "record-replay")]
+    pub traffic_recorder: Option<TrafficRecorder>,
 }
 
 impl Default for Config {
@@ -123,6 +178,14 @@ impl Default for Config {
             no_delay: DEFAULT_NO_DELAY,
             retry_on_error: DEFAULT_RETRY_ON_ERROR,
             reconnection: Default::default(),
+            pub_sub_capacity: DEFAULT_PUB_SUB_CAPACITY,
+            pub_sub_overflow_policy: Default::default(),
+            client_side_cache: None,
+            reply_limits: Default::default(),
+            #[cfg(feature = "fault-injection")]
+            fault_plan: Default::default(),
+            #[cfg(feature = "record-replay")]
+            traffic_recorder: None,
         }
     }
 }
@@ -175,6 +238,7 @@ impl Config {
             Standalone,
             Sentinel,
             Cluster,
+            Replicated,
         }
 
         #[cfg(feature = "tls")]
@@ -189,6 +253,10 @@ impl Config {
             "rediss+cluster" | "rediss-cluster" => {
                 (Some(TlsConfig::default()), ServerType::Cluster)
             }
+            "redis+replicated" | "redis-replicated" => (None, ServerType::Replicated),
+            "rediss+replicated" | "rediss-replicated" => {
+                (Some(TlsConfig::default()), ServerType::Replicated)
+            }
             _ => {
                 return None;
             }
@@ -199,6 +267,7 @@ impl Config {
             "redis" => ServerType::Standalone,
             "redis+sentinel" | "redis-sentinel" => ServerType::Sentinel,
             "redis+cluster" | "redis-cluster" => ServerType::Cluster,
+            "redis+replicated" | "redis-replicated" => ServerType::Replicated,
             _ => {
                 return None;
             }
@@ -244,6 +313,18 @@ impl Config {
 
                     sentinel_config.username = query.remove("sentinel_username");
                     sentinel_config.password = query.remove("sentinel_password");
+
+                    if let Some(millis) = query.remove("sentinel_connect_timeout") {
+                        if let Ok(millis) = millis.parse::<u64>() {
+                            sentinel_config.connect_timeout = Some(Duration::from_millis(millis));
+                        }
+                    }
+
+                    if let Some(strategy) = query.remove("read_from_replicas") {
+                        if let Ok(strategy) = strategy.parse::<ReplicaSelectionStrategy>() {
+                            sentinel_config.read_from_replicas = Some(strategy);
+                        }
+                    }
                 }
 
                 ServerConfig::Sentinel(sentinel_config)
@@ -256,6 +337,14 @@ impl Config {
 
                 ServerConfig::Cluster(ClusterConfig { nodes })
             }
+            ServerType::Replicated => {
+                let nodes = hosts
+                    .iter()
+                    .map(|(host, port)| ((*host).to_owned(), *port))
+                    .collect::<Vec<_>>();
+
+                ServerConfig::Replicated { nodes }
+            }
         };
 
         let database = match path_segments.next() {
@@ -324,13 +413,49 @@ impl Config {
                     config.retry_on_error = retry_on_error;
                 }
             }
+
+            if let Some(pub_sub_capacity) = query.remove("pub_sub_capacity") {
+                if let Ok(pub_sub_capacity) = pub_sub_capacity.parse::<usize>() {
+                    config.pub_sub_capacity = pub_sub_capacity;
+                }
+            }
+
+            if let Some(policy) = query.remove("pub_sub_overflow_policy") {
+                if let Ok(policy) = policy.parse::<PubSubOverflowPolicy>() {
+                    config.pub_sub_overflow_policy = policy;
+                }
+            }
+
+            if let Some(max_bulk_size) = query.remove("max_bulk_size") {
+                if let Ok(max_bulk_size) = max_bulk_size.parse::<usize>() {
+                    config.reply_limits.max_bulk_size = max_bulk_size;
+                }
+            }
+
+            if let Some(max_aggregate_len) = query.remove("max_aggregate_len") {
+                if let Ok(max_aggregate_len) = max_aggregate_len.parse::<usize>() {
+                    config.reply_limits.max_aggregate_len = max_aggregate_len;
+                }
+            }
+
+            if let Some(max_depth) = query.remove("max_depth") {
+                if let Ok(max_depth) = max_depth.parse::<usize>() {
+                    config.reply_limits.max_depth = max_depth;
+                }
+            }
+
+            if let Some(max_reply_size) = query.remove("max_reply_size") {
+                if let Ok(max_reply_size) = max_reply_size.parse::<usize>() {
+                    config.reply_limits.max_reply_size = max_reply_size;
+                }
+            }
         }
 
         Some(config)
     }
 
     /// break down an uri in a tuple (scheme, username, password, hosts, path_segments)
-    fn break_down_uri(uri: &str) -> Option<Uri> {
+    fn break_down_uri(uri: &str) -> Option<Uri<'_>> {
         let end_of_scheme = match uri.find("://") {
             Some(index) => index,
             None => {
@@ -436,12 +561,14 @@ impl Display for Config {
                 ServerConfig::Standalone { host: _, port: _ } => f.write_str("rediss://")?,
                 ServerConfig::Sentinel(_) => f.write_str("rediss+sentinel://")?,
                 ServerConfig::Cluster(_) => f.write_str("rediss+cluster://")?,
+                ServerConfig::Replicated { .. } => f.write_str("rediss+replicated://")?,
             }
         } else {
             match &self.server {
                 ServerConfig::Standalone { host: _, port: _ } => f.write_str("redis://")?,
                 ServerConfig::Sentinel(_) => f.write_str("redis+sentinel://")?,
                 ServerConfig::Cluster(_) => f.write_str("redis+cluster://")?,
+                ServerConfig::Replicated { .. } => f.write_str("redis+replicated://")?,
             }
         }
 
@@ -450,6 +577,7 @@ impl Display for Config {
             ServerConfig::Standalone { host: _, port: _ } => f.write_str("redis://")?,
             ServerConfig::Sentinel(_) => f.write_str("redis+sentinel://")?,
             ServerConfig::Cluster(_) => f.write_str("redis+cluster://")?,
+            ServerConfig::Replicated { .. } => f.write_str("redis+replicated://")?,
         }
 
         if let Some(username) = &self.username {
@@ -476,6 +604,10 @@ impl Display for Config {
                 wait_between_failures: _,
                 password: _,
                 username: _,
+                read_from_replicas: _,
+                connect_timeout: _,
+                #[cfg(feature = "tls")]
+                    tls_config: _,
             }) => {
                 f.write_str(
                     &instances
@@ -487,7 +619,7 @@ impl Display for Config {
                 f.write_char('/')?;
                 f.write_str(service_name)?;
             }
-            ServerConfig::Cluster(ClusterConfig { nodes }) => {
+            ServerConfig::Cluster(ClusterConfig { nodes }) | ServerConfig::Replicated { nodes } => {
                 f.write_str(
                     &nodes
                         .iter()
@@ -589,12 +721,88 @@ impl Display for Config {
             f.write_fmt(format_args!("retry_on_error={}", self.retry_on_error))?;
         }
 
+        if self.pub_sub_capacity != DEFAULT_PUB_SUB_CAPACITY {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!("pub_sub_capacity={}", self.pub_sub_capacity))?;
+        }
+
+        if self.pub_sub_overflow_policy != PubSubOverflowPolicy::default() {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!(
+                "pub_sub_overflow_policy={}",
+                self.pub_sub_overflow_policy
+            ))?;
+        }
+
+        if self.reply_limits.max_bulk_size != DEFAULT_MAX_BULK_SIZE {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!(
+                "max_bulk_size={}",
+                self.reply_limits.max_bulk_size
+            ))?;
+        }
+
+        if self.reply_limits.max_aggregate_len != DEFAULT_MAX_AGGREGATE_LEN {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!(
+                "max_aggregate_len={}",
+                self.reply_limits.max_aggregate_len
+            ))?;
+        }
+
+        if self.reply_limits.max_depth != DEFAULT_MAX_DEPTH {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!("max_depth={}", self.reply_limits.max_depth))?;
+        }
+
+        if self.reply_limits.max_reply_size != DEFAULT_MAX_REPLY_SIZE {
+            if !query_separator {
+                query_separator = true;
+                f.write_char('?')?;
+            } else {
+                f.write_char('&')?;
+            }
+            f.write_fmt(format_args!(
+                "max_reply_size={}",
+                self.reply_limits.max_reply_size
+            ))?;
+        }
+
         if let ServerConfig::Sentinel(SentinelConfig {
             instances: _,
             service_name: _,
             wait_between_failures: wait_beetween_failures,
             password,
             username,
+            read_from_replicas,
+            connect_timeout,
+            #[cfg(feature = "tls")]
+                tls_config: _,
         }) = &self.server
         {
             let wait_between_failures = wait_beetween_failures.as_millis() as u64;
@@ -621,6 +829,7 @@ impl Display for Config {
             }
             if let Some(password) = password {
                 if !query_separator {
+                    query_separator = true;
                     f.write_char('?')?;
                 } else {
                     f.write_char('&')?;
@@ -628,6 +837,26 @@ impl Display for Config {
                 f.write_str("sentinel_password=")?;
                 f.write_str(password)?;
             }
+            if let Some(connect_timeout) = connect_timeout {
+                if !query_separator {
+                    query_separator = true;
+                    f.write_char('?')?;
+                } else {
+                    f.write_char('&')?;
+                }
+                f.write_fmt(format_args!(
+                    "sentinel_connect_timeout={}",
+                    connect_timeout.as_millis()
+                ))?;
+            }
+            if let Some(read_from_replicas) = read_from_replicas {
+                if !query_separator {
+                    f.write_char('?')?;
+                } else {
+                    f.write_char('&')?;
+                }
+                f.write_fmt(format_args!("read_from_replicas={read_from_replicas}"))?;
+            }
         }
 
         Ok(())
@@ -648,6 +877,18 @@ pub enum ServerConfig {
     Sentinel(SentinelConfig),
     /// Configuration for connecting to a Redis [`Cluster`](https://redis.io/docs/management/scaling/)
     Cluster(ClusterConfig),
+    /// Configuration for connecting to a master and its replicas,
+    /// without Sentinel nor Cluster
+    ///
+    /// The role of each node is discovered with the [`ROLE`](https://redis.io/commands/role/) command.
+    /// Read-only commands are sent to replicas in turn, other commands to the master.
+    /// Replicas which report another master than the discovered one are ignored.
+    /// The topology is discovered again when the master replies with a `READONLY` error
+    /// or when a connection is closed.
+    Replicated {
+        /// An array of `(host, port)` tuples for each known node, master or replica.
+        nodes: Vec<(String, u16)>,
+    },
 }
 
 impl Default for ServerConfig {
@@ -663,6 +904,10 @@ impl Default for ServerConfig {
 #[derive(Debug, Clone)]
 pub struct SentinelConfig {
     /// An array of `(host, port)` tuples for each known sentinel instance.
+    ///
+    /// This list is only used to bootstrap the connection: once connected,
+    /// the list is refreshed with `SENTINEL SENTINELS` and the responsive instance
+    /// is moved to the front of the list.
     pub instances: Vec<(String, u16)>,
 
     /// The service name
@@ -676,6 +921,27 @@ pub struct SentinelConfig {
 
     /// Sentinel password
     pub password: Option<String>,
+
+    /// If set, replicas of the service are discovered through Sentinel
+    /// and read-only commands are sent to one of them, selected with this strategy.
+    ///
+    /// Other commands, and all the commands of a transaction, are still sent to the master.
+    /// In a pipeline, read-only commands following a command which is not read-only
+    /// are sent to the master too, to read the writes of the pipeline.
+    /// Replicas flagged `s_down`, `o_down` or `disconnected` by Sentinel are ignored.
+    /// When no replica is available, read-only commands are sent to the master.
+    pub read_from_replicas: Option<ReplicaSelectionStrategy>,
+
+    /// The time to attempt a connection to a Sentinel instance before timing out.
+    ///
+    /// If not set, [`Config::connect_timeout`] is used.
+    pub connect_timeout: Option<Duration>,
+
+    /// TLS configuration for connections to Sentinel instances
+    /// (default [`SentinelTlsConfig::Inherit`]).
+    #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+    #[cfg(feature = "tls")]
+    pub tls_config: SentinelTlsConfig,
 }
 
 impl Default for SentinelConfig {
@@ -686,6 +952,153 @@ impl Default for SentinelConfig {
             wait_between_failures: Duration::from_millis(DEFAULT_WAIT_BETWEEN_FAILURES),
             password: None,
             username: None,
+            read_from_replicas: None,
+            connect_timeout: None,
+            #[cfg(feature = "tls")]
+            tls_config: SentinelTlsConfig::Inherit,
+        }
+    }
+}
+
+/// TLS configuration for connections to Sentinel instances
+///
+/// See [`SentinelConfig::tls_config`]
+#[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, Default)]
+pub enum SentinelTlsConfig {
+    /// Same configuration as the Redis servers: [`Config::tls_config`]
+    #[default]
+    Inherit,
+    /// Non secure TCP connections, even if the Redis servers are reached with TLS
+    Disabled,
+    /// Dedicated TLS configuration
+    Enabled(TlsConfig),
+}
+
+/// Limits on the replies received from the server
+///
+/// For each limit, `0` means unlimited.
+/// Even unlimited, lengths that cannot be the ones of a frame held in memory
+/// (e.g. a map header whose number of keys and values overflows `usize`)
+/// are rejected with a protocol error.
+///
+/// See [`Config::reply_limits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplyLimits {
+    /// Maximum length in bytes of a bulk string, a verbatim string, a blob error
+    /// or a streamed string (all its chunks included).
+    ///
+    /// The default is 512 MB, the maximum size of a Redis string
+    pub max_bulk_size: usize,
+    /// Maximum number of elements of an array, a set, a push message,
+    /// or of entries of a map.
+    ///
+    /// The default is `0` (unlimited)
+    pub max_aggregate_len: usize,
+    /// Maximum nesting depth of the aggregates of a reply.
+    ///
+    /// The default is 128
+    pub max_depth: usize,
+    /// Maximum size in bytes of a whole reply.
+    ///
+    /// The default is `0` (unlimited)
+    pub max_reply_size: usize,
+}
+
+impl Default for ReplyLimits {
+    fn default() -> Self {
+        Self {
+            max_bulk_size: DEFAULT_MAX_BULK_SIZE,
+            max_aggregate_len: DEFAULT_MAX_AGGREGATE_LEN,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_reply_size: DEFAULT_MAX_REPLY_SIZE,
+        }
+    }
+}
+
+/// Policy applied when the buffer of a [`PubSubStream`](crate::client::PubSubStream)
+/// or a [`MonitorStream`](crate::client::MonitorStream) is full
+///
+/// See [`Config::pub_sub_capacity`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PubSubOverflowPolicy {
+    /// The oldest buffered message is dropped to make room for the new one
+    #[default]
+    DropOldest,
+    /// The new message is dropped
+    DropNewest,
+    /// The new message is dropped and the stream ends with an error
+    /// once the buffered messages have been consumed
+    Disconnect,
+    /// The connection stops reading until the consumer makes room for the new message.
+    ///
+    /// Be aware that all the callers sharing the connection are blocked meanwhile
+    Backpressure,
+}
+
+impl FromStr for PubSubOverflowPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "drop_oldest" => Ok(Self::DropOldest),
+            "drop_newest" => Ok(Self::DropNewest),
+            "disconnect" => Ok(Self::Disconnect),
+            "backpressure" => Ok(Self::Backpressure),
+            _ => Err(Error::Config(format!(
+                "Unknown pub/sub overflow policy `{s}`"
+            ))),
+        }
+    }
+}
+
+impl Display for PubSubOverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DropOldest => f.write_str("drop_oldest"),
+            Self::DropNewest => f.write_str("drop_newest"),
+            Self::Disconnect => f.write_str("disconnect"),
+            Self::Backpressure => f.write_str("backpressure"),
+        }
+    }
+}
+
+/// Strategy to select the replica a read-only command is sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaSelectionStrategy {
+    /// A random replica is selected for each command
+    Random,
+    /// Replicas are selected in turn
+    RoundRobin,
+    /// The replica with the highest replication offset, i.e. the lowest lag, is selected.
+    ///
+    /// Replication offsets are read at connection time,
+    /// then refreshed with `INFO replication` at most once per second while commands are sent.
+    LowestLag,
+}
+
+impl FromStr for ReplicaSelectionStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "random" => Ok(Self::Random),
+            "round_robin" => Ok(Self::RoundRobin),
+            "lowest_lag" => Ok(Self::LowestLag),
+            _ => Err(Error::Config(format!(
+                "Unknown replica selection strategy `{s}`"
+            ))),
+        }
+    }
+}
+
+impl Display for ReplicaSelectionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Random => f.write_str("random"),
+            Self::RoundRobin => f.write_str("round_robin"),
+            Self::LowestLag => f.write_str("lowest_lag"),
         }
     }
 }
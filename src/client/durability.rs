@@ -0,0 +1,53 @@
+use crate::{
+    resp::{cmd, Command, RespBuf},
+    Error, Result,
+};
+
+/// Durability guarantee of write commands, checked with
+/// [`WAIT`](https://redis.io/commands/wait/) or [`WAITAOF`](https://redis.io/commands/waitaof/)
+///
+/// See [`PreparedCommand::durable`](crate::client::PreparedCommand::durable)
+/// and [`Pipeline::durable`](crate::client::Pipeline::durable)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Durability {
+    /// Number of replicas which must acknowledge the writes
+    pub num_replicas: usize,
+    /// Number of local AOF fsync which must acknowledge the writes (`0` or `1`).
+    ///
+    /// If greater than `0`, `WAITAOF` is used instead of `WAIT`
+    pub num_local_aof: usize,
+    /// Timeout in milliseconds, `0` to block forever
+    pub timeout: u64,
+}
+
+impl Durability {
+    /// Command to send after the write commands to check the durability guarantee
+    pub(crate) fn command(&self) -> Command {
+        if self.num_local_aof > 0 {
+            cmd("WAITAOF")
+                .arg(self.num_local_aof)
+                .arg(self.num_replicas)
+                .arg(self.timeout)
+        } else {
+            cmd("WAIT").arg(self.num_replicas).arg(self.timeout)
+        }
+    }
+
+    /// Check the result of the command returned by [`command`](Durability::command)
+    pub(crate) fn check(&self, result: &RespBuf) -> Result<()> {
+        let (num_local_aof, num_replicas) = if self.num_local_aof > 0 {
+            result.to::<(usize, usize)>()?
+        } else {
+            (0, result.to::<usize>()?)
+        };
+
+        if num_local_aof < self.num_local_aof || num_replicas < self.num_replicas {
+            return Err(Error::Client(format!(
+                "Durability not reached: {num_replicas}/{} replica(s) and {num_local_aof}/{} local AOF acknowledged the write",
+                self.num_replicas, self.num_local_aof
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{resp::Command, RedisError, RedisErrorKind};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Fault injected by a [`FaultPlan`] when a matching command is sent
+#[derive(Debug, Clone)]
+pub enum Fault {
+    /// The connection is closed before the command is written:
+    /// the command is not executed by the server
+    KillBeforeWrite,
+    /// The connection is closed right after the command is written:
+    /// the command is executed by the server but its reply is lost
+    KillAfterWrite,
+    /// The reply of the command is received after the given delay
+    DelayRead(Duration),
+    /// The reply of the command is dropped: the caller only gets a timeout
+    DropReply,
+    /// The server replies with the given error instead of executing the command
+    Error(RedisError),
+}
+
+impl Fault {
+    /// `-LOADING` error, returned by a server loading its dataset in memory
+    #[must_use]
+    pub fn loading() -> Self {
+        Self::Error(RedisError {
+            kind: RedisErrorKind::Loading,
+            description: "Redis is loading the dataset in memory".to_owned(),
+        })
+    }
+
+    /// `-TRYAGAIN` error, returned by a cluster node during a resharding
+    #[must_use]
+    pub fn try_again() -> Self {
+        Self::Error(RedisError {
+            kind: RedisErrorKind::TryAgain,
+            description: "Multiple keys request during rehashing of slot".to_owned(),
+        })
+    }
+
+    /// `-MOVED` redirect to the given address
+    #[must_use]
+    pub fn moved(hash_slot: u16, address: (impl Into<String>, u16)) -> Self {
+        Self::Error(RedisError {
+            kind: RedisErrorKind::Moved {
+                hash_slot,
+                address: (address.0.into(), address.1),
+            },
+            description: String::new(),
+        })
+    }
+
+    /// `-ASK` redirect to the given address
+    #[must_use]
+    pub fn ask(hash_slot: u16, address: (impl Into<String>, u16)) -> Self {
+        Self::Error(RedisError {
+            kind: RedisErrorKind::Ask {
+                hash_slot,
+                address: (address.0.into(), address.1),
+            },
+            description: String::new(),
+        })
+    }
+}
+
+/// Rule of a [`FaultPlan`]: which fault to inject, on which commands, and how many times
+#[derive(Debug, Clone)]
+pub struct FaultRule {
+    fault: Fault,
+    command: Option<String>,
+    times: usize,
+}
+
+impl FaultRule {
+    /// Creates a new rule injecting the fault once, on the next command sent
+    #[must_use]
+    pub fn new(fault: Fault) -> Self {
+        Self {
+            fault,
+            command: None,
+            times: 1,
+        }
+    }
+
+    /// Only inject the fault on the commands with the given name (case insensitive)
+    ///
+    /// Sub commands are not taken into account: `CONFIG` matches `CONFIG GET` and `CONFIG SET`.
+    #[must_use]
+    pub fn command(mut self, name: &str) -> Self {
+        self.command = Some(name.to_ascii_uppercase());
+        self
+    }
+
+    /// Number of times the fault is injected (default `1`)
+    ///
+    /// If set to 0, the fault is injected on all the matching commands
+    /// until the plan is [cleared](FaultPlan::clear).
+    #[must_use]
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+
+    // `Option::is_none_or` requires Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    fn matches(&self, command: &Command) -> bool {
+        self.command
+            .as_deref()
+            .map_or(true, |name| name.eq_ignore_ascii_case(command.name))
+    }
+}
+
+/// Plan of the faults to inject in the connections of a client, to test retry and failover code paths
+///
+/// The plan is shared: faults can be added to the plan of a connected client,
+/// through [`Config::fault_plan`](crate::client::Config::fault_plan)
+/// or [`Client::fault_plan`](crate::client::Client::fault_plan).
+///
+/// Faults are injected on the commands sent by the caller,
+/// not on the commands sent internally during the connection handshake.
+/// Faults on replies are applied to the replies in the order of the commands,
+/// ignoring push messages.
+///
+/// This struct is only available with the feature `fault-injection`.
+///
+/// # Example
+/// ```no_run
+/// use rustis::{
+///     client::{Client, Config, Fault, FaultRule, IntoConfig},
+///     commands::StringCommands,
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let config = "127.0.0.1:6379".into_config()?;
+///     let client = Client::connect(config).await?;
+///
+///     // the first GET is retried after a disconnection
+///     client
+///         .fault_plan()
+///         .add(FaultRule::new(Fault::KillBeforeWrite).command("GET"));
+///     let _value: Option<String> = client.get("key").retry_on_error(true).await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FaultPlan {
+    rules: Arc<Mutex<Vec<FaultRule>>>,
+}
+
+impl FaultPlan {
+    /// Adds a rule to the plan
+    ///
+    /// When several rules match a command, the first added one wins.
+    pub fn add(&self, rule: FaultRule) {
+        self.rules.lock().unwrap().push(rule);
+    }
+
+    /// Removes all the rules of the plan
+    pub fn clear(&self) {
+        self.rules.lock().unwrap().clear();
+    }
+
+    /// Returns `true` if no fault remains to be injected
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.lock().unwrap().is_empty()
+    }
+
+    /// Returns the fault to inject on a command about to be sent, if any
+    pub(crate) fn take_fault(&self, command: &Command) -> Option<Fault> {
+        let mut rules = self.rules.lock().unwrap();
+        let idx = rules.iter().position(|rule| rule.matches(command))?;
+        let rule = &mut rules[idx];
+        let fault = rule.fault.clone();
+
+        match rule.times {
+            0 => (),
+            1 => {
+                rules.remove(idx);
+            }
+            _ => rule.times -= 1,
+        }
+
+        Some(fault)
+    }
+}
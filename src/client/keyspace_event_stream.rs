@@ -0,0 +1,283 @@
+use crate::{
+    client::{Client, PubSubMessage, PubSubStream},
+    commands::ServerCommands,
+    Error, Result,
+};
+use futures_channel::oneshot;
+use futures_util::{FutureExt, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Filter of the keyspace events streamed by [`Client::keyspace_events`]
+///
+/// # See Also
+/// [Redis keyspace notifications](https://redis.io/docs/manual/keyspace-notifications/)
+#[derive(Debug, Clone, Default)]
+pub struct KeyspaceEventsFilter {
+    key_pattern: Option<String>,
+    database: Option<usize>,
+    notify_keyspace_events: Option<String>,
+}
+
+impl KeyspaceEventsFilter {
+    /// Only stream events of the keys matching this glob-style pattern (default `*`)
+    #[must_use]
+    pub fn key_pattern(mut self, key_pattern: impl Into<String>) -> Self {
+        self.key_pattern = Some(key_pattern.into());
+        self
+    }
+
+    /// Stream events of this database instead of the database
+    /// the client is connected to
+    #[must_use]
+    pub fn database(mut self, database: usize) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Set the `notify-keyspace-events` server configuration to these flags
+    /// when the stream is created and each time the client reconnects (e.g. `KEA`).
+    ///
+    /// The flags must enable keyspace events (`K`).
+    /// When not set, the server configuration is left untouched.
+    ///
+    /// # See Also
+    /// [Configuration](https://redis.io/docs/manual/keyspace-notifications/#configuration)
+    #[must_use]
+    pub fn notify_keyspace_events(mut self, flags: impl Into<String>) -> Self {
+        self.notify_keyspace_events = Some(flags.into());
+        self
+    }
+}
+
+/// Kind of a [`KeyEvent`]
+///
+/// # See Also
+/// [Events generated by different commands](https://redis.io/docs/manual/keyspace-notifications/#events-generated-by-different-commands)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyEventKind {
+    /// `SET` and its variants
+    Set,
+    /// `DEL` and `UNLINK`
+    Del,
+    /// `EXPIRE` and its variants, or `SET` with an expiration
+    Expire,
+    /// Key expired by the server
+    Expired,
+    /// Key evicted by the `maxmemory` policy
+    Evicted,
+    /// Key added to the database
+    New,
+    /// `PERSIST`, or a write command removing the expiration of the key
+    Persist,
+    /// Source key of `RENAME` or `RENAMENX`
+    RenameFrom,
+    /// Destination key of `RENAME` or `RENAMENX`
+    RenameTo,
+    /// Source key of `MOVE`
+    MoveFrom,
+    /// Destination key of `MOVE`
+    MoveTo,
+    /// Destination key of `COPY`
+    CopyTo,
+    /// `RESTORE`
+    Restore,
+    /// `APPEND`
+    Append,
+    /// `INCR`, `DECR`, `INCRBY` or `DECRBY`
+    IncrBy,
+    /// `INCRBYFLOAT`
+    IncrByFloat,
+    /// `SETRANGE`
+    SetRange,
+    /// `LPUSH` or `LPUSHX`
+    LPush,
+    /// `RPUSH` or `RPUSHX`
+    RPush,
+    /// `LPOP` or the left pop of a blocking or move command
+    LPop,
+    /// `RPOP` or the right pop of a blocking or move command
+    RPop,
+    /// `LINSERT`
+    LInsert,
+    /// `LSET`
+    LSet,
+    /// `LREM`
+    LRem,
+    /// `LTRIM`
+    LTrim,
+    /// `HSET`, `HSETNX` or `HMSET`
+    HSet,
+    /// `HINCRBY`
+    HIncrBy,
+    /// `HINCRBYFLOAT`
+    HIncrByFloat,
+    /// `HDEL`
+    HDel,
+    /// `SADD`
+    SAdd,
+    /// `SREM`, or `SMOVE` on the source key
+    SRem,
+    /// `SPOP`
+    SPop,
+    /// `ZADD`
+    ZAdd,
+    /// `ZINCRBY`
+    ZIncr,
+    /// `ZREM`
+    ZRem,
+    /// `XADD`
+    XAdd,
+    /// `XDEL`
+    XDel,
+    /// `XTRIM`, or `XADD` with trimming
+    XTrim,
+    /// Any other event, with its name as sent by the server
+    Other(String),
+}
+
+impl From<&str> for KeyEventKind {
+    fn from(event: &str) -> Self {
+        match event {
+            "set" => Self::Set,
+            "del" => Self::Del,
+            "expire" => Self::Expire,
+            "expired" => Self::Expired,
+            "evicted" => Self::Evicted,
+            "new" => Self::New,
+            "persist" => Self::Persist,
+            "rename_from" => Self::RenameFrom,
+            "rename_to" => Self::RenameTo,
+            "move_from" => Self::MoveFrom,
+            "move_to" => Self::MoveTo,
+            "copy_to" => Self::CopyTo,
+            "restore" => Self::Restore,
+            "append" => Self::Append,
+            "incrby" => Self::IncrBy,
+            "incrbyfloat" => Self::IncrByFloat,
+            "setrange" => Self::SetRange,
+            "lpush" => Self::LPush,
+            "rpush" => Self::RPush,
+            "lpop" => Self::LPop,
+            "rpop" => Self::RPop,
+            "linsert" => Self::LInsert,
+            "lset" => Self::LSet,
+            "lrem" => Self::LRem,
+            "ltrim" => Self::LTrim,
+            "hset" => Self::HSet,
+            "hincrby" => Self::HIncrBy,
+            "hincrbyfloat" => Self::HIncrByFloat,
+            "hdel" => Self::HDel,
+            "sadd" => Self::SAdd,
+            "srem" => Self::SRem,
+            "spop" => Self::SPop,
+            "zadd" => Self::ZAdd,
+            "zincr" => Self::ZIncr,
+            "zrem" => Self::ZRem,
+            "xadd" => Self::XAdd,
+            "xdel" => Self::XDel,
+            "xtrim" => Self::XTrim,
+            _ => Self::Other(event.to_owned()),
+        }
+    }
+}
+
+/// Keyspace event streamed by [`Client::keyspace_events`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// Database of the key
+    pub db: usize,
+    /// Key targeted by the event
+    pub key: Vec<u8>,
+    /// Kind of event
+    pub kind: KeyEventKind,
+}
+
+impl KeyEvent {
+    /// Parse a message received on the channel `__keyspace@<db>__:<key>`
+    pub(crate) fn from_message(message: &PubSubMessage) -> Result<Self> {
+        let parse = || -> Option<Self> {
+            let suffix = message.channel.strip_prefix(b"__keyspace@")?;
+            let separator = memchr::memmem::find(suffix, b"__:")?;
+            let db = std::str::from_utf8(&suffix[..separator])
+                .ok()?
+                .parse()
+                .ok()?;
+            let key = suffix[separator + 3..].to_vec();
+            let kind = std::str::from_utf8(&message.payload).ok()?.into();
+            Some(Self { db, key, kind })
+        };
+
+        parse().ok_or_else(|| {
+            Error::Client(format!(
+                "Cannot parse keyspace event from channel `{}`",
+                String::from_utf8_lossy(&message.channel)
+            ))
+        })
+    }
+}
+
+/// Stream of [`KeyEvent`] built with [`Client::keyspace_events`]
+///
+/// The subscription is cancelled when the stream is dropped or closed
+pub struct KeyspaceEventStream {
+    inner: PubSubStream,
+    /// stops the task applying `notify-keyspace-events` after a reconnection
+    _stop_sender: Option<oneshot::Sender<()>>,
+}
+
+impl KeyspaceEventStream {
+    pub(crate) async fn new(client: &Client, filter: KeyspaceEventsFilter) -> Result<Self> {
+        let stop_sender = match filter.notify_keyspace_events {
+            Some(flags) => {
+                client
+                    .config_set([("notify-keyspace-events", flags.clone())])
+                    .await?;
+                // the subscription is restored by the client after a reconnection,
+                // but not the server configuration
+                Some(
+                    client.spawn_on_reconnect("notify-keyspace-events", move |client| {
+                        let flags = flags.clone();
+                        async move { client.config_set([("notify-keyspace-events", flags)]).await }
+                            .boxed()
+                    }),
+                )
+            }
+            None => None,
+        };
+
+        let pattern = format!(
+            "__keyspace@{}__:{}",
+            filter.database.unwrap_or(client.database()),
+            filter.key_pattern.as_deref().unwrap_or("*")
+        );
+
+        let mut inner = client.create_pub_sub();
+        inner.psubscribe(pattern).await?;
+
+        Ok(Self {
+            inner,
+            _stop_sender: stop_sender,
+        })
+    }
+
+    /// Cancel the subscription and close the stream
+    pub async fn close(self) -> Result<()> {
+        self.inner.close().await
+    }
+}
+
+impl Stream for KeyspaceEventStream {
+    type Item = Result<KeyEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.get_mut().inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(message))) => Poll::Ready(Some(KeyEvent::from_message(&message))),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
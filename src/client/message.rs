@@ -4,7 +4,7 @@ use smallvec::SmallVec;
 use crate::{
     network::{ResultSender, ResultsSender},
     resp::Command,
-    Error, PubSubSender, PushSender, RetryReason,
+    Error, PubSubSender, PushSender, RetryReason, SentinelEventSender,
 };
 
 #[cfg(debug_assertions)]
@@ -144,6 +144,7 @@ pub(crate) struct Message {
     pub commands: Commands,
     pub pub_sub_senders: Option<Vec<(Vec<u8>, PubSubSender)>>,
     pub push_sender: Option<PushSender>,
+    pub sentinel_event_sender: Option<SentinelEventSender>,
     pub retry_reasons: Option<SmallVec<[RetryReason; 10]>>,
     pub retry_on_error: bool,
     #[cfg(debug_assertions)]
@@ -158,6 +159,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: None,
             push_sender: None,
+            sentinel_event_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -171,6 +173,7 @@ impl Message {
             commands: Commands::Single(command, None),
             pub_sub_senders: None,
             push_sender: None,
+            sentinel_event_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -188,6 +191,7 @@ impl Message {
             commands: Commands::Batch(commands, results_sender),
             pub_sub_senders: None,
             push_sender: None,
+            sentinel_event_sender: None,
             retry_reasons: None,
             retry_on_error,
             #[cfg(debug_assertions)]
@@ -205,6 +209,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: Some(pub_sub_senders),
             push_sender: None,
+            sentinel_event_sender: None,
             retry_reasons: None,
             retry_on_error: true,
             #[cfg(debug_assertions)]
@@ -218,6 +223,7 @@ impl Message {
             commands: Commands::Single(command, Some(result_sender)),
             pub_sub_senders: None,
             push_sender: Some(push_sender),
+            sentinel_event_sender: None,
             retry_reasons: None,
             retry_on_error: true,
             #[cfg(debug_assertions)]
@@ -231,6 +237,21 @@ impl Message {
             commands: Commands::None,
             pub_sub_senders: None,
             push_sender: Some(push_sender),
+            sentinel_event_sender: None,
+            retry_reasons: None,
+            retry_on_error: false,
+            #[cfg(debug_assertions)]
+            message_seq: MESSAGE_SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    #[inline(always)]
+    pub fn sentinel_events(sentinel_event_sender: SentinelEventSender) -> Self {
+        Message {
+            commands: Commands::None,
+            pub_sub_senders: None,
+            push_sender: None,
+            sentinel_event_sender: Some(sentinel_event_sender),
             retry_reasons: None,
             retry_on_error: false,
             #[cfg(debug_assertions)]
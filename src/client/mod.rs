@@ -61,16 +61,13 @@ the [`Client`] instance through which the [`watch`](crate::commands::Transaction
 
 ### Managing multiplexed subscriptions
 
-Even if the [`subscribe`][crate::commands::PubSubCommands::subscribe] monopolize the whole connection,
-it is still possible to use it in a multiplexed [`Client`].
+Because **rustis** speaks [RESP3](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md),
+the subscribing mode of Redis does not monopolize the connection:
+pub/sub messages are delivered as push frames, interleaved with the replies to regular commands.
 
-Indeed the subscribing mode of Redis still allows to share the connection between multiple clients,
-at the only condition that this connection is dedicated to subscriptions.
-
-In a Web application that requires subscriptions and regualar commands, the prefered solution
-would be to connect two multiplexed clients to the Redis server:
-* 1 for the subscriptions
-* 1 for the regular commands
+Consequently, subscriptions and regular commands can share the same multiplexed [`Client`]:
+push frames are routed to the matching [`PubSubStream`]s, while regular replies are still
+sent back to the callers of the regular commands.
 
 ### See also
 [Multiplexing Explained](https://redis.com/blog/multiplexing-explained/)
@@ -87,8 +84,7 @@ use rustis::{
 #[cfg_attr(feature = "async-std-runtime", async_std::main)]
 async fn main() -> Result<()> {
     let config = "127.0.0.1:6379".into_config()?;
-    let regular_client1 = Client::connect(config.clone()).await?;
-    let pub_sub_client = Client::connect(config).await?;
+    let regular_client1 = Client::connect(config).await?;
 
     regular_client1.flushdb(FlushingMode::Sync).await?;
 
@@ -101,8 +97,10 @@ async fn main() -> Result<()> {
     let value: String = regular_client2.get("key").await?;
     println!("value: {value:?}");
 
-    // use 2nd connection to manager subscriptions
-    let pub_sub_stream = pub_sub_client.subscribe("my_channel").await?;
+    // subscriptions share the same connection with regular commands
+    let pub_sub_stream = regular_client2.subscribe("my_channel").await?;
+    let value: String = regular_client1.get("key").await?;
+    println!("value: {value:?}");
     pub_sub_stream.close().await?;
 
     Ok(())
@@ -157,7 +155,7 @@ async fn main() -> Result<()> {
 A [`Client`] instance can be configured with the [`Config`] struct:
 * Authentication
 * [`TlsConfig`]
-* [`ServerConfig`] (Standalone, Sentinel or Cluster)
+* [`ServerConfig`] (Standalone, Sentinel, Cluster or Replicated)
 
 [`IntoConfig`] is a convenient trait to convert more known types to a [`Config`] instance:
 * &[`str`](https://doc.rust-lang.org/std/primitive.str.html): host and port separated by a colon
@@ -181,11 +179,18 @@ redis|rediss://[[<username>]:<password>@]<host>[:<port>][/<database>]
 redis|rediss[+cluster]://[[<username>]:<password>@]<host1>[:<port1>][,<host2>:[<port2>][,<hostN>:[<portN>]]]
 ```
 
+### Replicated
+
+```text
+redis|rediss[+replicated]://[[<username>]:<password>@]<host1>[:<port1>][,<host2>:[<port2>][,<hostN>:[<portN>]]]
+```
+
 ### Sentinel
 
 ```text
 redis|rediss[+sentinel]://[[<username>]:<password>@]<host>[:<port>]/<service>[/<database>]
-                          [?wait_between_failures=<250>[&sentinel_username=<username>][&sentinel_password=<password>]]
+                          [?wait_between_failures=<250>[&sentinel_username=<username>][&sentinel_password=<password>]
+                          [&sentinel_connect_timeout=<ms>][&read_from_replicas=<random|round_robin|lowest_lag>]]
 ```
 
 `service` is the required name of the sentinel service
@@ -198,13 +203,15 @@ The URL scheme is used to detect the server type:
 * `rediss+sentinel://` or `rediss-sentinel://` - Secure (TSL) TCP connection to a Redis sentinel network
 * `redis+cluster://` or `redis-cluster://` - Non secure TCP connection to a Redis cluster
 * `rediss+cluster://` or `rediss-cluster://` - Secure (TSL) TCP connection to a Redis cluster
+* `redis+replicated://` or `redis-replicated://` - Non secure TCP connection to a Redis master and its replicas
+* `rediss+replicated://` or `rediss-replicated://` - Secure (TSL) TCP connection to a Redis master and its replicas
 
 ### QueryParameters
 Query parameters match perfectly optional configuration fields
 of the struct [`Config`] or its dependencies:
 * [`connect_timeout`](Config::connect_timeout) - The time to attempt a connection before timing out (default `10,000` ms).
 * [`command_timeout`](Config::command_timeout) - If a command does not return a reply within a set number of milliseconds,
-   a timeout error will be thrown. If set to 0, no timeout is apply (default `0`).
+  a timeout error will be thrown. If set to 0, no timeout is apply (default `0`).
 * [`auto_resubscribe`](Config::auto_resubscribe) - When the client reconnects, channels subscribed in the previous connection will be
   resubscribed automatically if `auto_resubscribe` is `true` (default `true`).
 * [`auto_remonitor`](Config::auto_remonitor) - When the client reconnects, if in `monitor` mode, the
@@ -215,10 +222,18 @@ of the struct [`Config`] or its dependencies:
 * [`no_delay`](Config::no_delay) - Enable/disable the use of Nagle's algorithm (default `true`)
 * [`retry_on_error`](Config::retry_on_error) - Defines the default strategy for retries on network error (default `false`).
 * [`reconnection`](Config::reconnection) - Reconnection policy configuration: Constant, Linear or Exponential (default `Constant`)
+* [`pub_sub_capacity`](Config::pub_sub_capacity) - Maximum number of messages buffered by each pub/sub or monitor stream.
+  If set to 0, the buffer is unbounded (default `0`).
+* [`pub_sub_overflow_policy`](Config::pub_sub_overflow_policy) - What to do when a stream buffer is full:
+  `drop_oldest`, `drop_newest`, `disconnect` or `backpressure` (default `drop_oldest`).
 * [`wait_between_failures`](SentinelConfig::wait_between_failures) - (Sentinel only) Waiting time after
   failing before connecting to the next Sentinel instance (default `250` ms).
 * [`sentinel_username`](SentinelConfig::username) - (Sentinel only) Sentinel username
 * [`sentinel_password`](SentinelConfig::password) - (Sentinel only) Sentinel password
+* [`sentinel_connect_timeout`](SentinelConfig::connect_timeout) - (Sentinel only) The time to attempt a connection
+  to a Sentinel instance before timing out (default [`connect_timeout`](Config::connect_timeout)).
+* [`read_from_replicas`](SentinelConfig::read_from_replicas) - (Sentinel only) Send read-only commands to replicas,
+  selected with the strategy `random`, `round_robin` or `lowest_lag` (default: all commands are sent to the master).
 
 ### Example
 
@@ -358,8 +373,7 @@ It also possible to use the sharded flavor of the publish function: [`spublish`]
 
 ### Subscribing
 
-Subscribing will block the current client connection, in order to let the client wait for incoming messages.
-Consequently, **rustis** implements subsribing through an async [`Stream`](https://docs.rs/futures/latest/futures/stream/trait.Stream.html).
+**rustis** implements subsribing through an async [`Stream`](https://docs.rs/futures/latest/futures/stream/trait.Stream.html).
 
 You can create a [`PubSubStream`] by calling [`subscribe`](crate::commands::PubSubCommands::subscribe),
 [`psubscribe`](crate::commands::PubSubCommands::psubscribe), or [`ssubscribe`](crate::commands::PubSubCommands::ssubscribe).
@@ -369,10 +383,8 @@ wait for an incoming message in the form of the struct [`PubSubMessage`].
 
 You can also create a [`PubSubStream`] without an upfront subscription by calling [`create_pub_sub`](crate::client::Client::create_pub_sub).
 
-### Warning!
-
-Multiplexed [`Client`] instances must be dedicated to Pub/Sub once a subscribing function has been called.
-Because subscription blocks the multiplexed client shared connection other callers would be blocked when sending regular commands.
+Subscribing does not block the [`Client`] connection: regular commands can still be sent
+through the same [`Client`] instance, or its clones, while messages are received.
 
 ### Simple Example
 
@@ -460,9 +472,15 @@ async fn main() -> Result<()> {
 
 #[allow(clippy::module_inception)]
 mod client;
+mod client_side_cache;
 mod client_state;
 mod client_tracking_invalidation_stream;
 mod config;
+mod durability;
+#[cfg_attr(docsrs, doc(cfg(feature = "fault-injection")))]
+#[cfg(feature = "fault-injection")]
+mod fault_plan;
+mod keyspace_event_stream;
 mod message;
 mod monitor_stream;
 mod pipeline;
@@ -470,13 +488,25 @@ mod pipeline;
 #[cfg(feature = "pool")]
 mod pooled_client_manager;
 mod prepared_command;
+mod pub_sub_codec;
+mod pub_sub_router;
 mod pub_sub_stream;
+mod sentinel_event_stream;
+#[cfg_attr(docsrs, doc(cfg(feature = "record-replay")))]
+#[cfg(feature = "record-replay")]
+mod traffic_recording;
 mod transaction;
 
 pub use client::*;
+pub use client_side_cache::*;
 pub use client_state::*;
 pub(crate) use client_tracking_invalidation_stream::*;
 pub use config::*;
+pub use durability::*;
+#[cfg_attr(docsrs, doc(cfg(feature = "fault-injection")))]
+#[cfg(feature = "fault-injection")]
+pub use fault_plan::*;
+pub use keyspace_event_stream::*;
 pub(crate) use message::*;
 pub use monitor_stream::*;
 pub use pipeline::*;
@@ -484,5 +514,11 @@ pub use pipeline::*;
 #[cfg(feature = "pool")]
 pub use pooled_client_manager::*;
 pub use prepared_command::*;
+pub use pub_sub_codec::*;
+pub use pub_sub_router::*;
 pub use pub_sub_stream::*;
+pub use sentinel_event_stream::*;
+#[cfg_attr(docsrs, doc(cfg(feature = "record-replay")))]
+#[cfg(feature = "record-replay")]
+pub use traffic_recording::*;
 pub use transaction::*;
@@ -35,6 +35,13 @@ impl MonitorStream {
         self.closed = true;
         Ok(())
     }
+
+    /// Number of events dropped because the stream buffer was full
+    ///
+    /// See [`Config::pub_sub_capacity`](crate::client::Config::pub_sub_capacity)
+    pub fn dropped_messages(&self) -> u64 {
+        self.receiver.dropped_messages()
+    }
 }
 
 impl Stream for MonitorStream {
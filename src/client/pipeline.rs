@@ -11,14 +11,14 @@ use crate::commands::{
     BloomCommands, CountMinSketchCommands, CuckooCommands, TDigestCommands, TopKCommands,
 };
 use crate::{
-    client::{Client, PreparedCommand},
+    client::{Client, Durability, PreparedCommand},
     commands::{
         BitmapCommands, ClusterCommands, ConnectionCommands, GenericCommands, GeoCommands,
         HashCommands, HyperLogLogCommands, ListCommands, ScriptingCommands, ServerCommands,
         SetCommands, SortedSetCommands, StreamCommands, StringCommands,
     },
     resp::{Command, RespBatchDeserializer, Response},
-    Result,
+    Error, Result,
 };
 use serde::de::DeserializeOwned;
 use std::iter::zip;
@@ -29,15 +29,21 @@ pub struct Pipeline<'a> {
     commands: Vec<Command>,
     forget_flags: Vec<bool>,
     retry_on_error: Option<bool>,
+    durability: Option<Durability>,
+    /// durability commands queued after commands prepared with
+    /// [`durable`](PreparedCommand::durable), with their index
+    command_durabilities: Vec<(usize, Durability)>,
 }
 
 impl Pipeline<'_> {
-    pub(crate) fn new(client: &Client) -> Pipeline {
+    pub(crate) fn new(client: &Client) -> Pipeline<'_> {
         Pipeline {
             client,
             commands: Vec::new(),
             forget_flags: Vec::new(),
             retry_on_error: None,
+            durability: None,
+            command_durabilities: Vec::new(),
         }
     }
     /// Set a flag to override default `retry_on_error` behavior.
@@ -47,6 +53,22 @@ impl Pipeline<'_> {
         self.retry_on_error = Some(retry_on_error);
     }
 
+    /// Wait for the commands of the pipeline to be acknowledged by `num_replicas` replicas
+    /// and, if `num_local_aof` is `1`, to be fsynced to the local AOF.
+    ///
+    /// `WAIT` or `WAITAOF` is appended to the pipeline, its reply is not part of the pipeline result,
+    /// and an error is returned when fewer replicas or local AOF than requested
+    /// acknowledged the writes before `timeout` (in milliseconds) elapsed.
+    ///
+    /// See [`PreparedCommand::durable`]
+    pub fn durable(&mut self, num_replicas: usize, num_local_aof: usize, timeout: u64) {
+        self.durability = Some(Durability {
+            num_replicas,
+            num_local_aof,
+            timeout,
+        });
+    }
+
     /// Queue a command
     pub fn queue(&mut self, command: Command) {
         self.commands.push(command);
@@ -59,6 +81,13 @@ impl Pipeline<'_> {
         self.forget_flags.push(true);
     }
 
+    /// Queue the durability command of the last queued command
+    fn queue_durability(&mut self, durability: Durability) {
+        self.command_durabilities
+            .push((self.commands.len(), durability));
+        self.forget(durability.command());
+    }
+
     /// Execute the pipeline by the sending the queued command
     /// as a whole batch to the Redis server.
     ///
@@ -95,13 +124,31 @@ impl Pipeline<'_> {
     ///     Ok(())
     /// }
     /// ```    
-    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+    pub async fn execute<T: DeserializeOwned>(mut self) -> Result<T> {
         let num_commands = self.commands.len();
-        let results = self
+        if let Some(durability) = &self.durability {
+            self.commands.push(durability.command());
+        }
+
+        let mut results = self
             .client
             .send_batch(self.commands, self.retry_on_error)
             .await?;
 
+        if let Some(durability) = &self.durability {
+            let durability_result = results.pop().ok_or_else(|| {
+                Error::Client("Missing reply to the durability command".to_owned())
+            })?;
+            durability.check(&durability_result)?;
+        }
+
+        for (index, durability) in &self.command_durabilities {
+            let durability_result = results.get(*index).ok_or_else(|| {
+                Error::Client("Missing reply to the durability command".to_owned())
+            })?;
+            durability.check(durability_result)?;
+        }
+
         if num_commands > 1 {
             let mut filtered_results = zip(results, self.forget_flags.iter())
                 .filter_map(|(value, forget_flag)| if *forget_flag { None } else { Some(value) })
@@ -135,13 +182,19 @@ impl<'a, R: Response> BatchPreparedCommand for PreparedCommand<'a, &'a mut Pipel
     /// Queue a command.
     #[inline]
     fn queue(self) {
-        self.executor.queue(self.command)
+        self.executor.queue(self.command);
+        if let Some(durability) = self.durability {
+            self.executor.queue_durability(durability);
+        }
     }
 
     /// Queue a command and forget its response.
     #[inline]
     fn forget(self) {
-        self.executor.forget(self.command)
+        self.executor.forget(self.command);
+        if let Some(durability) = self.durability {
+            self.executor.queue_durability(durability);
+        }
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::{
-    client::Client,
+    client::{Client, Durability},
     resp::{Command, RespBuf, Response},
     Future,
 };
@@ -24,6 +24,8 @@ where
     pub custom_converter: Option<Box<CustomConverter<'a, R>>>,
     /// Flag to retry sending the command on network error.
     pub retry_on_error: Option<bool>,
+    /// Durability guarantee to check after the command has been executed
+    pub durability: Option<Durability>,
 }
 
 impl<'a, E, R> PreparedCommand<'a, E, R>
@@ -39,6 +41,7 @@ where
             command,
             custom_converter: None,
             retry_on_error: None,
+            durability: None,
         }
     }
 
@@ -56,6 +59,30 @@ where
         self
     }
 
+    /// Wait for the command to be acknowledged by `num_replicas` replicas
+    /// and, if `num_local_aof` is `1`, to be fsynced to the local AOF.
+    ///
+    /// `WAIT` or `WAITAOF` is sent right after the command on the same connection,
+    /// and an error is returned when fewer replicas or local AOF than requested
+    /// acknowledged the write before `timeout` (in milliseconds) elapsed.
+    ///
+    /// * In a [`Pipeline`](crate::client::Pipeline), `WAIT` or `WAITAOF` is queued right after the command
+    ///   and [`execute`](crate::client::Pipeline::execute) fails if the guarantee is not reached.
+    /// * In a [`Transaction`](crate::client::Transaction), [`execute`](crate::client::Transaction::execute)
+    ///   fails because `WAIT` and `WAITAOF` do not block inside `MULTI`.
+    /// * With a cluster, `WAIT` or `WAITAOF` is sent to the node which executed the command.
+    ///
+    /// See [`wait`](crate::commands::GenericCommands::wait)
+    /// and [`waitaof`](crate::commands::GenericCommands::waitaof)
+    pub fn durable(mut self, num_replicas: usize, num_local_aof: usize, timeout: u64) -> Self {
+        self.durability = Some(Durability {
+            num_replicas,
+            num_local_aof,
+            timeout,
+        });
+        self
+    }
+
     /// Get a reference to the command to send
     pub fn command(&self) -> &Command {
         &self.command
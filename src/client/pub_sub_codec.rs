@@ -0,0 +1,92 @@
+use crate::{Error, Result};
+
+/// Codec to encode and decode the payload of pub/sub messages
+///
+/// The same codec is meant to be used on both sides of a channel:
+/// by the producers with [`publish_as`](crate::commands::PubSubCommands::publish_as)
+/// and by the consumers with [`PubSubStream::typed`](crate::client::PubSubStream::typed)
+/// or [`PubSubMessage::payload_as`](crate::client::PubSubMessage::payload_as).
+///
+/// The value wrappers [`Json`](crate::resp::Json), [`MsgPack`](crate::resp::MsgPack)
+/// and [`Cbor`](crate::resp::Cbor) are codecs too (features `json`, `msgpack` and `cbor`):
+/// a message published with `publish_as::<Json<T>>` can be read back with `get` as a `Json<T>`.
+/// Other formats can be plugged in by implementing this trait on a dedicated type.
+///
+/// # Example
+/// ```
+/// use rustis::{client::PayloadCodec, Error, Result};
+///
+/// struct Counter;
+///
+/// impl PayloadCodec for Counter {
+///     type Value = u64;
+///
+///     fn encode(value: &u64) -> Result<Vec<u8>> {
+///         Ok(value.to_be_bytes().to_vec())
+///     }
+///
+///     fn decode(payload: &[u8]) -> Result<u64> {
+///         let bytes = payload
+///             .try_into()
+///             .map_err(|_| Error::Client("Invalid counter payload".to_owned()))?;
+///         Ok(u64::from_be_bytes(bytes))
+///     }
+/// }
+/// ```
+pub trait PayloadCodec {
+    /// Type of the values carried by the payloads
+    type Value;
+
+    /// Encode a value into a payload
+    fn encode(value: &Self::Value) -> Result<Vec<u8>>;
+
+    /// Decode a value from a payload
+    fn decode(payload: &[u8]) -> Result<Self::Value>;
+}
+
+/// Codec for payloads which are left as raw bytes
+pub struct RawCodec;
+
+impl PayloadCodec for RawCodec {
+    type Value = Vec<u8>;
+
+    #[inline]
+    fn encode(value: &Vec<u8>) -> Result<Vec<u8>> {
+        Ok(value.clone())
+    }
+
+    #[inline]
+    fn decode(payload: &[u8]) -> Result<Vec<u8>> {
+        Ok(payload.to_vec())
+    }
+}
+
+/// Codec for UTF-8 string payloads
+pub struct Utf8Codec;
+
+impl PayloadCodec for Utf8Codec {
+    type Value = String;
+
+    #[inline]
+    fn encode(value: &String) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
+    }
+
+    #[inline]
+    fn decode(payload: &[u8]) -> Result<String> {
+        String::from_utf8(payload.to_vec())
+            .map_err(|e| Error::Client(format!("Cannot decode UTF-8 payload: {e}")))
+    }
+}
+
+/// Pub/Sub Message with a payload decoded by a [`PayloadCodec`],
+/// streamed from [`TypedPubSubStream`](crate::client::TypedPubSubStream)
+#[derive(Debug)]
+pub struct TypedPubSubMessage<T> {
+    /// Pattern matched by the channel, empty for a subscription to a channel
+    pub pattern: Vec<u8>,
+    /// Channel the message has been published to
+    pub channel: Vec<u8>,
+    /// Decoded payload of the message
+    pub payload: T,
+}
@@ -0,0 +1,269 @@
+use crate::{
+    client::{Client, PubSubMessage, PubSubOverflowPolicy, PubSubSplitSink, PubSubSplitStream},
+    network::{push_channel, PushChannelReceiver, PushChannelSender},
+    spawn, Error, JoinHandle, Result,
+};
+use futures_util::{future::BoxFuture, FutureExt, StreamExt};
+use log::{debug, warn};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::Semaphore;
+
+type Handler = Arc<dyn Fn(PubSubMessage) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Queue of the messages waiting for an execution of the handler of a route
+///
+/// The task of the route ends once the queue is dropped and drained.
+type RouteQueue = PushChannelSender<Result<PubSubMessage>>;
+
+#[derive(Default)]
+struct Routes {
+    channels: HashMap<Vec<u8>, RouteQueue>,
+    patterns: HashMap<Vec<u8>, RouteQueue>,
+}
+
+impl Routes {
+    fn get(&self, message: &PubSubMessage) -> Option<&RouteQueue> {
+        if message.pattern.is_empty() {
+            self.channels.get(&message.channel)
+        } else {
+            self.patterns.get(&message.pattern)
+        }
+    }
+}
+
+/// Dispatch the messages of a pub/sub stream to handlers registered
+/// per channel or per glob-style pattern
+///
+/// The router subscribes to a channel or to a pattern when a handler is added,
+/// and unsubscribes from it when the handler is removed.
+///
+/// Each handler is spawned for each message, with at most `max_concurrency` executions
+/// of the same handler at the same time.
+/// When a handler reaches this limit, its next messages are queued until one of its executions ends,
+/// without delaying the messages of the other handlers.
+/// With a `max_concurrency` of 1, messages are handled in the order they are received.
+///
+/// The queue of each handler holds at most [`pub_sub_capacity`](crate::client::Config::pub_sub_capacity)
+/// messages, then the [`pub_sub_overflow_policy`](crate::client::Config::pub_sub_overflow_policy)
+/// of the client applies: with the [`Backpressure`](PubSubOverflowPolicy::Backpressure) policy,
+/// the dispatch of the messages of all the handlers waits for room in the queue,
+/// and with the [`Disconnect`](PubSubOverflowPolicy::Disconnect) policy,
+/// the messages of the handler are dropped until it is removed.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{Client, PubSubRouter},
+///     commands::PubSubCommands,
+///     Result,
+/// };
+/// use futures_channel::mpsc;
+/// use futures_util::StreamExt;
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let pub_sub_client = Client::connect("127.0.0.1:6379").await?;
+///     let regular_client = Client::connect("127.0.0.1:6379").await?;
+///
+///     let (sender, mut receiver) = mpsc::unbounded::<String>();
+///
+///     let mut router = PubSubRouter::new(&pub_sub_client, 4);
+///     router
+///         .route("orders.*", move |message| {
+///             let sender = sender.clone();
+///             async move {
+///                 let _ = sender.unbounded_send(String::from_utf8(message.payload).unwrap());
+///             }
+///         })
+///         .await?;
+///
+///     regular_client.publish("orders.created", "order 1").await?;
+///     assert_eq!(Some("order 1".to_owned()), receiver.next().await);
+///
+///     router.close().await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct PubSubRouter {
+    sink: PubSubSplitSink,
+    routes: Arc<Mutex<Routes>>,
+    max_concurrency: usize,
+    queue_capacity: usize,
+    overflow_policy: PubSubOverflowPolicy,
+    dispatch_task: JoinHandle<()>,
+}
+
+impl PubSubRouter {
+    /// Create a router with no handler on a new pub/sub stream of the client
+    ///
+    /// `max_concurrency` is the maximum number of concurrent executions of each handler
+    /// (at least 1)
+    pub fn new(client: &Client, max_concurrency: usize) -> Self {
+        let (sink, stream) = client.create_pub_sub().split();
+        let routes = Arc::new(Mutex::new(Routes::default()));
+        let dispatch_task = spawn(Self::dispatch(stream, routes.clone()));
+
+        Self {
+            sink,
+            routes,
+            max_concurrency: max_concurrency.max(1),
+            queue_capacity: client.pub_sub_capacity,
+            overflow_policy: client.pub_sub_overflow_policy,
+            dispatch_task,
+        }
+    }
+
+    /// Register a handler for a channel or a glob-style pattern and subscribe to it
+    ///
+    /// `channel_or_pattern` is considered as a pattern if it contains
+    /// one of the characters `*`, `?` or `[`.
+    ///
+    /// # Errors
+    /// An error is returned if a handler is already registered for `channel_or_pattern`
+    /// or if the subscription fails
+    pub async fn route<F, Fut>(&mut self, channel_or_pattern: &str, handler: F) -> Result<()>
+    where
+        F: Fn(PubSubMessage) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let is_pattern = Self::is_pattern(channel_or_pattern);
+        let key = channel_or_pattern.as_bytes().to_vec();
+
+        {
+            let mut routes = self.routes.lock().unwrap();
+            let routes = if is_pattern {
+                &mut routes.patterns
+            } else {
+                &mut routes.channels
+            };
+
+            if routes.contains_key(&key) {
+                return Err(Error::Client(format!(
+                    "A handler is already registered for `{channel_or_pattern}`"
+                )));
+            }
+
+            let handler: Handler = Arc::new(move |message| handler(message).boxed());
+            let (queue, receiver) = push_channel(self.queue_capacity, self.overflow_policy);
+            spawn(Self::handle_route(handler, self.max_concurrency, receiver));
+            routes.insert(key.clone(), queue);
+        }
+
+        let result = if is_pattern {
+            self.sink.psubscribe(channel_or_pattern).await
+        } else {
+            self.sink.subscribe(channel_or_pattern).await
+        };
+
+        if result.is_err() {
+            self.remove_route(is_pattern, &key);
+        }
+
+        result
+    }
+
+    /// Unregister the handler of a channel or a pattern and unsubscribe from it
+    ///
+    /// Executions of the handler already started or queued are not cancelled.
+    ///
+    /// # Errors
+    /// An error is returned if no handler is registered for `channel_or_pattern`
+    /// or if the unsubscription fails
+    pub async fn remove(&mut self, channel_or_pattern: &str) -> Result<()> {
+        let is_pattern = Self::is_pattern(channel_or_pattern);
+
+        if !self.remove_route(is_pattern, channel_or_pattern.as_bytes()) {
+            return Err(Error::Client(format!(
+                "No handler is registered for `{channel_or_pattern}`"
+            )));
+        }
+
+        if is_pattern {
+            self.sink.punsubscribe(channel_or_pattern).await
+        } else {
+            self.sink.unsubscribe(channel_or_pattern).await
+        }
+    }
+
+    /// Cancel all subscriptions and wait for the end of the dispatch of the messages
+    ///
+    /// Executions of handlers already started are not awaited.
+    pub async fn close(self) -> Result<()> {
+        self.sink.close().await?;
+        self.dispatch_task.await
+    }
+
+    fn is_pattern(channel_or_pattern: &str) -> bool {
+        channel_or_pattern.contains(['*', '?', '['])
+    }
+
+    fn remove_route(&self, is_pattern: bool, key: &[u8]) -> bool {
+        let mut routes = self.routes.lock().unwrap();
+        let routes = if is_pattern {
+            &mut routes.patterns
+        } else {
+            &mut routes.channels
+        };
+
+        routes.remove(key).is_some()
+    }
+
+    async fn dispatch(mut stream: PubSubSplitStream, routes: Arc<Mutex<Routes>>) {
+        while let Some(message) = stream.next().await {
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!("Error while receiving message in pub/sub router: {e}");
+                    continue;
+                }
+            };
+
+            let Some(mut queue) = routes.lock().unwrap().get(&message).cloned() else {
+                debug!(
+                    "No handler for message on channel `{}`",
+                    String::from_utf8_lossy(&message.channel)
+                );
+                continue;
+            };
+
+            // the route may have been removed concurrently or disconnected by the overflow policy
+            if let Err(e) = queue.send(Ok(message)).await {
+                debug!("Cannot queue message in pub/sub router: {e}");
+            }
+        }
+    }
+
+    /// Spawn the handler of a route for each queued message,
+    /// with at most `max_concurrency` executions at the same time
+    async fn handle_route(
+        handler: Handler,
+        max_concurrency: usize,
+        mut queue: PushChannelReceiver<Result<PubSubMessage>>,
+    ) {
+        let semaphore = Arc::new(Semaphore::new(max_concurrency));
+
+        // queued messages wait in the bounded queue until an execution of the handler is allowed
+        while let Ok(permit) = semaphore.clone().acquire_owned().await {
+            let message = match queue.next().await {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => {
+                    warn!("Handler of pub/sub router disconnected: {e}");
+                    break;
+                }
+                None => break,
+            };
+
+            let handler = handler.clone();
+            spawn(async move {
+                handler(message).await;
+                drop(permit);
+            });
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    client::{Client, ClientPreparedCommand},
+    client::{Client, ClientPreparedCommand, PayloadCodec, TypedPubSubMessage},
     commands::InternalPubSubCommands,
     network::PubSubSender,
     resp::{ByteBufSeed, CommandArgs, SingleArg, SingleArgCollection},
     Error, PubSubReceiver, Result,
 };
+use bytes::Bytes;
 use futures_util::{Stream, StreamExt};
 use serde::{
     de::{self, Visitor},
@@ -12,6 +13,7 @@ use serde::{
 };
 use std::{
     fmt,
+    marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -24,6 +26,30 @@ pub struct PubSubMessage {
     pub payload: Vec<u8>,
 }
 
+impl PubSubMessage {
+    /// Decode the payload with the given [`PayloadCodec`]
+    ///
+    /// # Errors
+    /// Any error returned by the codec
+    #[inline]
+    pub fn payload_as<C: PayloadCodec>(&self) -> Result<C::Value> {
+        C::decode(&self.payload)
+    }
+
+    /// Convert this message into a [`TypedPubSubMessage`]
+    /// with a payload decoded by the given [`PayloadCodec`]
+    ///
+    /// # Errors
+    /// Any error returned by the codec
+    pub fn into_typed<C: PayloadCodec>(self) -> Result<TypedPubSubMessage<C::Value>> {
+        Ok(TypedPubSubMessage {
+            payload: C::decode(&self.payload)?,
+            pattern: self.pattern,
+            channel: self.channel,
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for PubSubMessage {
     #[inline]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
@@ -210,7 +236,7 @@ impl PubSubSplitSink {
     {
         let shardchannels = CommandArgs::default().arg(shardchannels).build();
         self.shardchannels
-            .retain(|shardchannel| shardchannels.iter().all(|sc: &Vec<u8>| sc != shardchannel));
+            .retain(|shardchannel| shardchannels.iter().all(|sc: &Bytes| sc != shardchannel));
         self.client.punsubscribe(shardchannels).await?;
 
         Ok(())
@@ -281,6 +307,15 @@ pub struct PubSubSplitStream {
     receiver: PubSubReceiver,
 }
 
+impl PubSubSplitStream {
+    /// Number of messages dropped because the stream buffer was full
+    ///
+    /// See [`Config::pub_sub_capacity`](crate::client::Config::pub_sub_capacity)
+    pub fn dropped_messages(&self) -> u64 {
+        self.receiver.dropped_messages()
+    }
+}
+
 impl Stream for PubSubSplitStream {
     type Item = Result<PubSubMessage>;
 
@@ -471,6 +506,55 @@ impl PubSubStream {
     pub async fn close(self) -> Result<()> {
         self.split_sink.close().await
     }
+
+    /// Number of messages dropped because the stream buffer was full
+    ///
+    /// See [`Config::pub_sub_capacity`](crate::client::Config::pub_sub_capacity)
+    pub fn dropped_messages(&self) -> u64 {
+        self.split_stream.dropped_messages()
+    }
+
+    /// Convert this stream into a stream of messages
+    /// with payloads decoded by the given [`PayloadCodec`]
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::{Client, Utf8Codec},
+    ///     commands::PubSubCommands,
+    ///     Result,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let pub_sub_client = Client::connect("127.0.0.1:6379").await?;
+    ///     let regular_client = Client::connect("127.0.0.1:6379").await?;
+    ///
+    ///     let mut pub_sub_stream = pub_sub_client
+    ///         .subscribe("mychannel")
+    ///         .await?
+    ///         .typed::<Utf8Codec>();
+    ///
+    ///     regular_client
+    ///         .publish_as::<Utf8Codec>("mychannel", "mymessage".to_owned())?
+    ///         .await?;
+    ///
+    ///     let message = pub_sub_stream.next().await.unwrap()?;
+    ///     assert_eq!("mymessage", message.payload);
+    ///
+    ///     pub_sub_stream.close().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn typed<C: PayloadCodec>(self) -> TypedPubSubStream<C> {
+        TypedPubSubStream {
+            inner: self,
+            phantom: PhantomData,
+        }
+    }
 }
 
 impl Stream for PubSubStream {
@@ -485,3 +569,63 @@ impl Stream for PubSubStream {
         }
     }
 }
+
+/// Stream of pub/sub messages with payloads decoded by a [`PayloadCodec`]
+///
+/// Built with [`PubSubStream::typed`]
+pub struct TypedPubSubStream<C: PayloadCodec> {
+    inner: PubSubStream,
+    phantom: PhantomData<fn() -> C>,
+}
+
+impl<C: PayloadCodec> TypedPubSubStream<C> {
+    /// Subscribe to additional channels
+    pub async fn subscribe<CH, CC>(&mut self, channels: CC) -> Result<()>
+    where
+        CH: SingleArg + Send,
+        CC: SingleArgCollection<CH>,
+    {
+        self.inner.subscribe(channels).await
+    }
+
+    /// Subscribe to additional patterns
+    pub async fn psubscribe<P, PP>(&mut self, patterns: PP) -> Result<()>
+    where
+        P: SingleArg + Send,
+        PP: SingleArgCollection<P>,
+    {
+        self.inner.psubscribe(patterns).await
+    }
+
+    /// Cancel all subscriptions and close the stream
+    ///
+    /// See [`PubSubStream::close`]
+    pub async fn close(self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    /// Number of messages dropped because the stream buffer was full
+    ///
+    /// See [`Config::pub_sub_capacity`](crate::client::Config::pub_sub_capacity)
+    pub fn dropped_messages(&self) -> u64 {
+        self.inner.dropped_messages()
+    }
+
+    /// Get back the underlying untyped stream
+    pub fn into_inner(self) -> PubSubStream {
+        self.inner
+    }
+}
+
+impl<C: PayloadCodec> Stream for TypedPubSubStream<C> {
+    type Item = Result<TypedPubSubMessage<C::Value>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.get_mut().inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(message))) => Poll::Ready(Some(message.into_typed::<C>())),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
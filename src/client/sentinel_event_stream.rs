@@ -0,0 +1,138 @@
+use crate::network::SentinelEventReceiver;
+use futures_util::{Stream, StreamExt};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Details of a Sentinel monitored instance, as published in Sentinel events
+///
+/// See [Sentinel events](https://redis.io/docs/management/sentinel/#pubsub-messages)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelInstanceDetails {
+    /// Instance type: `master`, `slave` or `sentinel`
+    pub instance_type: String,
+    /// Instance name
+    pub name: String,
+    /// Instance host
+    pub host: String,
+    /// Instance port
+    pub port: u16,
+    /// `(master name, master host, master port)` if the instance is not a master
+    pub master: Option<(String, String, u16)>,
+}
+
+impl SentinelInstanceDetails {
+    /// Parse the format `<instance-type> <name> <ip> <port> @ <master-name> <master-ip> <master-port>`
+    fn parse(payload: &str) -> Option<Self> {
+        let mut iter = payload.split_whitespace();
+
+        let instance_type = iter.next()?.to_owned();
+        let name = iter.next()?.to_owned();
+        let host = iter.next()?.to_owned();
+        let port = iter.next()?.parse::<u16>().ok()?;
+
+        let master = match iter.next() {
+            Some("@") => Some((
+                iter.next()?.to_owned(),
+                iter.next()?.to_owned(),
+                iter.next()?.parse::<u16>().ok()?,
+            )),
+            _ => None,
+        };
+
+        Some(Self {
+            instance_type,
+            name,
+            host,
+            port,
+            master,
+        })
+    }
+
+    /// Name of the master related to this instance
+    pub fn master_name(&self) -> &str {
+        match &self.master {
+            Some((master_name, _, _)) => master_name,
+            None => &self.name,
+        }
+    }
+}
+
+/// Event published by a Sentinel instance and forwarded
+/// by a [`Client`](crate::client::Client) connected through Sentinel
+///
+/// See [`Client::create_sentinel_event_stream`](crate::client::Client::create_sentinel_event_stream)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SentinelEvent {
+    /// `+switch-master`: the master address changed after a failover
+    SwitchMaster {
+        /// Name of the master
+        master_name: String,
+        /// `(host, port)` of the previous master
+        old_address: (String, u16),
+        /// `(host, port)` of the new master
+        new_address: (String, u16),
+    },
+    /// `+sdown`: the instance is now in Subjectively Down state
+    SubjectivelyDown(SentinelInstanceDetails),
+    /// `-odown`: the instance is no longer in Objectively Down state
+    ObjectivelyDownCleared(SentinelInstanceDetails),
+}
+
+impl SentinelEvent {
+    /// Channels to subscribe to on a Sentinel instance
+    pub(crate) const CHANNELS: [&'static str; 3] = ["+switch-master", "-odown", "+sdown"];
+
+    pub(crate) fn parse(channel: &[u8], payload: &[u8]) -> Option<Self> {
+        let payload = std::str::from_utf8(payload).ok()?;
+
+        match channel {
+            b"+switch-master" => {
+                // <master name> <oldip> <oldport> <newip> <newport>
+                let mut iter = payload.split_whitespace();
+                let master_name = iter.next()?.to_owned();
+                let old_host = iter.next()?.to_owned();
+                let old_port = iter.next()?.parse::<u16>().ok()?;
+                let new_host = iter.next()?.to_owned();
+                let new_port = iter.next()?.parse::<u16>().ok()?;
+
+                Some(SentinelEvent::SwitchMaster {
+                    master_name,
+                    old_address: (old_host, old_port),
+                    new_address: (new_host, new_port),
+                })
+            }
+            b"+sdown" => SentinelInstanceDetails::parse(payload).map(Self::SubjectivelyDown),
+            b"-odown" => SentinelInstanceDetails::parse(payload).map(Self::ObjectivelyDownCleared),
+            _ => None,
+        }
+    }
+
+    /// Name of the master related to this event
+    pub fn master_name(&self) -> &str {
+        match self {
+            SentinelEvent::SwitchMaster { master_name, .. } => master_name,
+            SentinelEvent::SubjectivelyDown(details)
+            | SentinelEvent::ObjectivelyDownCleared(details) => details.master_name(),
+        }
+    }
+}
+
+pub(crate) struct SentinelEventStream {
+    receiver: SentinelEventReceiver,
+}
+
+impl SentinelEventStream {
+    pub(crate) fn new(receiver: SentinelEventReceiver) -> Self {
+        Self { receiver }
+    }
+}
+
+impl Stream for SentinelEventStream {
+    type Item = SentinelEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.get_mut().receiver.poll_next_unpin(cx)
+    }
+}
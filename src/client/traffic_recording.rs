@@ -0,0 +1,268 @@
+use crate::{resp::Command, Error, Result};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Recorded in place of the passwords of the `AUTH` and `HELLO` commands
+pub(crate) const REDACTED_ARG: &[u8] = b"<redacted>";
+
+/// Recorded in place of the arguments streamed from a [`BulkReader`](crate::resp::BulkReader)
+pub(crate) const STREAMED_ARG: &[u8] = b"<streamed>";
+
+/// Copy of `command` as recorded, with its password replaced by [`REDACTED_ARG`]
+/// and its streamed arguments by [`STREAMED_ARG`],
+/// or `None` if the command can be recorded as is
+pub(crate) fn redact_command(command: &Command) -> Option<Command> {
+    let password_index = if command.name.eq_ignore_ascii_case("AUTH") {
+        // AUTH [username] password
+        command.args.len().checked_sub(1)
+    } else if command.name.eq_ignore_ascii_case("HELLO") {
+        // HELLO [protover [AUTH username password] [SETNAME clientname]]
+        command
+            .args
+            .into_iter()
+            .position(|arg| arg.eq_ignore_ascii_case(b"AUTH"))
+            .map(|index| index + 2)
+    } else {
+        None
+    };
+
+    let readers = command.args.readers();
+    if password_index.is_none() && readers.is_empty() {
+        return None;
+    }
+
+    let mut redacted = Command::new(command.name);
+    for (index, arg) in command.args.into_iter().enumerate() {
+        redacted.args.arg(if Some(index) == password_index {
+            REDACTED_ARG
+        } else if readers.iter().any(|(i, _)| *i == index) {
+            STREAMED_ARG
+        } else {
+            arg
+        });
+    }
+    Some(redacted)
+}
+
+/// Kind of a [`TrafficEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficEventKind {
+    /// A connection to the server has been established
+    Connect,
+    /// A command has been sent, as encoded on the wire
+    Command,
+    /// A reply has been received
+    Reply,
+    /// A push message has been received
+    Push,
+    /// The connection has been closed by the server or the network
+    Disconnect,
+}
+
+impl TrafficEventKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TrafficEventKind::Connect => "connect",
+            TrafficEventKind::Command => "command",
+            TrafficEventKind::Reply => "reply",
+            TrafficEventKind::Push => "push",
+            TrafficEventKind::Disconnect => "disconnect",
+        }
+    }
+}
+
+impl FromStr for TrafficEventKind {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self> {
+        match str {
+            "connect" => Ok(Self::Connect),
+            "command" => Ok(Self::Command),
+            "reply" => Ok(Self::Reply),
+            "push" => Ok(Self::Push),
+            "disconnect" => Ok(Self::Disconnect),
+            _ => Err(Error::Client(format!(
+                "Cannot parse traffic event kind `{str}`"
+            ))),
+        }
+    }
+}
+
+/// Event of a [`TrafficRecording`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrafficEvent {
+    /// Time elapsed between the creation of the recorder and the event
+    pub elapsed: Duration,
+    /// Address of the server (`host:port`) of the connection
+    pub connection: String,
+    /// Kind of the event
+    pub kind: TrafficEventKind,
+    /// Raw RESP bytes of commands, replies and push messages, empty for the other events
+    pub data: Vec<u8>,
+}
+
+/// Records the exact command/reply stream of a client to a file
+///
+/// Commands are recorded as encoded on the wire, replies and push messages as received,
+/// with connections and disconnections, all timestamped.
+///
+/// The passwords of the `AUTH` and `HELLO` commands are not recorded but replaced by `<redacted>`,
+/// which matches any password when the recording is replayed.
+/// Likewise, the arguments streamed from a [`BulkReader`](crate::resp::BulkReader)
+/// are replaced by `<streamed>`, which matches any argument.
+/// Other secrets sent in commands or received in replies (e.g. `CONFIG SET requirepass`)
+/// are recorded as is.
+///
+/// The recording can be loaded later with [`TrafficRecording::load`],
+/// typically to be replayed with a [`ReplayServer`](crate::mock::ReplayServer).
+///
+/// The recorder is shared by all the clones of a [`Config`](crate::client::Config),
+/// see [`Config::traffic_recorder`](crate::client::Config::traffic_recorder).
+///
+/// This struct is only available with the feature `record-replay`.
+#[derive(Clone)]
+pub struct TrafficRecorder {
+    writer: Arc<Mutex<BufWriter<File>>>,
+    start: Instant,
+}
+
+impl std::fmt::Debug for TrafficRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrafficRecorder").finish_non_exhaustive()
+    }
+}
+
+impl TrafficRecorder {
+    /// Creates a recorder writing to the given file, truncating it if it already exists
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            writer: Arc::new(Mutex::new(BufWriter::new(File::create(path)?))),
+            start: Instant::now(),
+        })
+    }
+
+    /// Flushes the recorded events to the file
+    ///
+    /// The events are also flushed when the last clone of the recorder is dropped.
+    pub fn flush(&self) -> Result<()> {
+        self.writer.lock().unwrap().flush()?;
+        Ok(())
+    }
+
+    pub(crate) fn record(&self, connection: &str, kind: TrafficEventKind, data: &[u8]) {
+        let elapsed = self.start.elapsed().as_micros();
+        let mut writer = self.writer.lock().unwrap();
+        let result = writeln!(
+            writer,
+            "{elapsed} {} {connection} {}",
+            kind.as_str(),
+            data.len()
+        )
+        .and_then(|_| writer.write_all(data))
+        .and_then(|_| writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            log::warn!("Cannot record traffic event: {e}");
+        }
+    }
+}
+
+/// Traffic recorded by a [`TrafficRecorder`]
+///
+/// This struct is only available with the feature `record-replay`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrafficRecording {
+    events: Vec<TrafficEvent>,
+}
+
+impl TrafficRecording {
+    /// Loads a recording from a file written by a [`TrafficRecorder`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut events = Vec::new();
+        let mut header = String::new();
+
+        loop {
+            header.clear();
+            if reader.read_line(&mut header)? == 0 {
+                break;
+            }
+
+            let mut parts = header.split_whitespace();
+            let (Some(elapsed), Some(kind), Some(connection), Some(len), None) = (
+                parts.next(),
+                parts.next(),
+                parts.next(),
+                parts.next(),
+                parts.next(),
+            ) else {
+                return Err(Error::Client(format!(
+                    "Cannot parse traffic event header `{}`",
+                    header.trim_end()
+                )));
+            };
+
+            let len = len.parse::<usize>()?;
+            let mut data = vec![0; len + 1];
+            reader.read_exact(&mut data)?;
+            if data.pop() != Some(b'\n') {
+                return Err(Error::Client(
+                    "Cannot parse traffic event: missing end of line".to_owned(),
+                ));
+            }
+
+            events.push(TrafficEvent {
+                elapsed: Duration::from_micros(elapsed.parse()?),
+                connection: connection.to_owned(),
+                kind: kind.parse()?,
+                data,
+            });
+        }
+
+        Ok(Self { events })
+    }
+
+    /// Recorded events, in chronological order
+    #[must_use]
+    pub fn events(&self) -> &[TrafficEvent] {
+        &self.events
+    }
+
+    /// Addresses of the servers (`host:port`) of the recorded connections
+    #[must_use]
+    pub fn connections(&self) -> Vec<&str> {
+        let mut connections = Vec::<&str>::new();
+        for event in &self.events {
+            if !connections.contains(&event.connection.as_str()) {
+                connections.push(&event.connection);
+            }
+        }
+        connections
+    }
+
+    /// Extracts the events of the connections to a single server,
+    /// typically a node of a cluster
+    #[must_use]
+    pub fn filter_connection(&self, connection: &str) -> Self {
+        Self {
+            events: self
+                .events
+                .iter()
+                .filter(|event| event.connection == connection)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl From<Vec<TrafficEvent>> for TrafficRecording {
+    fn from(events: Vec<TrafficEvent>) -> Self {
+        Self { events }
+    }
+}
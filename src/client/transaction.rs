@@ -33,6 +33,8 @@ pub struct Transaction {
     commands: Vec<Command>,
     forget_flags: Vec<bool>,
     retry_on_error: Option<bool>,
+    /// a command prepared with [`durable`](PreparedCommand::durable) has been queued
+    has_durable_command: bool,
 }
 
 impl Transaction {
@@ -42,6 +44,7 @@ impl Transaction {
             commands: vec![cmd("MULTI")],
             forget_flags: Vec::new(),
             retry_on_error: None,
+            has_durable_command: false,
         }
     }
 
@@ -100,6 +103,12 @@ impl Transaction {
     /// }
     /// ```
     pub async fn execute<T: DeserializeOwned>(mut self) -> Result<T> {
+        if self.has_durable_command {
+            return Err(Error::Client(
+                "Durability cannot be checked inside a transaction: WAIT and WAITAOF do not block inside MULTI".to_owned(),
+            ));
+        }
+
         self.commands.push(cmd("EXEC"));
 
         let num_commands = self.commands.len();
@@ -265,11 +274,13 @@ where
 impl<'a, R: Response> BatchPreparedCommand for PreparedCommand<'a, &'a mut Transaction, R> {
     /// Queue a command into the transaction.
     fn queue(self) {
+        self.executor.has_durable_command |= self.durability.is_some();
         self.executor.queue(self.command)
     }
 
     /// Queue a command into the transaction and forget its response.
     fn forget(self) {
+        self.executor.has_durable_command |= self.durability.is_some();
         self.executor.forget(self.command)
     }
 }
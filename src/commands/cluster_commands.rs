@@ -487,8 +487,10 @@ pub enum ClusterBumpEpochResult {
 }
 
 /// Options for the [`cluster_failover`](ClusterCommands::cluster_failover) command
+#[derive(Default)]
 pub enum ClusterFailoverOption {
     /// No option
+    #[default]
     Default,
     /// FORCE option: manual failover when the master is down
     Force,
@@ -496,12 +498,6 @@ pub enum ClusterFailoverOption {
     Takeover,
 }
 
-impl Default for ClusterFailoverOption {
-    fn default() -> Self {
-        Self::Default
-    }
-}
-
 impl ToArgs for ClusterFailoverOption {
     fn write_args(&self, args: &mut CommandArgs) {
         match self {
@@ -313,6 +313,11 @@ pub trait ConnectionCommands<'a> {
     /// This command enables the tracking feature of the Redis server,
     /// that is used for [`server assisted client side caching`](https://redis.io/topics/client-side-caching).
     ///
+    /// When sent through a [`Client`](crate::client::Client), the tracking is restored
+    /// with the same options after a reconnection.
+    /// Since connection IDs change on reconnection, the tracking must be set again
+    /// when the connection targeted by the `REDIRECT` option reconnects.
+    ///
     /// # See Also
     /// [<https://redis.io/commands/client-tracking/>](https://redis.io/commands/client-tracking/)
     #[must_use]
@@ -0,0 +1,348 @@
+use crate::{
+    client::{prepare_command, Client},
+    commands::{script::KeysAndArgs, FunctionListOptions, LibraryInfo, ScriptingCommands},
+    resp::{cmd, Command, SingleArg, SingleArgCollection},
+    Error, RedisErrorKind, Result,
+};
+use futures_channel::oneshot;
+use futures_util::FutureExt;
+use log::debug;
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, marker::PhantomData};
+
+/// Line added after the shebang of the library code to mark its version
+const VERSION_MARKER: &str = "-- version: ";
+
+/// Lua library of [Redis Functions](https://redis.io/docs/manual/programmability/functions-intro/)
+/// with a version
+///
+/// The code must start with a shebang declaring the name of the library
+/// (e.g. `#!lua name=mylib`), and is typically embedded with [`include_str!`].
+///
+/// When [deployed](FunctionLibrary::deploy), a version marker is added to the code,
+/// so that the library is only replaced when the version loaded on the server differs.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::Client,
+///     commands::{FunctionLibrary, LibraryFunction},
+///     Result,
+/// };
+///
+/// static MYLIB: FunctionLibrary = FunctionLibrary::new(
+///     "#!lua name=mylib\nredis.register_function('echo', function(keys, args) return args[1] end)",
+///     "1.0",
+/// );
+/// static ECHO: LibraryFunction<String> = MYLIB.function("echo");
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///
+///     MYLIB.deploy(&client).await?;
+///
+///     let value = ECHO.call().args("hello").invoke(&client).await?;
+///     assert_eq!("hello", value);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FunctionLibrary {
+    code: Cow<'static, str>,
+    version: Cow<'static, str>,
+}
+
+impl FunctionLibrary {
+    /// Declare a library from its code and its version
+    #[must_use]
+    pub const fn new(code: &'static str, version: &'static str) -> Self {
+        Self {
+            code: Cow::Borrowed(code),
+            version: Cow::Borrowed(version),
+        }
+    }
+
+    /// Declare a library from its code and its version, built at runtime
+    #[must_use]
+    pub fn from_owned(code: String, version: String) -> Self {
+        Self {
+            code: Cow::Owned(code),
+            version: Cow::Owned(version),
+        }
+    }
+
+    /// Name of the library, as declared in the shebang of its code
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        let shebang = self.code.lines().next()?.strip_prefix("#!")?;
+        shebang
+            .split_whitespace()
+            .find_map(|token| token.strip_prefix("name="))
+    }
+
+    /// Version of the library
+    #[must_use]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Declare a function registered by this library
+    #[must_use]
+    pub const fn function<R>(&self, name: &'static str) -> LibraryFunction<'_, R> {
+        LibraryFunction {
+            library: self,
+            name,
+            readonly: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Declare a function registered by this library
+    /// with the `no-writes` flag, invoked with [`fcall_readonly`](ScriptingCommands::fcall_readonly)
+    #[must_use]
+    pub const fn readonly_function<R>(&self, name: &'static str) -> LibraryFunction<'_, R> {
+        LibraryFunction {
+            library: self,
+            name,
+            readonly: true,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Load the library on the server, unless the same version is already loaded.
+    ///
+    /// # Return
+    /// `true` if the library has been loaded, `false` if it was already up to date.
+    ///
+    /// # Errors
+    /// An error is returned if the code does not declare the name of the library,
+    /// or any Redis driver [`Error`](crate::Error) that occurs during the load
+    pub async fn deploy(&self, client: &Client) -> Result<bool> {
+        let name = self.name().ok_or_else(|| {
+            Error::Client("Missing library name in the shebang of the library code".to_owned())
+        })?;
+
+        let libraries: Vec<LibraryInfo> = client
+            .function_list(
+                FunctionListOptions::default()
+                    .library_name_pattern(name)
+                    .with_code(),
+            )
+            .await?;
+
+        let loaded_version = libraries
+            .iter()
+            .find(|l| l.library_name == name)
+            .and_then(|l| l.library_code.as_deref())
+            .and_then(Self::parse_version);
+
+        if loaded_version == Some(self.version()) {
+            debug!(
+                "Function library `{name}` version {} is up to date",
+                self.version
+            );
+            return Ok(false);
+        }
+
+        debug!(
+            "Loading function library `{name}` version {} (loaded version: {loaded_version:?})",
+            self.version
+        );
+        self.load(client).await?;
+
+        Ok(true)
+    }
+
+    /// Load the library, replacing the loaded version if any
+    ///
+    /// In a cluster, `FUNCTION LOAD` is sent to all the shards.
+    async fn load(&self, client: &Client) -> Result<()> {
+        client
+            .function_load::<_, String>(true, self.versioned_code())
+            .await?;
+        Ok(())
+    }
+
+    /// Code with the version marker inserted after the shebang
+    fn versioned_code(&self) -> String {
+        let (shebang, body) = self.code.split_once('\n').unwrap_or((&self.code, ""));
+        format!("{shebang}\n{VERSION_MARKER}{}\n{body}", self.version)
+    }
+
+    fn parse_version(code: &str) -> Option<&str> {
+        code.lines()
+            .nth(1)
+            .and_then(|line| line.strip_prefix(VERSION_MARKER))
+    }
+}
+
+/// Deploy a set of [`FunctionLibrary`] and keep them deployed
+///
+/// The libraries are deployed again each time the client reconnects,
+/// since the server may have been restarted or replaced by a failover.
+/// The task redeploying the libraries stops when the manager is dropped.
+///
+/// In a cluster, a [`LibraryFunction`] invoked on a node where its library is missing
+/// (e.g. a new node) loads the library on all the shards before invoking the function again.
+pub struct FunctionLibraryManager {
+    client: Client,
+    libraries: Vec<FunctionLibrary>,
+    /// stops the task redeploying the libraries after a reconnection
+    _stop_sender: oneshot::Sender<()>,
+}
+
+impl FunctionLibraryManager {
+    /// Deploy the libraries and start redeploying them after each reconnection
+    ///
+    /// # Errors
+    /// Any [`Error`](crate::Error) returned by [`FunctionLibrary::deploy`]
+    pub async fn deploy<'l>(
+        client: &Client,
+        libraries: impl IntoIterator<Item = &'l FunctionLibrary>,
+    ) -> Result<Self> {
+        let libraries: Vec<FunctionLibrary> = libraries.into_iter().cloned().collect();
+        Self::deploy_libraries(client, &libraries).await?;
+
+        let redeployed_libraries = libraries.clone();
+        let stop_sender = client.spawn_on_reconnect("function libraries", move |client| {
+            let libraries = redeployed_libraries.clone();
+            async move { Self::deploy_libraries(&client, &libraries).await }.boxed()
+        });
+
+        Ok(Self {
+            client: client.clone(),
+            libraries,
+            _stop_sender: stop_sender,
+        })
+    }
+
+    /// Deploy again the libraries whose version is not up to date on the server
+    ///
+    /// # Errors
+    /// Any [`Error`](crate::Error) returned by [`FunctionLibrary::deploy`]
+    pub async fn redeploy(&self) -> Result<()> {
+        Self::deploy_libraries(&self.client, &self.libraries).await
+    }
+
+    /// Managed libraries
+    #[must_use]
+    pub fn libraries(&self) -> &[FunctionLibrary] {
+        &self.libraries
+    }
+
+    async fn deploy_libraries(client: &Client, libraries: &[FunctionLibrary]) -> Result<()> {
+        for library in libraries {
+            library.deploy(client).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Function registered by a [`FunctionLibrary`], returning a value of type `R`
+pub struct LibraryFunction<'l, R> {
+    library: &'l FunctionLibrary,
+    name: &'static str,
+    readonly: bool,
+    phantom: PhantomData<fn() -> R>,
+}
+
+impl<R> LibraryFunction<'_, R> {
+    /// Name of the function
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Start an invocation of the function
+    #[must_use]
+    pub fn call(&self) -> FunctionCall<'_, R> {
+        FunctionCall {
+            function: self,
+            keys_and_args: KeysAndArgs::default(),
+        }
+    }
+}
+
+/// Invocation of a [`LibraryFunction`] with its keys and arguments
+pub struct FunctionCall<'f, R> {
+    function: &'f LibraryFunction<'f, R>,
+    keys_and_args: KeysAndArgs,
+}
+
+impl<R> FunctionCall<'_, R> {
+    /// Keys accessed by the function.
+    ///
+    /// Can be called several times, the keys are appended.
+    #[must_use]
+    pub fn keys<K, C>(mut self, keys: C) -> Self
+    where
+        K: SingleArg,
+        C: SingleArgCollection<K>,
+    {
+        self.keys_and_args.keys(keys);
+        self
+    }
+
+    /// Additional input arguments that should not represent names of keys.
+    ///
+    /// Can be called several times, the arguments are appended.
+    #[must_use]
+    pub fn args<A, C>(mut self, args: C) -> Self
+    where
+        A: SingleArg,
+        C: SingleArgCollection<A>,
+    {
+        self.keys_and_args.args(args);
+        self
+    }
+
+    /// Invoke the function on a client with `FCALL` or `FCALL_RO`.
+    ///
+    /// If the function is not found on the server,
+    /// its library is loaded and the function is invoked again.
+    ///
+    /// # Return
+    /// The return value of the function
+    ///
+    /// # Errors
+    /// Any Redis driver [`Error`](crate::Error) that occurs during the send operation,
+    /// or an error raised by the function
+    pub async fn invoke(self, client: &Client) -> Result<R>
+    where
+        R: DeserializeOwned + Send,
+    {
+        let result = prepare_command::<_, R>(client, self.command()).await;
+
+        match result {
+            // `Function not found` has no dedicated error code.
+            // The version is not checked first: in a cluster, `FUNCTION LIST`
+            // would not be sent to the node which reported the missing function
+            Err(Error::Redis(e))
+                if e.kind == RedisErrorKind::Err
+                    && (e.description.starts_with("Function not found")
+                        || e.description.starts_with("Library not found")) =>
+            {
+                debug!(
+                    "Function `{}` not found, loading its library",
+                    self.function.name
+                );
+                self.function.library.load(client).await?;
+                prepare_command(client, self.command()).await
+            }
+            result => result,
+        }
+    }
+
+    fn command(&self) -> Command {
+        let name = if self.function.readonly {
+            "FCALL_RO"
+        } else {
+            "FCALL"
+        };
+        self.keys_and_args
+            .write_to(cmd(name).arg(self.function.name))
+    }
+}
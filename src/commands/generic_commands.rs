@@ -684,6 +684,33 @@ pub trait GenericCommands<'a> {
     {
         prepare_command(self, cmd("WAIT").arg(num_replicas).arg(timeout))
     }
+
+    /// This command blocks the current client until all the previous write commands are
+    /// acknowledged as having been fsynced to the AOF of the local Redis
+    /// and/or at least the specified number of replicas.
+    ///
+    /// # Return
+    /// A tuple made up of:
+    /// * the number of local Redis (0 or 1) that fsynced all the writes performed in the context of the current connection,
+    /// * the number of replicas that acknowledged all the writes performed in the context of the current connection.
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/waitaof/>](https://redis.io/commands/waitaof/)
+    #[must_use]
+    fn waitaof(
+        self,
+        num_local: usize,
+        num_replicas: usize,
+        timeout: u64,
+    ) -> PreparedCommand<'a, Self, (usize, usize)>
+    where
+        Self: Sized,
+    {
+        prepare_command(
+            self,
+            cmd("WAITAOF").arg(num_local).arg(num_replicas).arg(timeout),
+        )
+    }
 }
 
 /// Options for the [`expire`](GenericCommands::expire) command
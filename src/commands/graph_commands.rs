@@ -271,8 +271,8 @@ impl GraphResultSet {
         resp_buffer: RespBuf,
         command: Command,
         client: &Client,
-    ) -> Future<Self> {
-        let Some(graph_name) = command.args.iter().next() else {
+    ) -> Future<'_, Self> {
+        let Some(graph_name) = command.args.into_iter().next() else {
             return Box::pin(future::ready(Err(Error::Client(
                 "Cannot parse graph command".to_owned(),
             ))));
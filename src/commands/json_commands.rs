@@ -90,9 +90,9 @@ pub trait JsonCommands<'a> {
     /// * `key` - The key to modify.
     /// * `path`- The JSONPath to specify.
     /// * `index`- The position in the array where you want to insert a value.
-    ///  The index must be in the array's range.
-    ///  Inserting at index 0 prepends to the array.
-    ///  Negative index values start from the end of the array.
+    ///   The index must be in the array's range.
+    ///   Inserting at index 0 prepends to the array.
+    ///   Negative index values start from the end of the array.
     /// * `values` - one or more values to insert in one or more arrays.
     ///
     /// # Return
@@ -157,9 +157,9 @@ pub trait JsonCommands<'a> {
     /// * `key` - The key to modify.
     /// * `path`- The JSONPath to specify.
     /// * `index`- is position in the array to start popping from.
-    ///  Default is -1, meaning the last element.
-    ///  Out-of-range indexes round to their respective array ends.
-    ///  Popping an empty array returns null.
+    ///   Default is -1, meaning the last element.
+    ///   Out-of-range indexes round to their respective array ends.
+    ///   Popping an empty array returns null.
     ///
     /// # Return
     /// A collection of bulk string replies for each path, each reply is the popped JSON value,
@@ -191,7 +191,7 @@ pub trait JsonCommands<'a> {
     /// * `path`- The JSONPath to specify.
     /// * `start`- The index of the first element to keep (previous elements are trimmed).
     /// * `stop` - the index of the last element to keep (following elements are trimmed), including the last element.
-    ///  Negative values are interpreted as starting from the end.
+    ///   Negative values are interpreted as starting from the end.
     ///
     /// # Return
     /// A collection of integer replies for each path, the array's new size,
@@ -464,7 +464,7 @@ pub trait JsonCommands<'a> {
     /// * JSON string maps to the bulk string reply.
     /// * JSON array is represented as an array reply in which the first element is the simple string reply `[`, followed by the array's elements.
     /// * JSON object is represented as an array reply in which the first element is the simple string reply `{`.
-    ///  Each successive entry represents a key-value pair as a two-entry array reply of the bulk string reply.
+    ///   Each successive entry represents a key-value pair as a two-entry array reply of the bulk string reply.
     ///
     /// # See Also
     /// [<https://redis.io/commands/json.resp/>](https://redis.io/commands/json.resp/)
@@ -484,9 +484,9 @@ pub trait JsonCommands<'a> {
     /// # Arguments
     /// * `key` - The key to modify.
     /// * `path` - JSONPath to specify.\
-    ///  For new Redis keys the path must be the root.\
-    ///  For existing keys, when the entire path exists, the value that it contains is replaced with the json value.\
-    ///  For existing keys, when the path exists, except for the last element, a new child is added with the json value.
+    ///   For new Redis keys the path must be the root.\
+    ///   For existing keys, when the entire path exists, the value that it contains is replaced with the json value.\
+    ///   For existing keys, when the path exists, except for the last element, a new child is added with the json value.
     /// * `value`- The value to set at the specified path
     /// * `condition`- See [`SetCondition`](crate::commands::SetCondition)
     ///
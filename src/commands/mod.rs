@@ -10,37 +10,37 @@ Depending on the group of commands, traits will be implemented by [`Client`](cra
 [`Pipeline`](crate::client::Pipeline), [`Transaction`](crate::client::Transaction) or some of these structs.
 
 These is the list of existing command traits:
-* [`BitmapCommands`]: [Bitmaps](https://redis.io/docs/data-types/bitmaps/) & [Bitfields](https://redis.io/docs/data-types/bitfields/)
-* [`BlockingCommands`]: Commands that block the connection until the Redis server
+* [`BitmapCommands`] - [Bitmaps](https://redis.io/docs/data-types/bitmaps/) & [Bitfields](https://redis.io/docs/data-types/bitfields/)
+* [`BlockingCommands`] - Commands that block the connection until the Redis server
   has a new element to send. This trait is implemented only by the [`Client`](crate::client::Client) struct.
-* [`ClusterCommands`]: [Redis cluster](https://redis.io/docs/reference/cluster-spec/)
-* [`ConnectionCommands`]: Connection management like authentication or RESP version management
-* [`GenericCommands`]: Generic commands like deleting, renaming or expiring keys
-* [`GeoCommands`]: [Geospatial](https://redis.io/docs/data-types/geospatial/) indices
-* [`HashCommands`]: [Hashes](https://redis.io/docs/data-types/hashes/)
-* [`HyperLogLogCommands`]: [HyperLogLog](https://redis.io/docs/data-types/hyperloglogs/)
-* [`ListCommands`]: [Lists](https://redis.io/docs/data-types/lists/)
-* [`PubSubCommands`]: [Pub/Sub](https://redis.io/docs/manual/pubsub/)
-* [`ScriptingCommands`]: [Scripts](https://redis.io/docs/manual/programmability/eval-intro/) &
+* [`ClusterCommands`] - [Redis cluster](https://redis.io/docs/reference/cluster-spec/)
+* [`ConnectionCommands`] - Connection management like authentication or RESP version management
+* [`GenericCommands`] - Generic commands like deleting, renaming or expiring keys
+* [`GeoCommands`] - [Geospatial](https://redis.io/docs/data-types/geospatial/) indices
+* [`HashCommands`] - [Hashes](https://redis.io/docs/data-types/hashes/)
+* [`HyperLogLogCommands`] - [HyperLogLog](https://redis.io/docs/data-types/hyperloglogs/)
+* [`ListCommands`] - [Lists](https://redis.io/docs/data-types/lists/)
+* [`PubSubCommands`] - [Pub/Sub](https://redis.io/docs/manual/pubsub/)
+* [`ScriptingCommands`] - [Scripts](https://redis.io/docs/manual/programmability/eval-intro/) &
   [Functions](https://redis.io/docs/manual/programmability/functions-intro/)
-* [`SentinelCommands`]: [Sentinel](https://redis.io/docs/management/sentinel/)
-* [`ServerCommands`]: Server management like [Access Control Lists](https://redis.io/docs/management/security/acl/) or monitoring
-* [`SetCommands`]: [Sets](https://redis.io/docs/data-types/sets/)
-* [`SortedSetCommands`]: [Sorted sets](https://redis.io/docs/data-types/sorted-sets/)
-* [`StreamCommands`]: [Streams](https://redis.io/docs/data-types/streams/)
-* [`StringCommands`]: [Strings](https://redis.io/docs/data-types/strings/)
-* [`TransactionCommands`]: [Transactions](https://redis.io/docs/manual/transactions/)
+* [`SentinelCommands`] - [Sentinel](https://redis.io/docs/management/sentinel/)
+* [`ServerCommands`] - Server management like [Access Control Lists](https://redis.io/docs/management/security/acl/) or monitoring
+* [`SetCommands`] - [Sets](https://redis.io/docs/data-types/sets/)
+* [`SortedSetCommands`] - [Sorted sets](https://redis.io/docs/data-types/sorted-sets/)
+* [`StreamCommands`] - [Streams](https://redis.io/docs/data-types/streams/)
+* [`StringCommands`] - [Strings](https://redis.io/docs/data-types/strings/)
+* [`TransactionCommands`] - [Transactions](https://redis.io/docs/manual/transactions/)
 
 Redis Stack commands:
-* [`BloomCommands`]: [Bloom filters](https://redis.io/docs/stack/bloom/)
-* [`CuckooCommands`]: [Cuckoo filters](https://redis.io/docs/stack/bloom/)
-* [`CountMinSketchCommands`]: [Count min-sketch](https://redis.io/docs/stack/bloom/)
-* [`GraphCommands`]: [RedisGraph](https://redis.io/docs/stack/graph/)
-* [`JsonCommands`]: [RedisJson](https://redis.io/docs/stack/json/)
-* [`SearchCommands`]: [RedisSearch](https://redis.io/docs/stack/search/)
-* [`TDigestCommands`]: [T-Digest](https://redis.io/docs/stack/bloom/)
-* [`TimeSeriesCommands`]: [Time Series](https://redis.io/docs/stack/timeseries/)
-* [`TopKCommands`]: [Top-K](https://redis.io/docs/stack/bloom/)
+* [`BloomCommands`] - [Bloom filters](https://redis.io/docs/stack/bloom/)
+* [`CuckooCommands`] - [Cuckoo filters](https://redis.io/docs/stack/bloom/)
+* [`CountMinSketchCommands`] - [Count min-sketch](https://redis.io/docs/stack/bloom/)
+* [`GraphCommands`] - [RedisGraph](https://redis.io/docs/stack/graph/)
+* [`JsonCommands`] - [RedisJson](https://redis.io/docs/stack/json/)
+* [`SearchCommands`] - [RedisSearch](https://redis.io/docs/stack/search/)
+* [`TDigestCommands`] - [T-Digest](https://redis.io/docs/stack/bloom/)
+* [`TimeSeriesCommands`] - [Time Series](https://redis.io/docs/stack/timeseries/)
+* [`TopKCommands`] - [Top-K](https://redis.io/docs/stack/bloom/)
 
 # Example
 
@@ -97,6 +97,7 @@ mod count_min_sktech_commands;
 mod cuckoo_commands;
 #[cfg(test)]
 mod debug_commands;
+mod function_library;
 mod generic_commands;
 mod geo_commands;
 #[cfg_attr(docsrs, doc(cfg(feature = "redis-graph")))]
@@ -116,6 +117,7 @@ mod internal_pub_sub_commands;
 mod json_commands;
 mod list_commands;
 mod pub_sub_commands;
+mod script;
 mod scripting_commands;
 #[cfg_attr(docsrs, doc(cfg(feature = "redis-search")))]
 #[cfg(feature = "redis-search")]
@@ -152,6 +154,7 @@ pub use count_min_sktech_commands::*;
 pub use cuckoo_commands::*;
 #[cfg(test)]
 pub use debug_commands::*;
+pub use function_library::*;
 pub use generic_commands::*;
 pub use geo_commands::*;
 #[cfg_attr(docsrs, doc(cfg(feature = "redis-graph")))]
@@ -171,6 +174,7 @@ pub(crate) use internal_pub_sub_commands::*;
 pub use json_commands::*;
 pub use list_commands::*;
 pub use pub_sub_commands::*;
+pub use script::*;
 pub use scripting_commands::*;
 #[cfg_attr(docsrs, doc(cfg(feature = "redis-search")))]
 #[cfg(feature = "redis-search")]
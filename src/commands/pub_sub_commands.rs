@@ -1,5 +1,5 @@
 use crate::{
-    client::{prepare_command, PreparedCommand, PubSubStream},
+    client::{prepare_command, PayloadCodec, PreparedCommand, PubSubStream},
     resp::{
         cmd, CollectionResponse, CommandArgs, KeyValueCollectionResponse, PrimitiveResponse,
         SingleArg, SingleArgCollection, ToArgs,
@@ -7,6 +7,7 @@ use crate::{
     Result,
 };
 use serde::de::DeserializeOwned;
+use std::borrow::Borrow;
 
 /// A group of Redis commands related to [`Pub/Sub`](https://redis.io/docs/manual/pubsub/)
 /// # See Also
@@ -74,6 +75,37 @@ pub trait PubSubCommands<'a> {
         prepare_command(self, cmd("PUBLISH").arg(channel).arg(message))
     }
 
+    /// Posts a message to the given channel, after encoding it with a [`PayloadCodec`].
+    ///
+    /// The message can be passed by value or by reference.
+    /// Subscribers can decode it with the same codec
+    /// thanks to [`PubSubStream::typed`](crate::client::PubSubStream::typed).
+    ///
+    /// # Return
+    /// The number of clients that received the message.
+    ///
+    /// # Errors
+    /// Any error returned by the codec
+    ///
+    /// # See Also
+    /// [<https://redis.io/commands/publish/>](https://redis.io/commands/publish/)
+    fn publish_as<P>(
+        self,
+        channel: impl SingleArg,
+        message: impl Borrow<P::Value>,
+    ) -> Result<PreparedCommand<'a, Self, usize>>
+    where
+        Self: Sized,
+        P: PayloadCodec,
+    {
+        Ok(prepare_command(
+            self,
+            cmd("PUBLISH")
+                .arg(channel)
+                .arg(P::encode(message.borrow())?),
+        ))
+    }
+
     /// Lists the currently active channels.
     ///
     /// # Return
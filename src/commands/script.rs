@@ -0,0 +1,248 @@
+use crate::{
+    client::{
+        prepare_command, BatchPreparedCommand, Client, Pipeline, PreparedCommand, Transaction,
+    },
+    commands::ScriptingCommands,
+    resp::{cmd, Command, CommandArgs, Response, SingleArg, SingleArgCollection},
+    Error, RedisErrorKind, Result,
+};
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, sync::OnceLock};
+
+/// Lua script invoked with [`EVALSHA`](https://redis.io/commands/evalsha/),
+/// falling back to [`EVAL`](https://redis.io/commands/eval/)
+/// when the script is not in the cache of the server.
+///
+/// The SHA1 digest of the script is computed locally, once, the first time it is needed.
+/// A script can be declared once as a `static` and shared by all the callers.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::{BatchPreparedCommand, Client},
+///     commands::{Script, StringCommands},
+///     Result,
+/// };
+///
+/// static GET_AND_APPEND: Script = Script::new("return redis.call('GET', KEYS[1])..ARGV[1]");
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///     client.set("key", "hello").await?;
+///
+///     let value: String = GET_AND_APPEND
+///         .call()
+///         .keys("key")
+///         .args(" world")
+///         .invoke(&client)
+///         .await?;
+///     assert_eq!("hello world", value);
+///
+///     let mut pipeline = client.create_pipeline();
+///     GET_AND_APPEND
+///         .call()
+///         .keys("key")
+///         .args("!")
+///         .in_pipeline::<String>(&mut pipeline)
+///         .queue();
+///     let value: String = pipeline.execute().await?;
+///     assert_eq!("hello!", value);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Script {
+    source: Cow<'static, str>,
+    sha1: OnceLock<String>,
+}
+
+impl Script {
+    /// Declare a script from its Lua source
+    #[must_use]
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source: Cow::Borrowed(source),
+            sha1: OnceLock::new(),
+        }
+    }
+
+    /// Lua source of the script
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// SHA1 hexadecimal digest of the script, as computed by the server
+    #[must_use]
+    pub fn sha1(&self) -> &str {
+        self.sha1.get_or_init(|| {
+            sha1_smol::Sha1::from(self.source.as_bytes())
+                .digest()
+                .to_string()
+        })
+    }
+
+    /// Start an invocation of the script
+    #[must_use]
+    pub fn call(&self) -> ScriptCall<'_> {
+        ScriptCall {
+            script: self,
+            keys_and_args: KeysAndArgs::default(),
+            readonly: false,
+        }
+    }
+}
+
+impl From<String> for Script {
+    fn from(source: String) -> Self {
+        Self {
+            source: Cow::Owned(source),
+            sha1: OnceLock::new(),
+        }
+    }
+}
+
+/// Keys and arguments of the invocation of a script or a function,
+/// written after their number of keys
+#[derive(Default)]
+pub(crate) struct KeysAndArgs {
+    keys: CommandArgs,
+    args: CommandArgs,
+}
+
+impl KeysAndArgs {
+    pub fn keys<K, C>(&mut self, keys: C)
+    where
+        K: SingleArg,
+        C: SingleArgCollection<K>,
+    {
+        self.keys.arg(keys);
+    }
+
+    pub fn args<A, C>(&mut self, args: C)
+    where
+        A: SingleArg,
+        C: SingleArgCollection<A>,
+    {
+        self.args.arg(args);
+    }
+
+    pub fn write_to(&self, command: Command) -> Command {
+        command.arg(self.keys.len()).arg(&self.keys).arg(&self.args)
+    }
+}
+
+/// Invocation of a [`Script`] with its keys and arguments
+pub struct ScriptCall<'s> {
+    script: &'s Script,
+    keys_and_args: KeysAndArgs,
+    readonly: bool,
+}
+
+impl ScriptCall<'_> {
+    /// Keys accessed by the script.
+    ///
+    /// Can be called several times, the keys are appended.
+    #[must_use]
+    pub fn keys<K, C>(mut self, keys: C) -> Self
+    where
+        K: SingleArg,
+        C: SingleArgCollection<K>,
+    {
+        self.keys_and_args.keys(keys);
+        self
+    }
+
+    /// Additional input arguments that should not represent names of keys.
+    ///
+    /// Can be called several times, the arguments are appended.
+    #[must_use]
+    pub fn args<A, C>(mut self, args: C) -> Self
+    where
+        A: SingleArg,
+        C: SingleArgCollection<A>,
+    {
+        self.keys_and_args.args(args);
+        self
+    }
+
+    /// Invoke the script with [`EVALSHA_RO`](https://redis.io/commands/evalsha_ro/)
+    /// and [`EVAL_RO`](https://redis.io/commands/eval_ro/),
+    /// so that it cannot execute commands that modify data.
+    #[must_use]
+    pub fn readonly(mut self) -> Self {
+        self.readonly = true;
+        self
+    }
+
+    /// Invoke the script on a client with `EVALSHA`,
+    /// and with `EVAL` if the script is not in the cache of the server yet.
+    ///
+    /// # Return
+    /// The return value of the script
+    ///
+    /// # Errors
+    /// Any Redis driver [`Error`](crate::Error) that occurs during the send operation,
+    /// or an error raised by the script
+    pub async fn invoke<R>(self, client: &Client) -> Result<R>
+    where
+        R: DeserializeOwned + Send,
+    {
+        let result = prepare_command::<_, R>(client, self.evalsha_command()).await;
+
+        match result {
+            Err(Error::Redis(e)) if e.kind == RedisErrorKind::NoScript => {
+                prepare_command(client, self.eval_command()).await
+            }
+            result => result,
+        }
+    }
+
+    /// Add the invocation of the script to a pipeline, with `EVALSHA`.
+    ///
+    /// A forgotten [`SCRIPT LOAD`](https://redis.io/commands/script-load/) is queued
+    /// just before, so that the script is in the cache of the server when `EVALSHA` is executed,
+    /// without a second round trip breaking the order of the commands of the pipeline.
+    ///
+    /// The returned command must then be [queued](crate::client::BatchPreparedCommand::queue)
+    /// or [forgotten](crate::client::BatchPreparedCommand::forget).
+    pub fn in_pipeline<'a, 'p, R: Response>(
+        self,
+        pipeline: &'a mut Pipeline<'p>,
+    ) -> PreparedCommand<'a, &'a mut Pipeline<'p>, R> {
+        pipeline.script_load::<_, ()>(self.script.source()).forget();
+        prepare_command(pipeline, self.evalsha_command())
+    }
+
+    /// Add the invocation of the script to a transaction, with `EVAL`.
+    ///
+    /// `EVALSHA` is not used since a fallback on `EVAL` after the execution
+    /// of the transaction would break its atomicity.
+    ///
+    /// The returned command must then be [queued](crate::client::BatchPreparedCommand::queue)
+    /// or [forgotten](crate::client::BatchPreparedCommand::forget).
+    pub fn in_transaction<'a, R: Response>(
+        self,
+        transaction: &'a mut Transaction,
+    ) -> PreparedCommand<'a, &'a mut Transaction, R> {
+        prepare_command(transaction, self.eval_command())
+    }
+
+    fn evalsha_command(&self) -> Command {
+        let name = if self.readonly {
+            "EVALSHA_RO"
+        } else {
+            "EVALSHA"
+        };
+        self.keys_and_args
+            .write_to(cmd(name).arg(self.script.sha1()))
+    }
+
+    fn eval_command(&self) -> Command {
+        let name = if self.readonly { "EVAL_RO" } else { "EVAL" };
+        self.keys_and_args
+            .write_to(cmd(name).arg(self.script.source()))
+    }
+}
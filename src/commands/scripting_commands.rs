@@ -401,8 +401,10 @@ impl ToArgs for CallBuilder {
 }
 
 /// Policy option for the [`function_restore`](ScriptingCommands::function_restore) command.
+#[derive(Default)]
 pub enum FunctionRestorePolicy {
     /// Append
+    #[default]
     Default,
     /// Appends the restored libraries to the existing libraries and aborts on collision.
     /// This is the default policy.
@@ -415,12 +417,6 @@ pub enum FunctionRestorePolicy {
     Replace,
 }
 
-impl Default for FunctionRestorePolicy {
-    fn default() -> Self {
-        Self::Default
-    }
-}
-
 impl ToArgs for FunctionRestorePolicy {
     fn write_args(&self, args: &mut CommandArgs) {
         match self {
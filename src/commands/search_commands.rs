@@ -26,8 +26,8 @@ pub trait SearchCommands<'a> {
     /// # Arguments
     /// * `index` - index against which the query is executed.
     /// * `query`- is base filtering query that retrieves the documents.\
-    ///  It follows the exact same syntax as the search query,\
-    ///  including filters, unions, not, optional, and so on.
+    ///   It follows the exact same syntax as the search query,\
+    ///   including filters, unions, not, optional, and so on.
     /// * `options` - See [`FtAggregateOptions`](FtAggregateOptions)
     ///
     /// # Returns
@@ -233,7 +233,7 @@ pub trait SearchCommands<'a> {
     /// * `index` - index name.
     /// * `cursor_id` - id of the cursor.
     /// * `read_size` - number of results to read. This parameter overrides
-    /// [`count`](FtWithCursorOptions::count) specified in [`ft_aggregate`](SearchCommands::ft_aggregate).
+    ///   [`count`](FtWithCursorOptions::count) specified in [`ft_aggregate`](SearchCommands::ft_aggregate).
     ///
     /// # Returns
     /// an instance of [`FtAggregateResult`](FtAggregateResult)
@@ -326,12 +326,12 @@ pub trait SearchCommands<'a> {
     ///
     /// # Notes
     /// * By default, `ft_dropindex` does not delete the document hashes associated with the index.
-    /// Adding the `dd` option deletes the hashes as well.
+    ///   Adding the `dd` option deletes the hashes as well.
     /// * When using `ft_dropindex` with the parameter `dd`, if an index creation is still running
-    /// ([`ft_create`](SearchCommands::ft_create) is running asynchronously),
-    /// only the document hashes that have already been indexed are deleted.
-    /// The document hashes left to be indexed remain in the database.
-    /// You can use [`ft_info`](SearchCommands::ft_info) to check the completion of the indexing.
+    ///   ([`ft_create`](SearchCommands::ft_create) is running asynchronously),
+    ///   only the document hashes that have already been indexed are deleted.
+    ///   The document hashes left to be indexed remain in the database.
+    ///   You can use [`ft_info`](SearchCommands::ft_info) to check the completion of the indexing.
     ///
     /// # Return
     /// the number of new terms that were added.
@@ -353,8 +353,8 @@ pub trait SearchCommands<'a> {
     /// * `index` - full-text index name. You must first create the index using [`ft_create`](SearchCommands::ft_create).
     /// * `query` - query string, as if sent to [`ft_search`](SearchCommands::ft_search).
     /// * `dialect_version` - dialect version under which to execute the query. \
-    ///  If not specified, the query executes under the default dialect version set during module initial loading\
-    ///  or via [`ft_config_set`](SearchCommands::ft_config_set) command.
+    ///   If not specified, the query executes under the default dialect version set during module initial loading\
+    ///   or via [`ft_config_set`](SearchCommands::ft_config_set) command.
     ///
     /// # Notes
     /// * In the returned response, a `+` on a term is an indication of stemming.
@@ -390,8 +390,8 @@ pub trait SearchCommands<'a> {
     /// * `index` - full-text index name. You must first create the index using [`ft_create`](SearchCommands::ft_create).
     /// * `query` - query string, as if sent to [`ft_search`](SearchCommands::ft_search).
     /// * `dialect_version` - dialect version under which to execute the query. \
-    ///  If not specified, the query executes under the default dialect version set during module initial loading\
-    ///  or via [`ft_config_set`](SearchCommands::ft_config_set) command.
+    ///   If not specified, the query executes under the default dialect version set during module initial loading\
+    ///   or via [`ft_config_set`](SearchCommands::ft_config_set) command.
     ///
     /// # Notes
     /// * In the returned response, a `+` on a term is an indication of stemming.
@@ -3211,7 +3211,7 @@ impl FtSearchHighlightOptions {
 
     /// * `open_tag` - prepended to each term match
     /// * `close_tag` - appended to each term match
-    /// If no `TAGS` are specified, a built-in tag value is appended and prepended.
+    ///   If no `TAGS` are specified, a built-in tag value is appended and prepended.
     #[must_use]
     pub fn tags(mut self, open_tag: impl SingleArg, close_tag: impl SingleArg) -> Self {
         Self {
@@ -3233,6 +3233,7 @@ impl ToArgs for FtSearchHighlightOptions {
 
 /// Redis search supported languages
 /// See. [`Supported Languages`](https://redis.io/docs/stack/search/reference/stemming/#supported-languages)
+#[derive(Default)]
 pub enum FtLanguage {
     Arabic,
     Armenian,
@@ -3241,6 +3242,7 @@ pub enum FtLanguage {
     Chinese,
     Danish,
     Dutch,
+    #[default]
     English,
     Finnish,
     French,
@@ -3264,12 +3266,6 @@ pub enum FtLanguage {
     Yiddish,
 }
 
-impl Default for FtLanguage {
-    fn default() -> Self {
-        Self::English
-    }
-}
-
 impl ToArgs for FtLanguage {
     fn write_args(&self, args: &mut CommandArgs) {
         args.arg(match self {
@@ -3539,12 +3535,12 @@ impl FtSuggestion {
                     .command
                     .args
                     .iter()
-                    .any(|a| a.as_slice() == b"WITHSCORES");
+                    .any(|a| a.as_ref() == b"WITHSCORES");
                 let with_payloads = self
                     .command
                     .args
                     .iter()
-                    .any(|a| a.as_slice() == b"WITHPAYLOADS");
+                    .any(|a| a.as_ref() == b"WITHPAYLOADS");
 
                 let mut suggestions = if let Some(size) = seq.size_hint() {
                     Vec::with_capacity(size)
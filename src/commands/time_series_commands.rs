@@ -101,7 +101,7 @@ pub trait TimeSeriesCommands<'a> {
     /// * If a key already exists, you get a Redis error reply, TSDB: key already exists.
     ///   You can check for the existence of a key with the [`exists`](crate::commands::GenericCommands::exists) command.
     /// * Other commands that also create a new time series when called with a key that does not exist are
-    /// [`ts_add`](TimeSeriesCommands::ts_add), [`ts_incrby`](TimeSeriesCommands::ts_incrby), and [`ts_decrby`](TimeSeriesCommands::ts_decrby).
+    ///   [`ts_add`](TimeSeriesCommands::ts_add), [`ts_incrby`](TimeSeriesCommands::ts_incrby), and [`ts_decrby`](TimeSeriesCommands::ts_decrby).
     ///
     /// # See Also
     /// * [<https://redis.io/commands/ts.create/>](https://redis.io/commands/ts.create/)
@@ -122,7 +122,7 @@ pub trait TimeSeriesCommands<'a> {
     /// # Arguments
     /// * `src_key` - key name for the source time series.
     /// * `dst_key` - key name for destination (compacted) time series.
-    ///  It must be created before `ts_createrule` is called.
+    ///   It must be created before `ts_createrule` is called.
     /// * `aggregator` - aggregates results into time buckets by taking an aggregation type
     /// * `bucket_duration` - duration of each aggregation bucket, in milliseconds.
     /// * `options` - See [`TsCreateRuleOptions`](TsCreateRuleOptions)
@@ -131,11 +131,11 @@ pub trait TimeSeriesCommands<'a> {
     /// * Only new samples that are added into the source series after the creation of the rule will be aggregated.
     /// * Calling `ts_createrule` with a nonempty `dst_key` may result in inconsistencies between the raw and the compacted data.
     /// * Explicitly adding samples to a compacted time series (using [`ts_add`](TimeSeriesCommands::ts_add),
-    ///  [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby), or [`ts_decrby`](TimeSeriesCommands::ts_decrby))
-    /// may result in inconsistencies between the raw and the compacted data. The compaction process may override such samples.
+    ///   [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby), or [`ts_decrby`](TimeSeriesCommands::ts_decrby))
+    ///   may result in inconsistencies between the raw and the compacted data. The compaction process may override such samples.
     /// * If no samples are added to the source time series during a bucket period. no compacted sample is added to the destination time series.
     /// * The timestamp of a compacted sample added to the destination time series is set to the start timestamp the appropriate compaction bucket.
-    /// For example, for a 10-minute compaction bucket with no alignment, the compacted samples timestamps are `x:00`, `x:10`, `x:20`, and so on.
+    ///   For example, for a 10-minute compaction bucket with no alignment, the compacted samples timestamps are `x:00`, `x:10`, `x:20`, and so on.
     ///
     /// # See Also
     /// * [<https://redis.io/commands/ts.createrule/>](https://redis.io/commands/ts.createrule/)
@@ -175,9 +175,9 @@ pub trait TimeSeriesCommands<'a> {
     /// * When specified key does not exist, a new time series is created.
     /// * You can use this command as a counter or gauge that automatically gets history as a time series.
     /// * Explicitly adding samples to a compacted time series (using [`ts_add`](TimeSeriesCommands::ts_add),
-    ///  [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
-    /// or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
-    /// The compaction process may override such samples.
+    ///   [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
+    ///   or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
+    ///   The compaction process may override such samples.
     ///
     /// # See Also
     /// * [<https://redis.io/commands/ts.decrby/>](https://redis.io/commands/ts.decrby/)
@@ -287,9 +287,9 @@ pub trait TimeSeriesCommands<'a> {
     /// * When specified key does not exist, a new time series is created.
     /// * You can use this command as a counter or gauge that automatically gets history as a time series.
     /// * Explicitly adding samples to a compacted time series (using [`ts_add`](TimeSeriesCommands::ts_add),
-    ///  [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
-    /// or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
-    /// The compaction process may override such samples.
+    ///   [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
+    ///   or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
+    ///   The compaction process may override such samples.
     ///
     /// # See Also
     /// * [<https://redis.io/commands/ts.incrby/>](https://redis.io/commands/ts.incrby/)
@@ -332,20 +332,20 @@ pub trait TimeSeriesCommands<'a> {
     ///   * `key` - the key name for the time series.
     ///   * `timestamp` - the UNIX sample timestamp in milliseconds or * to set the timestamp according to the server clock.
     ///   * `value` - numeric data value of the sample (double). \
-    /// The double number should follow [`RFC 7159`](https://tools.ietf.org/html/rfc7159) (a JSON standard).
-    /// The parser rejects overly large values that would not fit in binary64.
-    /// It does not accept `NaN` or `infinite` values.
+    ///     The double number should follow [`RFC 7159`](https://tools.ietf.org/html/rfc7159) (a JSON standard).
+    ///     The parser rejects overly large values that would not fit in binary64.
+    ///     It does not accept `NaN` or `infinite` values.
     ///
     /// # Return
     /// a collection of the timestamps of added samples
     ///
     /// # Notes
     /// * If timestamp is older than the retention period compared to the maximum existing timestamp,
-    /// the sample is discarded and an error is returned.
+    ///   the sample is discarded and an error is returned.
     /// * Explicitly adding samples to a compacted time series (using [`ts_add`](TimeSeriesCommands::ts_add),
-    /// [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
-    /// or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
-    /// The compaction process may override such samples.
+    ///   [`ts_madd`](TimeSeriesCommands::ts_madd), [`ts_incrby`](TimeSeriesCommands::ts_incrby),
+    ///   or [`ts_decrby`](TimeSeriesCommands::ts_decrby)) may result in inconsistencies between the raw and the compacted data.
+    ///   The compaction process may override such samples.
     ///
     /// # See Also
     /// * [<https://redis.io/commands/ts.madd/>](https://redis.io/commands/ts.madd/)
@@ -399,9 +399,9 @@ pub trait TimeSeriesCommands<'a> {
     ///
     /// # Arguments
     /// * `from_timestamp` - start timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `-` to denote the timestamp of the earliest sample in the time series.
+    ///   or `-` to denote the timestamp of the earliest sample in the time series.
     /// * `to_timestamp` - end timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `+` to denote the timestamp of the latest sample in the time series.
+    ///   or `+` to denote the timestamp of the latest sample in the time series.
     /// * `options` - See [`TsMRangeOptions`](TsMRangeOptions)
     /// * `filters` - filters time series based on their labels and label values, with these options:
     ///   * `label=value`, where `label` equals `value`
@@ -448,9 +448,9 @@ pub trait TimeSeriesCommands<'a> {
     ///
     /// # Arguments
     /// * `from_timestamp` - start timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `-` to denote the timestamp of the earliest sample in the time series.
+    ///   or `-` to denote the timestamp of the earliest sample in the time series.
     /// * `to_timestamp` - end timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `+` to denote the timestamp of the latest sample in the time series.
+    ///   or `+` to denote the timestamp of the latest sample in the time series.
     /// * `options` - See [`TsMRangeOptions`](TsMRangeOptions)
     /// * `filters` - filters time series based on their labels and label values, with these options:
     ///   * `label=value`, where `label` equals `value`
@@ -535,9 +535,9 @@ pub trait TimeSeriesCommands<'a> {
     /// # Arguments
     /// * `key` - the key name for the time series.
     /// * `from_timestamp` - start timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `-`to denote the timestamp of the earliest sample in the time series.
+    ///   or `-`to denote the timestamp of the earliest sample in the time series.
     /// * `to_timestamp` - end timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `+` to denote the timestamp of the latest sample in the time series.
+    ///   or `+` to denote the timestamp of the latest sample in the time series.
     /// * `options` - See [`TsRangeOptions`](TsRangeOptions)
     ///
     /// # Return
@@ -577,9 +577,9 @@ pub trait TimeSeriesCommands<'a> {
     /// # Arguments
     /// * `key` - the key name for the time series.
     /// * `from_timestamp` - start timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `-`to denote the timestamp of the earliest sample in the time series.
+    ///   or `-`to denote the timestamp of the earliest sample in the time series.
     /// * `to_timestamp` - end timestamp for the range query (integer UNIX timestamp in milliseconds)
-    ///    or `+` to denote the timestamp of the latest sample in the time series.
+    ///   or `+` to denote the timestamp of the latest sample in the time series.
     /// * `options` - See [`TsRangeOptions`](TsRangeOptions)
     ///
     /// # Return
@@ -619,9 +619,9 @@ pub trait TimeSeriesCommands<'a> {
 ///
 /// # Notes
 /// * You can use this command to add data to a nonexisting time series in a single command.
-/// This is why [`retention`](TsAddOptions::retention), [`encoding`](TsAddOptions::encoding),
-/// [`chunk_size`](TsAddOptions::chunk_size), [`on_duplicate`](TsAddOptions::on_duplicate),
-/// and [`labels`](TsAddOptions::labels) are optional arguments.
+///   This is why [`retention`](TsAddOptions::retention), [`encoding`](TsAddOptions::encoding),
+///   [`chunk_size`](TsAddOptions::chunk_size), [`on_duplicate`](TsAddOptions::on_duplicate),
+///   and [`labels`](TsAddOptions::labels) are optional arguments.
 /// * Setting [`retention`](TsAddOptions::retention) and [`labels`](TsAddOptions::labels) introduces additional time complexity.
 #[derive(Default)]
 pub struct TsAddOptions {
@@ -896,7 +896,7 @@ pub enum TsAggregationType {
     #[serde(rename = "VAR.P")]
     VarP,
     /// Sample variance of the values
-    #[serde(rename = "VAR.P")]
+    #[serde(rename = "VAR.S")]
     VarS,
     /// Time-weighted average over the bucket's timeframe (since RedisTimeSeries v1.8)
     Twa,
@@ -955,9 +955,9 @@ impl ToArgs for TsCreateRuleOptions {
 ///
 /// # Notes
 /// * You can use this command to add data to a nonexisting time series in a single command.
-/// This is why `retention`, `uncompressed`, `chunk_size`, and `labels` are optional arguments.
+///   This is why `retention`, `uncompressed`, `chunk_size`, and `labels` are optional arguments.
 /// * When specified and the key doesn't exist, a new time series is created.
-/// Setting the `retention` and `labels` options introduces additional time complexity.
+///   Setting the `retention` and `labels` options introduces additional time complexity.
 #[derive(Default)]
 pub struct TsIncrByDecrByOptions {
     command_args: CommandArgs,
@@ -1385,12 +1385,12 @@ impl TsMRangeOptions {
     /// when `aggregator` values are:
     /// * `sum`, `count` - the value reported for each empty bucket is `0`
     /// * `last` - the value reported for each empty bucket is the value
-    ///    of the last sample before the bucket's start.
-    ///    `NaN` when no such sample.
+    ///   of the last sample before the bucket's start.
+    ///   `NaN` when no such sample.
     /// * `twa` - the value reported for each empty bucket is the average value
-    ///    over the bucket's timeframe based on linear interpolation
-    ///    of the last sample before the bucket's start and the first sample after the bucket's end.
-    ///    `NaN` when no such samples.
+    ///   over the bucket's timeframe based on linear interpolation
+    ///   of the last sample before the bucket's start and the first sample after the bucket's end.
+    ///   `NaN` when no such samples.
     /// * `min`, `max`, `range`, `avg`, `first`, `std.p`, `std.s` - the value reported for each empty bucket is `NaN`
     ///
     /// Regardless of the values of `from_timestamp` and `to_timestamp`,
@@ -1566,12 +1566,12 @@ impl TsRangeOptions {
     /// when `aggregator` values are:
     /// * `sum`, `count` - the value reported for each empty bucket is `0`
     /// * `last` - the value reported for each empty bucket is the value
-    ///    of the last sample before the bucket's start.
-    ///    `NaN` when no such sample.
+    ///   of the last sample before the bucket's start.
+    ///   `NaN` when no such sample.
     /// * `twa` - the value reported for each empty bucket is the average value
-    ///    over the bucket's timeframe based on linear interpolation
-    ///    of the last sample before the bucket's start and the first sample after the bucket's end.
-    ///    `NaN` when no such samples.
+    ///   over the bucket's timeframe based on linear interpolation
+    ///   of the last sample before the bucket's start and the first sample after the bucket's end.
+    ///   `NaN` when no such samples.
     /// * `min`, `max`, `range`, `avg`, `first`, `std.p`, `std.s` - the value reported for each empty bucket is `NaN`
     ///
     /// Regardless of the values of `from_timestamp` and `to_timestamp`,
@@ -50,6 +50,11 @@ pub enum Error {
     Tls(String),
     /// The I/O operation’s timeout expired
     Timeout(String),
+    /// Raised if a reply of the Redis server is malformed
+    /// or exceeds the [`ReplyLimits`](crate::client::ReplyLimits) of the client.
+    ///
+    /// The connection is reset after such an error.
+    Protocol(String),
     /// Internal error to trigger retry sending the command
     #[doc(hidden)]
     Retry(SmallVec<[RetryReason; 1]>),
@@ -71,6 +76,7 @@ impl std::fmt::Display for Error {
             Error::Tls(e) => f.write_fmt(format_args!("Tls error: {}", e)),
             Error::Retry(r) => f.write_fmt(format_args!("Retry: {:?}", r)),
             Error::Timeout(e) => f.write_fmt(format_args!("Timeout error: {}", e)),
+            Error::Protocol(e) => f.write_fmt(format_args!("Protocol error: {}", e)),
             Error::EOF => f.write_str("EOF error"),
         }
     }
@@ -164,6 +170,7 @@ pub enum RedisErrorKind {
     Err,
     InProg,
     IoErr,
+    Loading,
     MasterDown,
     MisConf,
     Moved {
@@ -214,6 +221,7 @@ impl FromStr for RedisErrorKind {
             "ERR" => Ok(Self::Err),
             "INPROG" => Ok(Self::InProg),
             "IOERR" => Ok(Self::IoErr),
+            "LOADING" => Ok(Self::Loading),
             "MASTERDOWN" => Ok(Self::MasterDown),
             "MISCONF" => Ok(Self::MisConf),
             "NOAUTH" => Ok(Self::NoAuth),
@@ -262,6 +270,7 @@ impl Display for RedisErrorKind {
             RedisErrorKind::Err => f.write_str("ERR"),
             RedisErrorKind::InProg => f.write_str("INPROG"),
             RedisErrorKind::IoErr => f.write_str("IOERR"),
+            RedisErrorKind::Loading => f.write_str("LOADING"),
             RedisErrorKind::MasterDown => f.write_str("MASTERDOWN"),
             RedisErrorKind::MisConf => f.write_str("MISCONF"),
             RedisErrorKind::Moved {
@@ -22,6 +22,7 @@ rustis is a Redis client for Rust.
 * [TLS](https://redis.io/docs/manual/security/encryption/) support
 * [Transaction](https://redis.io/docs/manual/transactions/) support
 * [Pub/sub](https://redis.io/docs/manual/pubsub/) support
+* [Client-side caching](https://redis.io/docs/manual/client-side-caching/) support
 * [Sentinel](https://redis.io/docs/manual/sentinel/) support
 * [LUA Scripts/Functions](https://redis.io/docs/manual/programmability/) support
 * [Cluster](https://redis.io/docs/manual/scaling/) support
@@ -40,6 +41,14 @@ rustis is a Redis client for Rust.
 | `tokio-tls` | Tokio TLS support (optional) |
 | `async-std-tls` | async-std TLS support (optional) |
 | `pool` | Pooled client manager (optional) |
+| `json` | JSON `Json<T>` values and [`PayloadCodec`](client::PayloadCodec) for pub/sub messages (optional) |
+| `msgpack` | [MessagePack](https://msgpack.org/) `MsgPack<T>` values and [`PayloadCodec`](client::PayloadCodec) (optional) |
+| `cbor` | [CBOR](https://cbor.io/) `Cbor<T>` values and [`PayloadCodec`](client::PayloadCodec) (optional) |
+| `lz4` | [LZ4](https://lz4.org/) `Compressed<Lz4, T>` values (optional) |
+| `zstd` | [Zstandard](https://facebook.github.io/zstd/) `Compressed<Zstd, T>` values (optional) |
+| `mock-server` | In-process scriptable [`MockServer`](mock::MockServer) to unit-test code without running Redis (optional) |
+| `fault-injection` | Configurable [`FaultPlan`](client::FaultPlan) to test retry and failover code paths (optional) |
+| `record-replay` | Record the traffic of a client with a [`TrafficRecorder`](client::TrafficRecorder) and replay it offline (optional) |
 | `redis-json` | [RedisJSON v2.4](https://redis.io/docs/stack/json/) support (optional) |
 | `redis-search` | [RedisSearch v2.6](https://redis.io/docs/stack/search/) support (optional) |
 | `redis-graph` | [RedisGraph v2.10](https://redis.io/docs/stack/graph/) support (optional) |
@@ -158,6 +167,9 @@ async fn main() -> Result<()> {
 pub mod client;
 pub mod commands;
 mod error;
+#[cfg(feature = "mock-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock-server")))]
+pub mod mock;
 mod network;
 pub mod resp;
 
@@ -0,0 +1,384 @@
+use crate::{
+    resp::{Command, CommandArgs, RespVersion, ServerCodec, ServerCommand, ToArgs, Value},
+    RedisError, RedisErrorKind, Result,
+};
+use futures_util::{SinkExt, StreamExt};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tokio_util::codec::Framed;
+
+/// Version reported by the mock server in the reply to the `HELLO` command
+const MOCK_SERVER_VERSION: &str = "7.2.0";
+
+/// In-process scriptable mock Redis server
+///
+/// The server listens on a random local port, accepts any number of connections
+/// and replies to each received command with the first matching [`Expectation`].
+///
+/// Without matching expectation:
+/// * `HELLO`, `PING`, `AUTH`, `SELECT` and `CLIENT SETNAME` receive the same replies as from a real Redis server,
+///   so that a [`Client`](crate::client::Client) can connect,
+/// * commands not supported by this crate receive an `ERR unknown command` error reply,
+/// * other commands receive an `ERR` error reply.
+///
+/// The server is stopped and all its connections are closed when it is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a new mock server listening on a random port of the loopback interface
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+        let accept_task = tokio::spawn(accept(listener, state.clone()));
+
+        Ok(Self {
+            addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Local address the server is listening on
+    #[must_use]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Connection URI of the server, to be passed to [`Client::connect`](crate::client::Client::connect)
+    #[must_use]
+    pub fn uri(&self) -> String {
+        format!("redis://{}", self.addr)
+    }
+
+    /// Registers an expectation for a command with exactly the given arguments
+    ///
+    /// The command name is case insensitive, arguments are compared byte by byte.
+    #[must_use]
+    pub fn on(&self, name: &str, args: impl ToArgs) -> Expectation<'_> {
+        let args = CommandArgs::default().arg(args).build();
+        Expectation::new(
+            self,
+            name,
+            Some(args.into_iter().map(<[u8]>::to_vec).collect()),
+        )
+    }
+
+    /// Registers an expectation for a command, whatever its arguments
+    ///
+    /// The command name is case insensitive.
+    #[must_use]
+    pub fn on_any(&self, name: &str) -> Expectation<'_> {
+        Expectation::new(self, name, None)
+    }
+
+    /// Sends a push message to all the connected clients
+    ///
+    /// The message is typically a [`Value::Push`], which is encoded as an array
+    /// for the clients which did not switch to RESP3.
+    pub fn push(&self, message: Value) {
+        let message = Arc::new(message);
+        self.broadcast(|| Event::Push(message.clone()));
+    }
+
+    /// Abruptly closes all the current connections
+    ///
+    /// The server keeps accepting new connections.
+    pub fn disconnect_all(&self) {
+        self.broadcast(|| Event::Disconnect);
+    }
+
+    /// Commands received so far by the server, from all connections, in reception order
+    ///
+    /// Commands not supported by this crate are not recorded.
+    #[must_use]
+    pub fn received(&self) -> Vec<Command> {
+        self.state.lock().unwrap().received.clone()
+    }
+
+    /// Removes all the registered expectations and forgets the received commands
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.rules.clear();
+        state.received.clear();
+    }
+
+    fn broadcast(&self, event: impl Fn() -> Event) {
+        self.state
+            .lock()
+            .unwrap()
+            .connections
+            .retain(|connection| connection.send(event()).is_ok());
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.disconnect_all();
+    }
+}
+
+/// Expectation of a command received by a [`MockServer`]
+///
+/// Built with [`MockServer::on`] or [`MockServer::on_any`]
+/// and registered by one of the terminal functions
+/// ([`reply`](Expectation::reply), [`moved`](Expectation::moved), [`disconnect`](Expectation::disconnect), ...).
+///
+/// By default, an expectation matches all the commands it describes.
+/// When several expectations match a command, the first registered one wins.
+pub struct Expectation<'a> {
+    server: &'a MockServer,
+    name: String,
+    args: Option<Vec<Vec<u8>>>,
+    once: bool,
+}
+
+impl<'a> Expectation<'a> {
+    fn new(server: &'a MockServer, name: &str, args: Option<Vec<Vec<u8>>>) -> Self {
+        Self {
+            server,
+            name: name.to_ascii_uppercase(),
+            args,
+            once: false,
+        }
+    }
+
+    /// The expectation is removed after its first match
+    ///
+    /// Useful to script a sequence of different replies to the same command.
+    #[must_use]
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Replies with the given value
+    pub fn reply(self, value: Value) {
+        self.register(Action::Reply(Arc::new(value)), None);
+    }
+
+    /// Replies with the given value after a delay
+    ///
+    /// The next commands of the same connection are not processed before the reply is sent.
+    pub fn reply_after(self, delay: Duration, value: Value) {
+        self.register(Action::Reply(Arc::new(value)), Some(delay));
+    }
+
+    /// Replies with an error
+    pub fn error(self, kind: RedisErrorKind, description: impl Into<String>) {
+        let error = RedisError {
+            kind,
+            description: description.into(),
+        };
+        self.register(Action::Reply(Arc::new(Value::Error(error))), None);
+    }
+
+    /// Replies with a `MOVED` redirect to the given address
+    pub fn moved(self, hash_slot: u16, address: (impl Into<String>, u16)) {
+        let address = (address.0.into(), address.1);
+        self.error(RedisErrorKind::Moved { hash_slot, address }, "");
+    }
+
+    /// Replies with an `ASK` redirect to the given address
+    pub fn ask(self, hash_slot: u16, address: (impl Into<String>, u16)) {
+        let address = (address.0.into(), address.1);
+        self.error(RedisErrorKind::Ask { hash_slot, address }, "");
+    }
+
+    /// Abruptly closes the connection instead of replying
+    pub fn disconnect(self) {
+        self.register(Action::Disconnect, None);
+    }
+
+    fn register(self, action: Action, delay: Option<Duration>) {
+        self.server.state.lock().unwrap().rules.push(Rule {
+            name: self.name,
+            args: self.args,
+            once: self.once,
+            action,
+            delay,
+        });
+    }
+}
+
+#[derive(Clone)]
+enum Action {
+    Reply(Arc<Value>),
+    Disconnect,
+}
+
+struct Rule {
+    name: String,
+    args: Option<Vec<Vec<u8>>>,
+    once: bool,
+    action: Action,
+    delay: Option<Duration>,
+}
+
+impl Rule {
+    // `Option::is_none_or` requires Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    fn matches(&self, command: &ServerCommand) -> bool {
+        self.name == command.name()
+            && self.args.as_ref().map_or(true, |args| {
+                args.len() == command.args().len()
+                    && args
+                        .iter()
+                        .zip(command.args())
+                        .all(|(expected, actual)| expected[..] == *actual)
+            })
+    }
+}
+
+enum Event {
+    Push(Arc<Value>),
+    Disconnect,
+}
+
+#[derive(Default)]
+struct State {
+    rules: Vec<Rule>,
+    received: Vec<Command>,
+    connections: Vec<mpsc::UnboundedSender<Event>>,
+    next_connection_id: i64,
+}
+
+impl State {
+    fn handle(&mut self, command: &ServerCommand) -> Option<(Action, Option<Duration>)> {
+        if let ServerCommand::Known(command) = command {
+            self.received.push(command.clone());
+        }
+
+        let idx = self.rules.iter().position(|rule| rule.matches(command))?;
+        let rule = &self.rules[idx];
+        let result = (rule.action.clone(), rule.delay);
+        if rule.once {
+            self.rules.remove(idx);
+        }
+
+        Some(result)
+    }
+}
+
+async fn accept(listener: TcpListener, state: Arc<Mutex<State>>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let id = {
+            let mut state = state.lock().unwrap();
+            state.connections.push(sender);
+            state.next_connection_id += 1;
+            state.next_connection_id
+        };
+        tokio::spawn(serve(id, stream, state.clone(), receiver));
+    }
+}
+
+async fn serve(
+    id: i64,
+    stream: TcpStream,
+    state: Arc<Mutex<State>>,
+    mut events: mpsc::UnboundedReceiver<Event>,
+) -> Result<()> {
+    let mut framed = Framed::new(stream, ServerCodec::default());
+
+    loop {
+        tokio::select! {
+            command = framed.next() => {
+                let Some(command) = command else {
+                    break;
+                };
+                let command = command?;
+
+                let handled = state.lock().unwrap().handle(&command);
+                let (action, delay) = match handled {
+                    Some(handled) => handled,
+                    None => (Action::Reply(Arc::new(default_reply(id, &command, framed.codec_mut()))), None),
+                };
+
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+
+                match action {
+                    Action::Reply(value) => framed.send(&*value).await?,
+                    Action::Disconnect => break,
+                }
+            }
+            event = events.recv() => match event {
+                Some(Event::Push(message)) => framed.send(&*message).await?,
+                Some(Event::Disconnect) | None => break,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replies to the commands without matching expectation
+fn default_reply(id: i64, command: &ServerCommand, codec: &mut ServerCodec) -> Value {
+    let command = match command {
+        ServerCommand::Known(command) => command,
+        ServerCommand::Unknown { name, args } => {
+            return Value::Error(RedisError {
+                kind: RedisErrorKind::Err,
+                description: format!(
+                    "unknown command '{name}', with args beginning with: {}",
+                    args.into_iter()
+                        .map(|arg| format!("'{}' ", String::from_utf8_lossy(arg)))
+                        .collect::<String>()
+                ),
+            })
+        }
+    };
+    let mut args = command.args.into_iter();
+    let ok = || Value::SimpleString("OK".to_owned());
+
+    match (command.name, args.next()) {
+        ("HELLO", version) => {
+            if version == Some(b"3") {
+                codec.set_version(RespVersion::Resp3);
+            }
+            let proto = match codec.version() {
+                RespVersion::Resp2 => 2,
+                RespVersion::Resp3 => 3,
+            };
+            hello_reply(id, proto)
+        }
+        ("PING", None) => Value::SimpleString("PONG".to_owned()),
+        ("PING", Some(message)) => Value::BulkString(message.to_vec()),
+        ("AUTH" | "SELECT", _) => ok(),
+        ("CLIENT", Some(sub_command)) if sub_command.eq_ignore_ascii_case(b"SETNAME") => ok(),
+        _ => Value::Error(RedisError {
+            kind: RedisErrorKind::Err,
+            description: format!("no expectation registered on the mock server for {command:?}"),
+        }),
+    }
+}
+
+fn hello_reply(id: i64, proto: i64) -> Value {
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+    Value::Map(HashMap::from([
+        (str("server"), str("redis")),
+        (str("version"), str(MOCK_SERVER_VERSION)),
+        (str("proto"), Value::Integer(proto)),
+        (str("id"), Value::Integer(id)),
+        (str("mode"), str("standalone")),
+        (str("role"), str("master")),
+        (str("modules"), Value::Array(Vec::new())),
+    ]))
+}
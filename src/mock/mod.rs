@@ -0,0 +1,56 @@
+/*!
+In-process scriptable mock Redis server, to unit-test code using a [`Client`](crate::client::Client)
+without running Redis
+
+The [`MockServer`] listens on a local TCP port and speaks the
+[RESP](https://redis.io/docs/reference/protocol-spec/) protocol with the [`ServerCodec`](crate::resp::ServerCodec).
+Tests register expectations on the server, then connect a regular [`Client`](crate::client::Client)
+to [`MockServer::addr`].
+
+The following scenarios can be scripted:
+* replies to a command, optionally delayed with [`Expectation::reply_after`],
+* error replies, including [`MOVED`](Expectation::moved) and [`ASK`](Expectation::ask) redirects,
+* abrupt disconnections with [`Expectation::disconnect`] or [`MockServer::disconnect_all`],
+* push messages sent to all the connected clients with [`MockServer::push`].
+
+With the feature `record-replay`, the [`ReplayServer`] replays the traffic
+recorded by a [`TrafficRecorder`](crate::client::TrafficRecorder), to reproduce production incidents offline.
+
+This module is only available with the feature `mock-server`.
+
+# Example
+```
+use rustis::{
+    client::Client,
+    commands::StringCommands,
+    mock::MockServer,
+    resp::Value,
+    Result,
+};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::main)]
+#[cfg_attr(feature = "async-std-runtime", async_std::main)]
+async fn main() -> Result<()> {
+    let server = MockServer::start().await?;
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(b"value".to_vec()));
+
+    let client = Client::connect(server.uri()).await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+```
+*/
+
+mod mock_server;
+#[cfg_attr(docsrs, doc(cfg(feature = "record-replay")))]
+#[cfg(feature = "record-replay")]
+mod replay_server;
+
+pub use mock_server::*;
+#[cfg_attr(docsrs, doc(cfg(feature = "record-replay")))]
+#[cfg(feature = "record-replay")]
+pub use replay_server::*;
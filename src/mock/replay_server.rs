@@ -0,0 +1,223 @@
+use crate::{
+    client::{TrafficEvent, TrafficEventKind, TrafficRecording, REDACTED_ARG, STREAMED_ARG},
+    resp::{BufferDecoder, RespDeserializer},
+    Result,
+};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_util::codec::FramedRead;
+
+/// Command received by a [`ReplayServer`] which differs from the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayMismatch {
+    /// Recorded command, as encoded on the wire.
+    ///
+    /// Empty if the command has been received after the end of the recorded session.
+    pub expected: Vec<u8>,
+    /// Received command, as encoded on the wire
+    pub actual: Vec<u8>,
+}
+
+/// Server replaying the traffic recorded by a [`TrafficRecorder`](crate::client::TrafficRecorder)
+///
+/// Each connection accepted by the server replays the next recorded session,
+/// i.e. the events between two [`Connect`](TrafficEventKind::Connect) events:
+/// * recorded commands are awaited from the client and compared with the received ones,
+///   differences are reported by [`mismatches`](ReplayServer::mismatches),
+/// * recorded replies and push messages are sent as is,
+/// * recorded disconnections close the connection,
+/// * commands received after the end of the session are reported as mismatches
+///   and receive an `ERR` error reply.
+///
+/// The recording of a client connected to several servers (cluster, sentinel, replicas)
+/// should be filtered first with [`TrafficRecording::filter_connection`].
+///
+/// This struct is only available with the feature `record-replay`.
+pub struct ReplayServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl ReplayServer {
+    /// Starts a new replay server listening on a random port of the loopback interface.
+    ///
+    /// Replies are sent as soon as possible.
+    pub async fn start(recording: TrafficRecording) -> Result<Self> {
+        Self::start_with_timing(recording, false).await
+    }
+
+    /// Starts a new replay server which sends the replies with the delays of the recording
+    pub async fn start_realtime(recording: TrafficRecording) -> Result<Self> {
+        Self::start_with_timing(recording, true).await
+    }
+
+    async fn start_with_timing(recording: TrafficRecording, realtime: bool) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            sessions: split_sessions(recording.events()),
+            mismatches: Vec::new(),
+            connection_tasks: Vec::new(),
+        }));
+        let accept_task = tokio::spawn(accept(listener, state.clone(), realtime));
+
+        Ok(Self {
+            addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Local address the server is listening on
+    #[must_use]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Connection URI of the server, to be passed to [`Client::connect`](crate::client::Client::connect)
+    #[must_use]
+    pub fn uri(&self) -> String {
+        format!("redis://{}", self.addr)
+    }
+
+    /// Received commands which differ from the recorded ones
+    #[must_use]
+    pub fn mismatches(&self) -> Vec<ReplayMismatch> {
+        self.state.lock().unwrap().mismatches.clone()
+    }
+
+    /// Number of recorded sessions not replayed yet
+    #[must_use]
+    pub fn remaining_sessions(&self) -> usize {
+        self.state.lock().unwrap().sessions.len()
+    }
+}
+
+impl Drop for ReplayServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        for connection_task in self.state.lock().unwrap().connection_tasks.drain(..) {
+            connection_task.abort();
+        }
+    }
+}
+
+struct State {
+    sessions: VecDeque<Vec<TrafficEvent>>,
+    mismatches: Vec<ReplayMismatch>,
+    connection_tasks: Vec<JoinHandle<Result<()>>>,
+}
+
+fn split_sessions(events: &[TrafficEvent]) -> VecDeque<Vec<TrafficEvent>> {
+    let mut sessions = VecDeque::new();
+    let mut session = Vec::new();
+
+    for event in events {
+        if event.kind == TrafficEventKind::Connect && !session.is_empty() {
+            sessions.push_back(std::mem::take(&mut session));
+        }
+        session.push(event.clone());
+    }
+
+    if !session.is_empty() {
+        sessions.push_back(session);
+    }
+
+    sessions
+}
+
+async fn accept(listener: TcpListener, state: Arc<Mutex<State>>, realtime: bool) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let mut guard = state.lock().unwrap();
+        // without remaining session, the connection is closed immediately
+        if let Some(session) = guard.sessions.pop_front() {
+            let task = tokio::spawn(replay(stream, session, state.clone(), realtime));
+            guard.connection_tasks.push(task);
+        }
+    }
+}
+
+async fn replay(
+    stream: TcpStream,
+    session: Vec<TrafficEvent>,
+    state: Arc<Mutex<State>>,
+    realtime: bool,
+) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = FramedRead::new(reader, BufferDecoder::default());
+    let mut previous: Option<(Instant, &TrafficEvent)> = None;
+
+    for event in &session {
+        if realtime && matches!(event.kind, TrafficEventKind::Reply | TrafficEventKind::Push) {
+            if let Some((instant, previous_event)) = previous {
+                let delay = event.elapsed.saturating_sub(previous_event.elapsed);
+                tokio::time::sleep_until((instant + delay).into()).await;
+            }
+        }
+
+        match event.kind {
+            TrafficEventKind::Connect => (),
+            TrafficEventKind::Command => {
+                let Some(frame) = reader.next().await else {
+                    return Ok(());
+                };
+                let frame = frame?;
+                if !matches_recorded_command(&event.data, &frame) {
+                    state.lock().unwrap().mismatches.push(ReplayMismatch {
+                        expected: event.data.clone(),
+                        actual: frame.to_vec(),
+                    });
+                }
+            }
+            TrafficEventKind::Reply | TrafficEventKind::Push => {
+                writer.write_all(&event.data).await?;
+            }
+            TrafficEventKind::Disconnect => return Ok(()),
+        }
+
+        previous = Some((Instant::now(), event));
+    }
+
+    // the recorded session is over: the next commands are unexpected
+    while let Some(frame) = reader.next().await {
+        state.lock().unwrap().mismatches.push(ReplayMismatch {
+            expected: Vec::new(),
+            actual: frame?.to_vec(),
+        });
+        writer
+            .write_all(b"-ERR unexpected command after the end of the recorded session\r\n")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Compare a received command to the recorded one, argument by argument,
+/// a redacted or streamed argument matching any argument
+fn matches_recorded_command(recorded: &[u8], received: &[u8]) -> bool {
+    if recorded == received {
+        return true;
+    }
+
+    let parse = |command| Vec::<&[u8]>::deserialize(&mut RespDeserializer::new(command));
+    let (Ok(recorded), Ok(received)) = (parse(recorded), parse(received)) else {
+        return false;
+    };
+
+    recorded.len() == received.len()
+        && recorded.iter().zip(received).all(|(recorded, received)| {
+            *recorded == REDACTED_ARG || *recorded == STREAMED_ARG || *recorded == received
+        })
+}
@@ -5,6 +5,7 @@ use futures_util::{Future, FutureExt};
 use log::{debug, info};
 use socket2::{SockRef, TcpKeepalive};
 use std::{
+    net::SocketAddr,
     pin::Pin,
     task::{Context, Poll},
     time::Duration,
@@ -183,6 +184,17 @@ where
     return JoinHandle::AsyncStd(async_std::task::spawn(future));
 }
 
+/// Resolve a host name to socket addresses
+pub(crate) async fn lookup_host(host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+    #[cfg(feature = "tokio-runtime")]
+    return Ok(tokio::net::lookup_host((host, port)).await?.collect());
+    #[cfg(feature = "async-std-runtime")]
+    {
+        use async_std::net::ToSocketAddrs;
+        return Ok((host, port).to_socket_addrs().await?.collect());
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) async fn sleep(duration: Duration) {
     #[cfg(feature = "tokio-runtime")]
@@ -4,7 +4,7 @@ use crate::{
         ClusterCommands, ClusterHealthStatus, ClusterNodeResult, ClusterShardResult, CommandTip,
         LegacyClusterShardResult, RequestPolicy, ResponsePolicy,
     },
-    network::{CommandInfoManager, Version},
+    network::{util::is_client_caching, CommandInfoManager, Version},
     resp::{Command, RespBuf, RespDeserializer, RespSerializer},
     Error, RedisError, RedisErrorKind, Result, RetryReason, StandaloneConnection,
 };
@@ -131,14 +131,16 @@ impl ClusterConnection {
     }
 
     pub async fn write(&mut self, command: &Command) -> Result<()> {
-        self.internal_write(command, &[]).await
+        self.internal_write(command, &[]).await?;
+        Ok(())
     }
 
+    /// Returns the hash slots of the keys of the command
     async fn internal_write(
         &mut self,
         command: &Command,
         ask_reasons: &[(u16, (String, u16))],
-    ) -> Result<()> {
+    ) -> Result<SmallVec<[u16; 10]>> {
         debug!("[{}] Analyzing command {command:?}", self.tag);
 
         let command_info = self.command_info_manager.get_command_info(command);
@@ -186,21 +188,21 @@ impl ClusterConnection {
                         command,
                         &command_name,
                         keys,
-                        slots,
+                        slots.clone(),
                         ask_reasons,
                     )
                     .await?;
                 }
                 RequestPolicy::Special => {
-                    self.request_policy_special(command, command_name, keys, slots);
+                    self.request_policy_special(command, command_name, keys, slots.clone());
                 }
             }
         } else {
-            self.no_request_policy(command, command_name, keys, slots, ask_reasons)
+            self.no_request_policy(command, command_name, keys, slots.clone(), ask_reasons)
                 .await?;
         }
 
-        Ok(())
+        Ok(slots)
     }
 
     pub async fn write_batch(
@@ -261,8 +263,49 @@ impl ClusterConnection {
                 .await?;
             }
         } else {
-            for command in commands {
-                self.internal_write(command, &ask_reasons).await?;
+            let mut previous_slots = SmallVec::<[u16; 10]>::new();
+            let mut commands = commands.into_iter().peekable();
+            while let Some(command) = commands.next() {
+                // WAIT & WAITAOF check the writes of the previous command on its node
+                let same_slot =
+                    !previous_slots.is_empty() && previous_slots.windows(2).all(|s| s[0] == s[1]);
+                if matches!(command.name, "WAIT" | "WAITAOF") && same_slot {
+                    self.no_request_policy(
+                        command,
+                        command.name.to_string(),
+                        smallvec![],
+                        SmallVec::from_slice(&previous_slots[..1]),
+                        &ask_reasons,
+                    )
+                    .await?;
+                    continue;
+                }
+
+                // `CLIENT CACHING` applies to the next command of the same connection:
+                // it is sent to the node of the next command
+                let next_slots = match commands.peek() {
+                    Some(next_command) if is_client_caching(command) => {
+                        let node_idx = self.get_random_node_index();
+                        let keys = self
+                            .command_info_manager
+                            .extract_keys(next_command, &mut self.nodes[node_idx].connection)
+                            .await?;
+                        Self::hash_slots(&keys)
+                    }
+                    _ => SmallVec::new(),
+                };
+                if !next_slots.is_empty() && next_slots.windows(2).all(|s| s[0] == s[1]) {
+                    self.no_request_policy(
+                        command,
+                        command.name.to_string(),
+                        smallvec![],
+                        SmallVec::from_slice(&next_slots[..1]),
+                        &ask_reasons,
+                    )
+                    .await?;
+                } else {
+                    previous_slots = self.internal_write(command, &ask_reasons).await?;
+                }
             }
         }
 
@@ -4,6 +4,7 @@ use crate::{
     resp::{cmd, Command, CommandArgs},
     Error, Result, StandaloneConnection,
 };
+use bytes::Bytes;
 use smallvec::SmallVec;
 use std::collections::HashMap;
 
@@ -82,7 +83,7 @@ impl CommandInfoManager {
                 let keys: SmallVec<[String; 10]> = connection.command_getkeys(args).await?;
                 return Ok(keys);
             } else {
-                let mut slice: &[Vec<u8>] = &command.args[command_info.first_key - 1..];
+                let mut slice: &[Bytes] = &command.args.bytes()[command_info.first_key - 1..];
                 let stop_index = if command_info.last_key >= 0 {
                     command_info.last_key as usize
                 } else {
@@ -97,7 +98,7 @@ impl CommandInfoManager {
                         if bs.is_empty() {
                             None
                         } else {
-                            String::from_utf8(bs.clone()).ok()
+                            String::from_utf8(bs.to_vec()).ok()
                         }
                     })
                     .collect();
@@ -108,7 +109,7 @@ impl CommandInfoManager {
         let mut keys = SmallVec::<[String; 10]>::new();
 
         for key_spec in &command_info.key_specifications {
-            let mut slice: &[Vec<u8>] = &command.args;
+            let mut slice: &[Bytes] = command.args.bytes();
 
             // begin_search
             match &key_spec.begin_search {
@@ -121,14 +122,14 @@ impl CommandInfoManager {
                         slice
                             .iter()
                             .skip(*start_from as usize - 1)
-                            .position(|arg| arg.as_slice() == keyword.as_bytes())
+                            .position(|arg| arg.as_ref() == keyword.as_bytes())
                             .map(|i| i + *start_from as usize)
                     } else {
                         slice
                             .iter()
                             .rev()
                             .skip((-*start_from - 1) as usize)
-                            .position(|arg| arg.as_slice() == keyword.as_bytes())
+                            .position(|arg| arg.as_ref() == keyword.as_bytes())
                             .map(|i| slice.len() - (i + -start_from as usize - 1))
                     };
 
@@ -171,7 +172,7 @@ impl CommandInfoManager {
                     first_key,
                     key_step,
                 } => {
-                    let num_keys = slice[*key_num_idx].as_slice();
+                    let num_keys = slice[*key_num_idx].as_ref();
                     let num_keys: usize = atoi::atoi(num_keys).ok_or_else(|| {
                         Error::Client(format!(
                             "Cannot parse integer from {}",
@@ -193,7 +194,7 @@ impl CommandInfoManager {
                 if bs.is_empty() {
                     None
                 } else {
-                    String::from_utf8(bs.clone()).ok()
+                    String::from_utf8(bs.to_vec()).ok()
                 }
             }));
         }
@@ -219,7 +220,7 @@ impl CommandInfoManager {
         };
 
         if let Some(key_spec) = command_info.key_specifications.first() {
-            let slice: &[Vec<u8>] = &command.args;
+            let slice: &[Bytes] = command.args.bytes();
             let mut shard_command = cmd(command.name);
 
             // begin_search
@@ -266,7 +267,7 @@ impl CommandInfoManager {
             for shard_key in shard_keys {
                 let key_index = if let Some(key_index) = slice
                     .iter()
-                    .position(|arg| arg.as_slice() == shard_key.as_bytes())
+                    .position(|arg| arg.as_ref() == shard_key.as_bytes())
                 {
                     key_index
                 } else {
@@ -2,9 +2,10 @@ use crate::{
     client::{Config, PreparedCommand, ServerConfig},
     commands::InternalPubSubCommands,
     resp::{Command, RespBuf},
-    ClusterConnection, Error, Future, Result, RetryReason, SentinelConnection,
-    StandaloneConnection,
+    ClusterConnection, Error, Future, ReplicatedConnection, Result, RetryReason,
+    SentinelConnection, SentinelEventSender, StandaloneConnection,
 };
+use log::debug;
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
 use std::future::IntoFuture;
@@ -14,6 +15,7 @@ pub enum Connection {
     Standalone(StandaloneConnection),
     Sentinel(SentinelConnection),
     Cluster(ClusterConnection),
+    Replicated(ReplicatedConnection),
 }
 
 impl Connection {
@@ -29,6 +31,9 @@ impl Connection {
             ServerConfig::Cluster(cluster_config) => Ok(Connection::Cluster(
                 ClusterConnection::connect(cluster_config, &config).await?,
             )),
+            ServerConfig::Replicated { nodes } => Ok(Connection::Replicated(
+                ReplicatedConnection::connect(nodes, &config).await?,
+            )),
         }
     }
 
@@ -38,6 +43,7 @@ impl Connection {
             Connection::Standalone(connection) => connection.write(command).await,
             Connection::Sentinel(connection) => connection.write(command).await,
             Connection::Cluster(connection) => connection.write(command).await,
+            Connection::Replicated(connection) => connection.write(command).await,
         }
     }
 
@@ -57,6 +63,9 @@ impl Connection {
             Connection::Cluster(connection) => {
                 connection.write_batch(commands, retry_reasons).await
             }
+            Connection::Replicated(connection) => {
+                connection.write_batch(commands, retry_reasons).await
+            }
         }
     }
 
@@ -66,6 +75,7 @@ impl Connection {
             Connection::Standalone(connection) => connection.read().await,
             Connection::Sentinel(connection) => connection.read().await,
             Connection::Cluster(connection) => connection.read().await,
+            Connection::Replicated(connection) => connection.read().await,
         }
     }
 
@@ -75,6 +85,7 @@ impl Connection {
             Connection::Standalone(connection) => connection.reconnect().await,
             Connection::Sentinel(connection) => connection.reconnect().await,
             Connection::Cluster(connection) => connection.reconnect().await,
+            Connection::Replicated(connection) => connection.reconnect().await,
         }
     }
 
@@ -86,11 +97,25 @@ impl Connection {
             .ok_or_else(|| Error::Client("Disconnected by peer".to_owned()))?
     }
 
+    /// Register a sender to forward Sentinel events to.
+    ///
+    /// The sender is dropped if the connection is not established through Sentinel
+    pub(crate) fn register_sentinel_event_sender(&mut self, sender: SentinelEventSender) {
+        match self {
+            Connection::Sentinel(connection) => connection.register_sentinel_event_sender(sender),
+            _ => debug!(
+                "[{}] Sentinel events are not available on this connection",
+                self.tag()
+            ),
+        }
+    }
+
     pub(crate) fn tag(&self) -> &str {
         match self {
             Connection::Standalone(connection) => connection.tag(),
             Connection::Sentinel(connection) => connection.tag(),
             Connection::Cluster(connection) => connection.tag(),
+            Connection::Replicated(connection) => connection.tag(),
         }
     }
 }
@@ -0,0 +1,124 @@
+use crate::{
+    client::{Fault, FaultPlan},
+    resp::{Command, RespBuf},
+    sleep, Result,
+};
+use bytes::Bytes;
+use std::{collections::VecDeque, time::Instant};
+
+/// Fault to apply to a command when it is written
+pub(crate) enum WriteFault {
+    /// The command is written as is
+    None,
+    /// `PING` is written instead, its reply will be replaced
+    ReplaceWithPing,
+    KillBeforeWrite,
+    KillAfterWrite,
+}
+
+/// Applies the faults of a [`FaultPlan`] to a connection
+pub(crate) struct FaultInjector {
+    plan: FaultPlan,
+    killed: bool,
+    /// faults to apply to the replies of the written commands, in their order,
+    /// `None` for the commands without fault
+    reply_faults: VecDeque<Option<Fault>>,
+    delayed_reply: Option<(Instant, Result<RespBuf>)>,
+}
+
+impl FaultInjector {
+    pub fn new(plan: FaultPlan) -> Self {
+        Self {
+            plan,
+            killed: false,
+            reply_faults: VecDeque::new(),
+            delayed_reply: None,
+        }
+    }
+
+    /// Forgets the state of the previous connection
+    pub fn reset(&mut self) {
+        self.killed = false;
+        self.reply_faults.clear();
+        self.delayed_reply = None;
+    }
+
+    pub fn kill(&mut self) {
+        self.killed = true;
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.killed
+    }
+
+    pub fn on_write(&mut self, command: &Command) -> WriteFault {
+        let fault = self.plan.take_fault(command);
+
+        let (write_fault, reply_fault) = match fault {
+            None => (WriteFault::None, None),
+            Some(Fault::KillBeforeWrite) => (WriteFault::KillBeforeWrite, None),
+            Some(Fault::KillAfterWrite) => (WriteFault::KillAfterWrite, None),
+            // the server must not execute the command
+            Some(fault @ Fault::Error(_)) => (WriteFault::ReplaceWithPing, Some(fault)),
+            Some(fault) => (WriteFault::None, Some(fault)),
+        };
+
+        // every reply is matched with its command,
+        // so that a fault does not apply to the reply of a command written before
+        if has_regular_reply(command) {
+            self.reply_faults.push_back(reply_fault);
+        }
+
+        write_fault
+    }
+
+    /// Keeps the replies matched with the commands
+    /// when a command is written without fault injection
+    pub fn on_internal_write(&mut self, command: &Command) {
+        if has_regular_reply(command) {
+            self.reply_faults.push_back(None);
+        }
+    }
+
+    /// Returns `None` if the reply must be dropped
+    pub fn on_read(&mut self, result: Result<RespBuf>) -> Option<Result<RespBuf>> {
+        if matches!(&result, Ok(resp_buf) if resp_buf.is_push_message()) {
+            return Some(result);
+        }
+
+        match self.reply_faults.pop_front().flatten() {
+            None => Some(result),
+            Some(Fault::DropReply) => None,
+            Some(Fault::Error(error)) => {
+                Some(Ok(RespBuf::new(Bytes::from(format!("-{error}\r\n")))))
+            }
+            Some(Fault::DelayRead(delay)) => {
+                self.delayed_reply = Some((Instant::now() + delay, result));
+                None
+            }
+            Some(Fault::KillBeforeWrite | Fault::KillAfterWrite) => Some(result),
+        }
+    }
+
+    /// Waits for the delayed reply, if any
+    ///
+    /// The reply is kept until its delay has elapsed, even if the wait is cancelled.
+    pub async fn delayed_reply(&mut self) -> Option<Result<RespBuf>> {
+        let deadline = self.delayed_reply.as_ref()?.0;
+        sleep(deadline.saturating_duration_since(Instant::now())).await;
+        self.delayed_reply.take().map(|(_, result)| result)
+    }
+
+    pub fn has_delayed_reply(&self) -> bool {
+        self.delayed_reply.is_some()
+    }
+}
+
+/// In RESP3, subscription commands are acknowledged by push messages,
+/// which are not matched with the written commands
+fn has_regular_reply(command: &Command) -> bool {
+    !matches!(
+        command.name,
+        "SUBSCRIBE" | "PSUBSCRIBE" | "SSUBSCRIBE" | "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "SUNSUBSCRIBE"
+    )
+}
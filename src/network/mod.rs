@@ -2,8 +2,13 @@ mod async_executor_strategy;
 mod cluster_connection;
 mod command_info_manager;
 mod connection;
+#[cfg(feature = "fault-injection")]
+mod fault_injector;
 mod network_handler;
+mod push_channel;
 mod reconnection_state;
+mod replica_set;
+mod replicated_connection;
 mod sentinel_connection;
 mod standalone_connection;
 mod util;
@@ -13,8 +18,13 @@ pub(crate) use async_executor_strategy::*;
 pub(crate) use cluster_connection::*;
 pub(crate) use command_info_manager::*;
 pub(crate) use connection::*;
+#[cfg(feature = "fault-injection")]
+pub(crate) use fault_injector::*;
 pub(crate) use network_handler::*;
+pub(crate) use push_channel::*;
 pub(crate) use reconnection_state::*;
+pub(crate) use replica_set::*;
+pub(crate) use replicated_connection::*;
 pub(crate) use sentinel_connection::*;
 pub(crate) use standalone_connection::*;
 pub(crate) use version::*;
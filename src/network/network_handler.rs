@@ -1,16 +1,17 @@
-use super::util::RefPubSubMessage;
+use super::{util::RefPubSubMessage, PushChannelReceiver, PushChannelSender};
 use crate::{
-    client::{Commands, Config, Message},
+    client::{ClientSideCache, Commands, Config, Message, SentinelEvent},
     commands::InternalPubSubCommands,
-    resp::{cmd, Command, RespBuf},
+    resp::{cmd, Command, CommandArgs, RespBuf},
     spawn, timeout, Connection, Error, JoinHandle, ReconnectionState, Result, RetryReason,
 };
 use futures_channel::{mpsc, oneshot};
-use futures_util::{select, FutureExt, SinkExt, StreamExt};
+use futures_util::{select, FutureExt, StreamExt};
 use log::{debug, error, info, log_enabled, trace, warn, Level};
 use smallvec::SmallVec;
 use std::{
     collections::{HashMap, VecDeque},
+    sync::Arc,
     time::Duration,
 };
 use tokio::{sync::broadcast, time::Instant};
@@ -21,10 +22,12 @@ pub(crate) type ResultSender = oneshot::Sender<Result<RespBuf>>;
 pub(crate) type ResultReceiver = oneshot::Receiver<Result<RespBuf>>;
 pub(crate) type ResultsSender = oneshot::Sender<Result<Vec<RespBuf>>>;
 pub(crate) type ResultsReceiver = oneshot::Receiver<Result<Vec<RespBuf>>>;
-pub(crate) type PubSubSender = mpsc::UnboundedSender<Result<RespBuf>>;
-pub(crate) type PubSubReceiver = mpsc::UnboundedReceiver<Result<RespBuf>>;
-pub(crate) type PushSender = mpsc::UnboundedSender<Result<RespBuf>>;
-pub(crate) type PushReceiver = mpsc::UnboundedReceiver<Result<RespBuf>>;
+pub(crate) type PubSubSender = PushChannelSender;
+pub(crate) type PubSubReceiver = PushChannelReceiver;
+pub(crate) type PushSender = PushChannelSender;
+pub(crate) type PushReceiver = PushChannelReceiver;
+pub(crate) type SentinelEventSender = mpsc::UnboundedSender<SentinelEvent>;
+pub(crate) type SentinelEventReceiver = mpsc::UnboundedReceiver<SentinelEvent>;
 pub(crate) type ReconnectSender = broadcast::Sender<()>;
 pub(crate) type ReconnectReceiver = broadcast::Receiver<()>;
 
@@ -32,8 +35,6 @@ pub(crate) type ReconnectReceiver = broadcast::Receiver<()>;
 enum Status {
     Disconnected,
     Connected,
-    Subscribing,
-    Subscribed,
     EnteringMonitor,
     Monitor,
     LeavingMonitor,
@@ -103,10 +104,16 @@ pub(crate) struct NetworkHandler {
     auto_remonitor: bool,
     tag: String,
     reconnection_state: ReconnectionState,
+    client_side_cache: Option<Arc<ClientSideCache>>,
+    /// last `CLIENT TRACKING ON` command, replayed after a reconnection
+    client_tracking: Option<Command>,
 }
 
 impl NetworkHandler {
-    pub async fn connect(config: Config) -> Result<(MsgSender, JoinHandle<()>, ReconnectSender)> {
+    pub async fn connect(
+        config: Config,
+        client_side_cache: Option<Arc<ClientSideCache>>,
+    ) -> Result<(MsgSender, JoinHandle<()>, ReconnectSender)> {
         // options
         let auto_resubscribe = config.auto_resubscribe;
         let auto_remonitor = config.auto_remonitor;
@@ -135,6 +142,8 @@ impl NetworkHandler {
             auto_remonitor,
             tag,
             reconnection_state: ReconnectionState::new(reconnection_config),
+            client_side_cache,
+            client_tracking: None,
         };
 
         let join_handle = spawn(async move {
@@ -173,8 +182,9 @@ impl NetworkHandler {
                 break;
             }
 
-            match self.msg_receiver.try_next() {
-                Ok(m) => msg = m,
+            match self.msg_receiver.try_recv() {
+                Ok(m) => msg = Some(m),
+                Err(e) if e.is_closed() => msg = None,
                 Err(_) => {
                     // there are no messages available, but channel is not yet closed
                     is_channel_closed = false;
@@ -249,30 +259,25 @@ impl NetworkHandler {
             self.push_sender = Some(push_sender);
         }
 
+        let sentinel_event_sender = msg.sentinel_event_sender.take();
+        if let Some(sentinel_event_sender) = sentinel_event_sender {
+            debug!("[{}] Registering sentinel_event_sender", self.tag);
+            self.connection
+                .register_sentinel_event_sender(sentinel_event_sender);
+        }
+
         match &self.status {
             Status::Connected => {
-                for command in &msg.commands {
-                    match command.name {
-                        "SUBSCRIBE" | "PSUBSCRIBE" | "SSUBSCRIBE" => {
-                            self.status = Status::Subscribing;
-                        }
-                        "MONITOR" => {
-                            self.status = Status::EnteringMonitor;
-                        }
-                        _ => (),
-                    }
-                }
-                self.messages_to_send.push_back(MessageToSend::new(msg));
-            }
-            Status::Subscribing => {
-                self.messages_to_send.push_back(MessageToSend::new(msg));
-            }
-            Status::Subscribed => {
+                self.remember_client_tracking(&msg);
                 for command in &msg.commands {
                     let subscription_type = match command.name {
                         "UNSUBSCRIBE" => Some(SubscriptionType::Channel),
                         "PUNSUBSCRIBE" => Some(SubscriptionType::Pattern),
                         "SUNSUBSCRIBE" => Some(SubscriptionType::ShardChannel),
+                        "MONITOR" => {
+                            self.status = Status::EnteringMonitor;
+                            None
+                        }
                         _ => None,
                     };
                     if let Some(subscription_type) = subscription_type {
@@ -293,6 +298,7 @@ impl NetworkHandler {
                         "[{}] network disconnected, queuing command: {:?}",
                         self.tag, msg.commands
                     );
+                    self.remember_client_tracking(&msg);
                     self.messages_to_send.push_back(MessageToSend::new(msg));
                 } else {
                     debug!(
@@ -320,6 +326,35 @@ impl NetworkHandler {
         }
     }
 
+    /// Remember the options of the client tracking to replay them after a reconnection
+    ///
+    /// With a client side cache, the client tracking is enabled with the options of the cache
+    /// on each connection, and the options of the caller are not replayed.
+    fn remember_client_tracking(&mut self, msg: &Message) {
+        if self.client_side_cache.is_some() {
+            return;
+        }
+
+        for command in &msg.commands {
+            let mut args = command.args.into_iter();
+            match command.name {
+                "CLIENT"
+                    if args
+                        .next()
+                        .is_some_and(|a| a.eq_ignore_ascii_case(b"TRACKING")) =>
+                {
+                    if args.next().is_some_and(|a| a.eq_ignore_ascii_case(b"ON")) {
+                        self.client_tracking = Some(command.clone());
+                    } else {
+                        self.client_tracking = None;
+                    }
+                }
+                "RESET" => self.client_tracking = None,
+                _ => (),
+            }
+        }
+    }
+
     async fn send_messages(&mut self) {
         if log_enabled!(Level::Debug) {
             let num_commands = self
@@ -341,6 +376,19 @@ impl NetworkHandler {
             let mut num_commands_to_receive: usize = 0;
 
             for command in commands.into_iter() {
+                if command.name == "SELECT" {
+                    if let Some(client_side_cache) = &self.client_side_cache {
+                        let database = command
+                            .args
+                            .into_iter()
+                            .next()
+                            .and_then(|db| std::str::from_utf8(db).ok()?.parse::<usize>().ok());
+                        if let Some(database) = database {
+                            client_side_cache.select(database);
+                        }
+                    }
+                }
+
                 if command.name == "CLIENT" {
                     let mut args = command.args.into_iter();
 
@@ -374,10 +422,21 @@ impl NetworkHandler {
         {
             error!("[{}] Error while writing batch: {e}", self.tag);
 
+            // the connection is broken: the commands to retry are sent again after the reconnection
+            let is_io_error = matches!(e, Error::IO(_));
+
             let mut idx: usize = 0;
             while let Some(msg) = self.messages_to_send.pop_front() {
                 if commands_to_receive[idx] > 0 {
-                    msg.message.commands.send_error(&self.tag, e.clone());
+                    if is_io_error && msg.message.retry_on_error {
+                        self.messages_to_receive.push_back(MessageToReceive::new(
+                            msg.message,
+                            commands_to_receive[idx],
+                            msg.attempts,
+                        ));
+                    } else {
+                        msg.message.commands.send_error(&self.tag, e.clone());
+                    }
                 }
                 idx += 1;
             }
@@ -398,46 +457,49 @@ impl NetworkHandler {
 
     async fn handle_result(&mut self, result: Option<Result<RespBuf>>) -> bool {
         match result {
+            // the rest of the stream cannot be trusted
+            Some(Err(Error::Protocol(e))) => {
+                warn!(
+                    "[{}] Resetting the connection after a protocol error: {e}",
+                    self.tag
+                );
+                self.receive_result(Err(Error::Protocol(e)));
+                return self.reconnect().await;
+            }
             Some(result) => match self.status {
                 Status::Disconnected => (),
                 Status::Connected => match &result {
-                    Ok(resp_buf) if resp_buf.is_push_message() => match &mut self.push_sender {
-                        Some(push_sender) => {
-                            if let Err(e) = push_sender.send(result).await {
-                                warn!("[{}] Cannot send monitor result to caller: {e}", self.tag);
+                    // RESP3 push frames are interleaved with regular replies
+                    Ok(resp_buf) if resp_buf.is_push_message() => {
+                        if RefPubSubMessage::from_resp(resp_buf).is_some() {
+                            if let Some(result) = self.try_match_pubsub_message(result).await {
+                                self.receive_result(result);
+                            }
+                        } else {
+                            let is_invalidation = self
+                                .client_side_cache
+                                .as_ref()
+                                .is_some_and(|cache| cache.handle_push_message(resp_buf));
+                            match &mut self.push_sender {
+                                Some(push_sender) => {
+                                    if let Err(e) = push_sender.send(result).await {
+                                        warn!(
+                                            "[{}] Cannot send push message to caller: {e}",
+                                            self.tag
+                                        );
+                                    }
+                                }
+                                None if is_invalidation => (),
+                                None => {
+                                    warn!("[{}] Received a push message with no sender configured: {resp_buf}", self.tag)
+                                }
                             }
                         }
-                        None => {
-                            warn!("[{}] Received a push message with no sender configured: {resp_buf}", self.tag)
-                        }
-                    },
+                    }
                     _ => {
                         self.receive_result(result);
                     }
                 },
-                Status::Subscribing => {
-                    if result.is_ok() {
-                        self.status = Status::Subscribed;
-                    } else {
-                        self.status = Status::Connected;
-                    }
-
-                    if let Some(resp_buf) = self.try_match_pubsub_message(result).await {
-                        self.receive_result(resp_buf);
-                    }
-                }
-                Status::Subscribed => {
-                    if let Some(resp_buf) = self.try_match_pubsub_message(result).await {
-                        self.receive_result(resp_buf);
-                        if self.subscriptions.is_empty() && self.pending_subscriptions.is_empty() {
-                            debug!(
-                                "[{}] goint out from Pub/Sub state to connected state",
-                                self.tag
-                            );
-                            self.status = Status::Connected;
-                        }
-                    }
-                }
                 Status::EnteringMonitor => {
                     self.receive_result(result);
                     self.status = Status::Monitor;
@@ -592,21 +654,14 @@ impl NetworkHandler {
                     | RefPubSubMessage::SMessage(channel_or_pattern, _) => {
                         match self.subscriptions.get_mut(channel_or_pattern) {
                             Some((_subscription_type, pub_sub_sender)) => {
-                                if let Err(e) = pub_sub_sender.unbounded_send(value) {
-                                    let error_desc = e.to_string();
-                                    if let Ok(ref_value) = &e.into_inner() {
-                                        if let Some(
-                                            RefPubSubMessage::Message(channel_or_pattern, _)
-                                            | RefPubSubMessage::SMessage(channel_or_pattern, _),
-                                        ) = RefPubSubMessage::from_resp(ref_value)
-                                        {
-                                            warn!(
-                                                "[{}] Cannot send pub/sub message to caller from channel `{}`: {error_desc}",
-                                                self.tag,
-                                                String::from_utf8_lossy(channel_or_pattern)
-                                            );
-                                        }
-                                    }
+                                let channel = channel_or_pattern.to_vec();
+                                if let Err(e) = pub_sub_sender.send(value).await {
+                                    warn!(
+                                        "[{}] Cannot send pub/sub message to caller from channel `{}`: {e}",
+                                        self.tag,
+                                        String::from_utf8_lossy(&channel)
+                                    );
+                                    self.unsubscribe_if_disconnected(&channel).await;
                                 }
                             }
                             None => {
@@ -696,17 +751,25 @@ impl NetworkHandler {
                                 Some(Ok(RespBuf::ok()))
                             }
                         } else {
-                            Some(value)
+                            // unsubscription initiated by the server
+                            debug!(
+                                "[{}] Unsubscribed from `{}`",
+                                self.tag,
+                                String::from_utf8_lossy(channel_or_pattern)
+                            );
+                            None
                         }
                     }
                     RefPubSubMessage::PMessage(pattern, channel, _) => {
                         match self.subscriptions.get_mut(pattern) {
                             Some((_subscription_type, pub_sub_sender)) => {
+                                let pattern = pattern.to_vec();
                                 if let Err(e) = pub_sub_sender.send(value).await {
                                     warn!(
                                         "[{}] Cannot send pub/sub message to caller: {e}",
                                         self.tag
                                     );
+                                    self.unsubscribe_if_disconnected(&pattern).await;
                                 }
                             }
                             None => {
@@ -734,6 +797,11 @@ impl NetworkHandler {
         let old_status = self.status;
         self.status = Status::Disconnected;
 
+        // invalidation messages may be lost while disconnected
+        if let Some(client_side_cache) = &self.client_side_cache {
+            client_side_cache.flush();
+        }
+
         while let Some(message_to_receive) = self.messages_to_receive.front() {
             if !message_to_receive.message.retry_on_error {
                 if let Some(message_to_receive) = self.messages_to_receive.pop_front() {
@@ -801,6 +869,10 @@ impl NetworkHandler {
                 continue;
             }
 
+            if let Some(client_side_cache) = &self.client_side_cache {
+                client_side_cache.flush();
+            }
+
             if self.auto_resubscribe {
                 if let Err(e) = self.auto_resubscribe().await {
                     error!("[{}] Failed to reconnect: {e:?}", self.tag);
@@ -815,6 +887,11 @@ impl NetworkHandler {
                 }
             }
 
+            if let Err(e) = self.restore_client_tracking(old_status).await {
+                error!("[{}] Failed to reconnect: {e:?}", self.tag);
+                continue;
+            }
+
             if let Err(e) = self.reconnect_sender.send(()) {
                 debug!(
                     "[{}] Cannot send reconnect notification to clients: {e}",
@@ -831,9 +908,7 @@ impl NetworkHandler {
 
             self.send_messages().await;
 
-            if !self.subscriptions.is_empty() {
-                self.status = Status::Subscribed;
-            } else if let Status::Monitor | Status::EnteringMonitor = old_status {
+            if let Status::Monitor | Status::EnteringMonitor = old_status {
                 if self.push_sender.is_some() {
                     self.status = Status::Monitor;
                 }
@@ -847,6 +922,53 @@ impl NetworkHandler {
         }
     }
 
+    /// When the stream of a subscription has been disconnected because its buffer is full,
+    /// unsubscribe from all the channels and patterns of this stream
+    async fn unsubscribe_if_disconnected(&mut self, channel_or_pattern: &[u8]) {
+        let Some((_, pub_sub_sender)) = self.subscriptions.get(channel_or_pattern) else {
+            return;
+        };
+
+        if !pub_sub_sender.is_disconnected() {
+            return;
+        }
+
+        let mut channels = CommandArgs::default();
+        let mut patterns = CommandArgs::default();
+        let mut shardchannels = CommandArgs::default();
+
+        for (channel_or_pattern, (subscription_type, sender)) in &self.subscriptions {
+            if sender.same_channel(pub_sub_sender) {
+                match subscription_type {
+                    SubscriptionType::Channel => channels.arg(channel_or_pattern.as_slice()),
+                    SubscriptionType::Pattern => patterns.arg(channel_or_pattern.as_slice()),
+                    SubscriptionType::ShardChannel => {
+                        shardchannels.arg(channel_or_pattern.as_slice())
+                    }
+                };
+            }
+        }
+
+        for (name, args) in [
+            ("UNSUBSCRIBE", channels),
+            ("PUNSUBSCRIBE", patterns),
+            ("SUNSUBSCRIBE", shardchannels),
+        ] {
+            if !args.is_empty() {
+                debug!(
+                    "[{}] Stream disconnected, sending {name} {args:?}",
+                    self.tag
+                );
+                self.handle_message(Message::single_forget(cmd(name).arg(args), false))
+                    .await;
+            }
+        }
+
+        if self.status != Status::Disconnected {
+            self.send_messages().await;
+        }
+    }
+
     async fn auto_resubscribe(&mut self) -> Result<()> {
         if !self.subscriptions.is_empty() {
             for (channel_or_pattern, (subscription_type, _)) in &self.subscriptions {
@@ -926,6 +1048,48 @@ impl NetworkHandler {
         Ok(())
     }
 
+    /// Replay the client tracking options and notify the invalidation stream
+    /// that all the keys must be invalidated,
+    /// since invalidation messages may have been lost while disconnected
+    ///
+    /// With a client side cache, the client tracking has already been enabled
+    /// with the options of the cache when connecting.
+    /// If the options cannot be replayed, the invalidation stream receives the error and ends.
+    async fn restore_client_tracking(&mut self, old_status: Status) -> Result<()> {
+        match &self.client_tracking {
+            Some(command) => {
+                let result = self.connection.send(command).await?;
+                if let Err(e) = result.to::<()>() {
+                    error!("[{}] Cannot restore client tracking: {e}", self.tag);
+                    self.client_tracking = None;
+                    if let Some(push_sender) = &mut self.push_sender {
+                        if let Err(e) = push_sender.send(Err(e)).await {
+                            warn!("[{}] Cannot send error to caller: {e}", self.tag);
+                        }
+                    }
+                    return Ok(());
+                }
+            }
+            None if self.client_side_cache.is_some() => (),
+            None => return Ok(()),
+        }
+
+        if let Status::Monitor | Status::EnteringMonitor | Status::LeavingMonitor = old_status {
+            return Ok(());
+        }
+
+        if let Some(push_sender) = &mut self.push_sender {
+            if let Err(e) = push_sender.send(Ok(RespBuf::invalidate_all())).await {
+                warn!(
+                    "[{}] Cannot send invalidation message to caller: {e}",
+                    self.tag
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     async fn auto_remonitor(&mut self, old_status: Status) -> Result<()> {
         if let Status::Monitor | Status::EnteringMonitor = old_status {
             self.connection.send(&cmd("MONITOR")).await?;
@@ -0,0 +1,236 @@
+use crate::{client::PubSubOverflowPolicy, resp::RespBuf, Error, Result};
+use futures_util::Stream;
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::poll_fn,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Create a channel to forward push messages from the network handler to a stream,
+/// with an optional capacity and the policy to apply when this capacity is reached.
+///
+/// A capacity of 0 means that the channel is unbounded.
+pub(crate) fn push_channel<T>(
+    capacity: usize,
+    overflow_policy: PubSubOverflowPolicy,
+) -> (PushChannelSender<T>, PushChannelReceiver<T>) {
+    let state = Arc::new(Mutex::new(State {
+        buffer: VecDeque::new(),
+        capacity,
+        overflow_policy,
+        num_senders: 1,
+        receiver_alive: true,
+        overflowed: false,
+        overflow_reported: false,
+        dropped_messages: 0,
+        receiver_waker: None,
+        sender_wakers: Vec::new(),
+    }));
+
+    (
+        PushChannelSender {
+            state: state.clone(),
+        },
+        PushChannelReceiver { state },
+    )
+}
+
+struct State<T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    overflow_policy: PubSubOverflowPolicy,
+    num_senders: usize,
+    receiver_alive: bool,
+    /// set with the [`Disconnect`](PubSubOverflowPolicy::Disconnect) policy
+    /// when the buffer has been full
+    overflowed: bool,
+    overflow_reported: bool,
+    dropped_messages: u64,
+    receiver_waker: Option<Waker>,
+    /// senders waiting for room in the buffer
+    /// with the [`Backpressure`](PubSubOverflowPolicy::Backpressure) policy
+    sender_wakers: Vec<Waker>,
+}
+
+impl<T> State<T> {
+    fn is_full(&self) -> bool {
+        self.capacity > 0 && self.buffer.len() >= self.capacity
+    }
+
+    fn push(&mut self, item: T) {
+        self.buffer.push_back(item);
+        self.wake_receiver();
+    }
+
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.receiver_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_senders(&mut self) {
+        for waker in self.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+pub(crate) struct PushChannelSender<T = Result<RespBuf>> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+impl<T> PushChannelSender<T> {
+    /// Send an item to the receiver, applying the overflow policy when the buffer is full.
+    ///
+    /// With the [`Backpressure`](PubSubOverflowPolicy::Backpressure) policy,
+    /// the returned future is pending until the receiver makes room for the item.
+    ///
+    /// # Errors
+    /// An error is returned when the receiver has been dropped,
+    /// or when the channel gets disconnected by the [`Disconnect`](PubSubOverflowPolicy::Disconnect) policy.
+    /// Once disconnected, items are dropped until the sender is dropped.
+    pub async fn send(&mut self, item: T) -> Result<()> {
+        let mut item = Some(item);
+
+        poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+
+            if !state.receiver_alive {
+                return Poll::Ready(Err(Error::Client(
+                    "Push channel receiver has been dropped".to_owned(),
+                )));
+            }
+
+            if state.overflowed {
+                state.dropped_messages += 1;
+                return Poll::Ready(Ok(()));
+            }
+
+            if !state.is_full() {
+                if let Some(item) = item.take() {
+                    state.push(item);
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            match state.overflow_policy {
+                PubSubOverflowPolicy::DropOldest => {
+                    state.buffer.pop_front();
+                    state.dropped_messages += 1;
+                    if let Some(item) = item.take() {
+                        state.push(item);
+                    }
+                }
+                PubSubOverflowPolicy::DropNewest => {
+                    state.dropped_messages += 1;
+                }
+                PubSubOverflowPolicy::Disconnect => {
+                    state.overflowed = true;
+                    state.dropped_messages += 1;
+                    state.wake_receiver();
+                    return Poll::Ready(Err(Error::Client(format!(
+                        "Push channel buffer is full (capacity: {}), the channel has been disconnected",
+                        state.capacity
+                    ))));
+                }
+                PubSubOverflowPolicy::Backpressure => {
+                    state.sender_wakers.push(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+
+            Poll::Ready(Ok(()))
+        })
+        .await
+    }
+
+    /// The channel has been disconnected by the [`Disconnect`](PubSubOverflowPolicy::Disconnect) policy
+    pub fn is_disconnected(&self) -> bool {
+        self.state.lock().unwrap().overflowed
+    }
+
+    /// Both senders send to the same receiver
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl<T> Clone for PushChannelSender<T> {
+    fn clone(&self) -> Self {
+        self.state.lock().unwrap().num_senders += 1;
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Drop for PushChannelSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            state.wake_receiver();
+        }
+    }
+}
+
+impl<T> fmt::Debug for PushChannelSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PushChannelSender").finish_non_exhaustive()
+    }
+}
+
+pub(crate) struct PushChannelReceiver<T = Result<RespBuf>> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+impl<T> PushChannelReceiver<T> {
+    /// Number of messages dropped because the buffer was full
+    pub fn dropped_messages(&self) -> u64 {
+        self.state.lock().unwrap().dropped_messages
+    }
+}
+
+impl<U> Stream for PushChannelReceiver<Result<U>> {
+    type Item = Result<U>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(item) = state.buffer.pop_front() {
+            state.wake_senders();
+            return Poll::Ready(Some(item));
+        }
+
+        if state.overflowed {
+            if state.overflow_reported {
+                return Poll::Ready(None);
+            }
+
+            state.overflow_reported = true;
+            return Poll::Ready(Some(Err(Error::Client(format!(
+                "Stream buffer is full (capacity: {}), the stream has been disconnected",
+                state.capacity
+            )))));
+        }
+
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+
+        state.receiver_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for PushChannelReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        state.receiver_alive = false;
+        state.buffer.clear();
+        state.wake_senders();
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{
+    client::ReplicaSelectionStrategy,
+    network::{util::is_client_caching, CommandInfoManager},
+    resp::{cmd, Command, RespBuf},
+    Result, RetryReason, StandaloneConnection,
+};
+use futures_util::{future, FutureExt};
+use log::{debug, warn};
+use rand::Rng;
+use smallvec::SmallVec;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Minimum time between two refreshes of the replication offsets,
+/// with the [`LowestLag`](ReplicaSelectionStrategy::LowestLag) strategy
+const REPLICATION_OFFSETS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Connection to a replica
+pub(crate) struct Replica {
+    pub address: (String, u16),
+    /// replication offset at discovery time, then refreshed
+    /// with the [`LowestLag`](ReplicaSelectionStrategy::LowestLag) strategy
+    pub replication_offset: u64,
+    pub connection: StandaloneConnection,
+}
+
+impl std::fmt::Debug for Replica {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Replica")
+            .field("address", &self.address)
+            .field("replication_offset", &self.replication_offset)
+            .finish()
+    }
+}
+
+/// Read/write splitting between a master and its replicas
+///
+/// Read-only commands are sent to a replica selected with a [`ReplicaSelectionStrategy`],
+/// other commands are sent to the master.
+/// Within a batch, read-only commands following a command which is not read-only
+/// are sent to the master too, so that they read the writes of the batch.
+/// Replies are read back in the order the commands have been written.
+pub(crate) struct ReplicaSet {
+    strategy: ReplicaSelectionStrategy,
+    replicas: Vec<Replica>,
+    command_info_manager: CommandInfoManager,
+    next_replica_idx: usize,
+    pending_replies: VecDeque<PendingReply>,
+    /// replies of the master read while waiting for the reply of a replica
+    master_replies: VecDeque<Result<RespBuf>>,
+    in_transaction: bool,
+    last_offsets_refresh: Instant,
+}
+
+/// Connection a pending reply is read from
+#[derive(Clone, Copy)]
+enum PendingReply {
+    /// Reply to a command sent to the master
+    Master,
+    /// Reply to a command sent to a replica
+    Replica(usize),
+    /// Reply to an internal `INFO replication` sent to a replica, to refresh its replication offset
+    ReplicationOffset(usize),
+}
+
+impl PendingReply {
+    fn new(target: Option<usize>) -> Self {
+        match target {
+            Some(replica_idx) => Self::Replica(replica_idx),
+            None => Self::Master,
+        }
+    }
+
+    fn replica_idx(self) -> Option<usize> {
+        match self {
+            Self::Master => None,
+            Self::Replica(replica_idx) | Self::ReplicationOffset(replica_idx) => Some(replica_idx),
+        }
+    }
+}
+
+impl ReplicaSet {
+    pub async fn new(
+        strategy: ReplicaSelectionStrategy,
+        replicas: Vec<Replica>,
+        master_connection: &mut StandaloneConnection,
+    ) -> Result<Self> {
+        let command_info_manager = CommandInfoManager::initialize(master_connection).await?;
+
+        debug!(
+            "[{}] Replicas connected: {replicas:?}",
+            master_connection.tag()
+        );
+
+        Ok(Self {
+            strategy,
+            replicas,
+            command_info_manager,
+            next_replica_idx: 0,
+            pending_replies: VecDeque::new(),
+            master_replies: VecDeque::new(),
+            in_transaction: false,
+            last_offsets_refresh: Instant::now(),
+        })
+    }
+
+    pub async fn write(
+        &mut self,
+        master_connection: &mut StandaloneConnection,
+        command: &Command,
+    ) -> Result<()> {
+        self.refresh_replication_offsets().await?;
+
+        let num_pending_replies = self.pending_replies.len();
+        let target = self.route(command);
+        self.push_pending_reply(command, target);
+
+        let result = match target {
+            Some(replica_idx) => self.replicas[replica_idx].connection.write(command).await,
+            None => master_connection.write(command).await,
+        };
+
+        if result.is_err() {
+            self.pending_replies.truncate(num_pending_replies);
+        }
+
+        result
+    }
+
+    pub async fn write_batch(
+        &mut self,
+        master_connection: &mut StandaloneConnection,
+        commands: SmallVec<[&mut Command; 10]>,
+        retry_reasons: &[RetryReason],
+    ) -> Result<()> {
+        self.refresh_replication_offsets().await?;
+
+        let num_pending_replies = self.pending_replies.len();
+        let mut master_commands = SmallVec::<[&mut Command; 10]>::new();
+        let mut replica_commands: Vec<SmallVec<[&mut Command; 10]>> =
+            (0..self.replicas.len()).map(|_| SmallVec::new()).collect();
+
+        let mut commands = commands.into_iter().peekable();
+        let mut next_target = None;
+        // set after the first command of the batch which may write, for the next commands to read its writes
+        let mut pinned_to_master = false;
+        while let Some(command) = commands.next() {
+            let target = match next_target.take() {
+                Some(target) => target,
+                // `CLIENT CACHING` applies to the next command of the same connection:
+                // both commands are sent to the target of the next command
+                None if is_client_caching(command) => {
+                    let target = match commands.peek() {
+                        Some(next_command) => self.route(next_command),
+                        None => None,
+                    };
+                    let target = target.filter(|_| !pinned_to_master);
+                    next_target = Some(target);
+                    target
+                }
+                None => {
+                    let target = self.route(command).filter(|_| !pinned_to_master);
+                    if target.is_none() && !self.is_read_only(command) {
+                        pinned_to_master = true;
+                    }
+                    target
+                }
+            };
+            self.push_pending_reply(command, target);
+
+            match target {
+                Some(replica_idx) => replica_commands[replica_idx].push(command),
+                None => master_commands.push(command),
+            }
+        }
+
+        let mut result = Ok(());
+        if !master_commands.is_empty() {
+            result = master_connection
+                .write_batch(master_commands, retry_reasons)
+                .await;
+        }
+        let master_written = result.is_ok();
+
+        // replicas are written in order, until a write fails
+        let mut num_written_replicas = 0;
+        if result.is_ok() {
+            for (replica, commands) in self.replicas.iter_mut().zip(replica_commands) {
+                if !commands.is_empty() {
+                    result = replica
+                        .connection
+                        .write_batch(commands, retry_reasons)
+                        .await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                num_written_replicas += 1;
+            }
+        }
+
+        if result.is_err() {
+            // only the commands that have not been written are not waiting for a reply
+            let mut idx = 0;
+            self.pending_replies.retain(|pending_reply| {
+                let written = idx < num_pending_replies
+                    || match pending_reply.replica_idx() {
+                        None => master_written,
+                        Some(replica_idx) => replica_idx < num_written_replicas,
+                    };
+                idx += 1;
+                written
+            });
+        }
+
+        result
+    }
+
+    /// Read the next reply, from the connection the matching command has been sent to.
+    ///
+    /// The master connection is always read to receive push messages:
+    /// while a reply is expected from a replica, replies of the master
+    /// are kept until their turn comes.
+    pub async fn read(
+        &mut self,
+        master_connection: &mut StandaloneConnection,
+    ) -> Option<Result<RespBuf>> {
+        loop {
+            let Some(replica_idx) = self
+                .pending_replies
+                .front()
+                .and_then(|pending_reply| pending_reply.replica_idx())
+            else {
+                if let Some(result) = self.master_replies.pop_front() {
+                    self.pending_replies.pop_front();
+                    return Some(result);
+                }
+
+                let result = master_connection.read().await;
+                return self.on_reply(result);
+            };
+
+            let result = match future::select(
+                self.replicas[replica_idx].connection.read().boxed(),
+                master_connection.read().boxed(),
+            )
+            .await
+            {
+                future::Either::Left((result, _)) => future::Either::Left(result),
+                future::Either::Right((result, _)) => future::Either::Right(result),
+            };
+
+            match result {
+                future::Either::Left(result) => {
+                    if let Some(PendingReply::ReplicationOffset(replica_idx)) =
+                        self.pending_replies.front().copied()
+                    {
+                        if result.is_some() {
+                            self.pending_replies.pop_front();
+                            self.on_replication_info(replica_idx, result);
+                            continue;
+                        }
+                    }
+
+                    return self.on_reply(result);
+                }
+                future::Either::Right(Some(Ok(resp_buf))) if resp_buf.is_push_message() => {
+                    return Some(Ok(resp_buf));
+                }
+                future::Either::Right(Some(result)) => self.master_replies.push_back(result),
+                future::Either::Right(None) => return self.on_reply(None),
+            }
+        }
+    }
+
+    /// Send `INFO replication` to each replica when their replication offsets are due for a refresh.
+    ///
+    /// Replies are consumed by [`read`](ReplicaSet::read) in the order of the other pending replies.
+    async fn refresh_replication_offsets(&mut self) -> Result<()> {
+        if self.strategy != ReplicaSelectionStrategy::LowestLag
+            || self.last_offsets_refresh.elapsed() < REPLICATION_OFFSETS_REFRESH_INTERVAL
+        {
+            return Ok(());
+        }
+
+        self.last_offsets_refresh = Instant::now();
+        let command = cmd("INFO").arg("replication");
+
+        for (replica_idx, replica) in self.replicas.iter_mut().enumerate() {
+            replica.connection.write_internal(&command).await?;
+            self.pending_replies
+                .push_back(PendingReply::ReplicationOffset(replica_idx));
+        }
+
+        Ok(())
+    }
+
+    fn on_replication_info(&mut self, replica_idx: usize, result: Option<Result<RespBuf>>) {
+        let replica = &mut self.replicas[replica_idx];
+        let offset = result
+            .and_then(|result| result.ok())
+            .and_then(|resp_buf| resp_buf.to::<String>().ok())
+            .and_then(|info| {
+                info.lines()
+                    .find_map(|line| line.strip_prefix("slave_repl_offset:"))
+                    .and_then(|offset| offset.trim().parse::<u64>().ok())
+            });
+
+        match offset {
+            Some(offset) => replica.replication_offset = offset,
+            None => warn!(
+                "Cannot refresh the replication offset of replica {}:{}",
+                replica.address.0, replica.address.1
+            ),
+        }
+    }
+
+    fn on_reply(&mut self, result: Option<Result<RespBuf>>) -> Option<Result<RespBuf>> {
+        match &result {
+            Some(Ok(resp_buf)) if resp_buf.is_push_message() => (),
+            Some(_) => {
+                self.pending_replies.pop_front();
+            }
+            None => {
+                self.pending_replies.clear();
+                self.master_replies.clear();
+            }
+        }
+
+        result
+    }
+
+    /// Select the target of a command: `None` for the master, `Some(replica_idx)` for a replica
+    fn route(&mut self, command: &Command) -> Option<usize> {
+        match command.name {
+            "MULTI" => {
+                self.in_transaction = true;
+                None
+            }
+            "EXEC" | "DISCARD" => {
+                self.in_transaction = false;
+                None
+            }
+            _ if !self.in_transaction && self.is_read_only(command) => self.select_replica(),
+            _ => None,
+        }
+    }
+
+    fn push_pending_reply(&mut self, command: &Command, target: Option<usize>) {
+        match command.name {
+            // replies to these commands are push messages
+            "SUBSCRIBE" | "PSUBSCRIBE" | "SSUBSCRIBE" | "UNSUBSCRIBE" | "PUNSUBSCRIBE"
+            | "SUNSUBSCRIBE" => (),
+            _ => self.pending_replies.push_back(PendingReply::new(target)),
+        }
+    }
+
+    fn is_read_only(&self, command: &Command) -> bool {
+        self.command_info_manager
+            .get_command_info(command)
+            .map(|command_info| command_info.flags.iter().any(|f| f == "readonly"))
+            .unwrap_or(false)
+    }
+
+    fn select_replica(&mut self) -> Option<usize> {
+        if self.replicas.is_empty() {
+            return None;
+        }
+
+        match self.strategy {
+            ReplicaSelectionStrategy::Random => {
+                Some(rand::thread_rng().gen_range(0..self.replicas.len()))
+            }
+            ReplicaSelectionStrategy::RoundRobin => {
+                let replica_idx = self.next_replica_idx % self.replicas.len();
+                self.next_replica_idx = replica_idx + 1;
+                Some(replica_idx)
+            }
+            ReplicaSelectionStrategy::LowestLag => self
+                .replicas
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, replica)| replica.replication_offset)
+                .map(|(replica_idx, _)| replica_idx),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    client::{Config, ReplicaSelectionStrategy},
+    commands::{ReplicationState, RoleResult, ServerCommands},
+    network::{lookup_host, Replica, ReplicaSet},
+    resp::{Command, RespBuf},
+    Error, Result, RetryReason, StandaloneConnection,
+};
+use log::{debug, info, warn};
+use smallvec::SmallVec;
+
+/// Connection to a master and its replicas, discovered with the `ROLE` command
+///
+/// Read-only commands are sent to replicas in turn, other commands to the master.
+/// The topology is discovered again when the master replies with a `READONLY` error
+/// or when a connection is closed.
+pub struct ReplicatedConnection {
+    nodes: Vec<(String, u16)>,
+    config: Config,
+    master_connection: StandaloneConnection,
+    replica_set: ReplicaSet,
+    /// set when the master has been demoted, to discover the topology again
+    /// once the `READONLY` error has been delivered
+    master_demoted: bool,
+}
+
+impl ReplicatedConnection {
+    pub async fn connect(nodes: &[(String, u16)], config: &Config) -> Result<Self> {
+        let (master_connection, replica_set) = Self::discover(nodes, config).await?;
+
+        Ok(Self {
+            nodes: nodes.to_vec(),
+            config: config.clone(),
+            master_connection,
+            replica_set,
+            master_demoted: false,
+        })
+    }
+
+    #[inline]
+    pub async fn write(&mut self, command: &Command) -> Result<()> {
+        self.replica_set
+            .write(&mut self.master_connection, command)
+            .await
+    }
+
+    #[inline]
+    pub async fn write_batch(
+        &mut self,
+        commands: SmallVec<[&mut Command; 10]>,
+        retry_reasons: &[RetryReason],
+    ) -> Result<()> {
+        self.replica_set
+            .write_batch(&mut self.master_connection, commands, retry_reasons)
+            .await
+    }
+
+    /// Read the next result from the master or from a replica.
+    ///
+    /// When the master is demoted and replies with a `READONLY` error,
+    /// the error is returned to the caller of the command,
+    /// then `None` is returned by the next call as if the connection was closed,
+    /// so that the caller reconnects to the new master.
+    pub async fn read(&mut self) -> Option<Result<RespBuf>> {
+        if self.master_demoted {
+            info!(
+                "[{}] Master has been demoted, discovering the new topology",
+                self.tag()
+            );
+            return None;
+        }
+
+        let result = self.replica_set.read(&mut self.master_connection).await;
+
+        if let Some(Ok(resp_buf)) = &result {
+            if resp_buf.is_error() && resp_buf.as_bytes().starts_with(b"-READONLY") {
+                self.master_demoted = true;
+            }
+        }
+
+        result
+    }
+
+    pub async fn reconnect(&mut self) -> Result<()> {
+        let (master_connection, replica_set) = Self::discover(&self.nodes, &self.config).await?;
+        self.master_connection = master_connection;
+        self.replica_set = replica_set;
+        self.master_demoted = false;
+
+        Ok(())
+    }
+
+    /// Call the `ROLE` command on each node to find the master
+    /// and the replicas connected to it
+    ///
+    /// Replicas which report another master are ignored.
+    async fn discover(
+        nodes: &[(String, u16)],
+        config: &Config,
+    ) -> Result<(StandaloneConnection, ReplicaSet)> {
+        let mut master: Option<((String, u16), StandaloneConnection)> = None;
+        // replicas with the address of their master
+        let mut candidates: Vec<(Replica, (String, u16))> = Vec::new();
+
+        for (host, port) in nodes {
+            let mut connection = match StandaloneConnection::connect(host, *port, config).await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("Cannot connect to node {host}:{port}: {e}");
+                    continue;
+                }
+            };
+
+            match connection.role().await {
+                Ok(RoleResult::Master { .. }) => {
+                    if master.is_none() {
+                        master = Some(((host.clone(), *port), connection));
+                    } else {
+                        warn!(
+                            "[{}] Ignoring node {host}:{port}: a master has already been found",
+                            connection.tag()
+                        );
+                    }
+                }
+                // a replica which is synchronizing serves the data it already has
+                Ok(RoleResult::Replica {
+                    master_ip,
+                    master_port,
+                    state: ReplicationState::Connected | ReplicationState::Sync,
+                    amount_data_received,
+                }) => candidates.push((
+                    Replica {
+                        address: (host.clone(), *port),
+                        replication_offset: amount_data_received.max(0) as u64,
+                        connection,
+                    },
+                    (master_ip, master_port),
+                )),
+                Ok(role) => debug!(
+                    "[{}] Ignoring node {host}:{port} with role {role:?}",
+                    connection.tag()
+                ),
+                Err(e) => warn!(
+                    "[{}] Cannot execute command `ROLE` on node {host}:{port}: {e}",
+                    connection.tag()
+                ),
+            }
+        }
+
+        let Some(((master_host, master_port), mut master_connection)) = master else {
+            return Err(Error::Client("Cannot find a master node".to_owned()));
+        };
+
+        let master_addrs = lookup_host(&master_host, master_port)
+            .await
+            .unwrap_or_default();
+        let mut replicas = Vec::with_capacity(candidates.len());
+
+        for (replica, (replica_master_host, replica_master_port)) in candidates {
+            let same_master = replica_master_port == master_port
+                && (replica_master_host.eq_ignore_ascii_case(&master_host)
+                    || lookup_host(&replica_master_host, replica_master_port)
+                        .await
+                        .is_ok_and(|addrs| addrs.iter().any(|addr| master_addrs.contains(addr))));
+
+            if same_master {
+                replicas.push(replica);
+            } else {
+                warn!(
+                    "[{}] Ignoring replica {}:{} of another master {replica_master_host}:{replica_master_port}",
+                    master_connection.tag(),
+                    replica.address.0,
+                    replica.address.1
+                );
+            }
+        }
+
+        let replica_set = ReplicaSet::new(
+            ReplicaSelectionStrategy::RoundRobin,
+            replicas,
+            &mut master_connection,
+        )
+        .await?;
+
+        Ok((master_connection, replica_set))
+    }
+
+    pub(crate) fn tag(&self) -> &str {
+        self.master_connection.tag()
+    }
+}
@@ -1,22 +1,35 @@
+use super::util::RefPubSubMessage;
+#[cfg(feature = "tls")]
+use crate::client::SentinelTlsConfig;
 use crate::{
-    client::{Config, SentinelConfig},
+    client::{Config, SentinelConfig, SentinelEvent},
     commands::{RoleResult, SentinelCommands, ServerCommands},
-    resp::{Command, RespBuf},
-    sleep, Error, Result, RetryReason, StandaloneConnection,
+    network::{Replica, ReplicaSet},
+    resp::{cmd, Command, RespBuf},
+    sleep, Error, Result, RetryReason, SentinelEventSender, StandaloneConnection,
 };
-use log::debug;
+use futures_util::{select, FutureExt};
+use log::{debug, info, warn};
 use smallvec::SmallVec;
 
 pub struct SentinelConnection {
     sentinel_config: SentinelConfig,
     config: Config,
     pub inner_connection: StandaloneConnection,
+    /// replicas of the master, when read-only commands are sent to replicas
+    replica_set: Option<ReplicaSet>,
+    /// connection to the Sentinel instance subscribed to Sentinel events
+    events_connection: Option<StandaloneConnection>,
+    event_senders: Vec<SentinelEventSender>,
 }
 
 impl SentinelConnection {
     #[inline]
     pub async fn write(&mut self, command: &Command) -> Result<()> {
-        self.inner_connection.write(command).await
+        match &mut self.replica_set {
+            Some(replica_set) => replica_set.write(&mut self.inner_connection, command).await,
+            None => self.inner_connection.write(command).await,
+        }
     }
 
     #[inline]
@@ -25,24 +38,184 @@ impl SentinelConnection {
         commands: SmallVec<[&mut Command; 10]>,
         retry_reasons: &[RetryReason],
     ) -> Result<()> {
-        self.inner_connection
-            .write_batch(commands, retry_reasons)
-            .await
+        match &mut self.replica_set {
+            Some(replica_set) => {
+                replica_set
+                    .write_batch(&mut self.inner_connection, commands, retry_reasons)
+                    .await
+            }
+            None => {
+                self.inner_connection
+                    .write_batch(commands, retry_reasons)
+                    .await
+            }
+        }
     }
 
-    #[inline]
+    /// Read the next result from the master, or from a replica for read-only commands.
+    ///
+    /// Meanwhile, Sentinel events are forwarded to registered senders.
+    /// When Sentinel announces a new master for the service, `None` is returned
+    /// as if the connection was closed, so that the caller reconnects to the new master.
     pub async fn read(&mut self) -> Option<Result<RespBuf>> {
-        self.inner_connection.read().await
+        loop {
+            let Some(events_connection) = &mut self.events_connection else {
+                return Self::read_from_data_nodes(
+                    &mut self.inner_connection,
+                    &mut self.replica_set,
+                )
+                .await;
+            };
+
+            let event = select! {
+                result = Self::read_from_data_nodes(&mut self.inner_connection, &mut self.replica_set).fuse() => return result,
+                event = events_connection.read().fuse() => event,
+            };
+
+            match event {
+                Some(Ok(resp_buf)) => {
+                    if self.handle_event(&resp_buf) {
+                        return None;
+                    }
+                }
+                Some(Err(e)) => {
+                    warn!(
+                        "[{}] Error while reading Sentinel events: {e}",
+                        self.inner_connection.tag()
+                    );
+                    self.events_connection = None;
+                }
+                None => {
+                    warn!(
+                        "[{}] Connection to Sentinel events has been closed",
+                        self.inner_connection.tag()
+                    );
+                    self.events_connection = None;
+                }
+            }
+        }
+    }
+
+    async fn read_from_data_nodes(
+        inner_connection: &mut StandaloneConnection,
+        replica_set: &mut Option<ReplicaSet>,
+    ) -> Option<Result<RespBuf>> {
+        match replica_set {
+            Some(replica_set) => replica_set.read(inner_connection).await,
+            None => inner_connection.read().await,
+        }
     }
 
     #[inline]
     pub async fn reconnect(&mut self) -> Result<()> {
-        self.inner_connection =
-            Self::connect_to_sentinel(&self.sentinel_config, &self.config).await?;
+        let (mut inner_connection, mut sentinel_connection) =
+            Self::connect_to_sentinel(&mut self.sentinel_config, &self.config).await?;
+        self.replica_set = Self::connect_to_replicas(
+            &self.sentinel_config,
+            &self.config,
+            &mut inner_connection,
+            &mut sentinel_connection,
+        )
+        .await?;
+        self.inner_connection = inner_connection;
+        self.events_connection = Self::subscribe_to_events(sentinel_connection).await;
 
         Ok(())
     }
 
+    pub(crate) fn register_sentinel_event_sender(&mut self, sender: SentinelEventSender) {
+        self.event_senders.push(sender);
+    }
+
+    /// Forward a Sentinel event to registered senders.
+    ///
+    /// Returns `true` if the master of the service has been switched
+    fn handle_event(&mut self, resp_buf: &RespBuf) -> bool {
+        let Some(RefPubSubMessage::Message(channel, payload)) =
+            RefPubSubMessage::from_resp(resp_buf)
+        else {
+            return false;
+        };
+
+        let Some(event) = SentinelEvent::parse(channel, payload) else {
+            debug!(
+                "[{}] Cannot parse Sentinel event from channel `{}`",
+                self.inner_connection.tag(),
+                String::from_utf8_lossy(channel)
+            );
+            return false;
+        };
+
+        if event.master_name() != self.sentinel_config.service_name {
+            return false;
+        }
+
+        debug!(
+            "[{}] Received Sentinel event: {event:?}",
+            self.inner_connection.tag()
+        );
+
+        self.event_senders
+            .retain(|sender| sender.unbounded_send(event.clone()).is_ok());
+
+        if let SentinelEvent::SwitchMaster { new_address, .. } = &event {
+            info!(
+                "[{}] Master switched to {}:{}",
+                self.inner_connection.tag(),
+                new_address.0,
+                new_address.1
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Subscribe to Sentinel events on the connection used to discover the master.
+    ///
+    /// Events are optional: failing to subscribe does not prevent the connection to the master
+    async fn subscribe_to_events(
+        mut sentinel_connection: StandaloneConnection,
+    ) -> Option<StandaloneConnection> {
+        let result: Result<()> = async {
+            sentinel_connection
+                .write_internal(&cmd("SUBSCRIBE").arg(SentinelEvent::CHANNELS))
+                .await?;
+
+            for _ in 0..SentinelEvent::CHANNELS.len() {
+                let resp_buf = sentinel_connection.read().await.ok_or_else(|| {
+                    Error::Client(format!(
+                        "[{}] disconnected by peer",
+                        sentinel_connection.tag()
+                    ))
+                })??;
+
+                if !matches!(
+                    RefPubSubMessage::from_resp(&resp_buf),
+                    Some(RefPubSubMessage::Subscribe(_))
+                ) {
+                    return Err(Error::Client(format!(
+                        "Unexpected subscription confirmation: {resp_buf}"
+                    )));
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => Some(sentinel_connection),
+            Err(e) => {
+                warn!(
+                    "[{}] Cannot subscribe to Sentinel events: {e}",
+                    sentinel_connection.tag()
+                );
+                None
+            }
+        }
+    }
+
     /// Follow `Redis service discovery via Sentinel` documentation
     /// #See <https://redis.io/docs/reference/sentinel-clients/#redis-service-discovery-via-sentinel>
     ///
@@ -53,22 +226,135 @@ impl SentinelConnection {
         sentinel_config: &SentinelConfig,
         config: &Config,
     ) -> Result<SentinelConnection> {
-        let inner_connection = Self::connect_to_sentinel(sentinel_config, config).await?;
+        let mut sentinel_config = sentinel_config.clone();
+        let (mut inner_connection, mut sentinel_connection) =
+            Self::connect_to_sentinel(&mut sentinel_config, config).await?;
+        let replica_set = Self::connect_to_replicas(
+            &sentinel_config,
+            config,
+            &mut inner_connection,
+            &mut sentinel_connection,
+        )
+        .await?;
+        let events_connection = Self::subscribe_to_events(sentinel_connection).await;
 
         Ok(SentinelConnection {
-            sentinel_config: sentinel_config.clone(),
+            sentinel_config,
             config: config.clone(),
             inner_connection,
+            replica_set,
+            events_connection,
+            event_senders: Vec::new(),
         })
     }
 
-    async fn connect_to_sentinel(
+    /// Discover the replicas of the service with `SENTINEL REPLICAS`
+    /// and connect to the healthy ones, if reading from replicas is enabled
+    async fn connect_to_replicas(
         sentinel_config: &SentinelConfig,
         config: &Config,
-    ) -> Result<StandaloneConnection> {
-        let mut restart = false;
-        let mut unreachable_sentinel = true;
+        master_connection: &mut StandaloneConnection,
+        sentinel_connection: &mut StandaloneConnection,
+    ) -> Result<Option<ReplicaSet>> {
+        let Some(strategy) = sentinel_config.read_from_replicas else {
+            return Ok(None);
+        };
+
+        // without the list of replicas, all the commands are sent to the master
+        let replica_infos = match sentinel_connection
+            .sentinel_replicas(sentinel_config.service_name.clone())
+            .await
+        {
+            Ok(replica_infos) => replica_infos,
+            Err(e) => {
+                warn!(
+                    "[{}] Cannot execute command `SENTINEL REPLICAS`, reading from master only: {e}",
+                    master_connection.tag()
+                );
+                Vec::new()
+            }
+        };
+
+        let mut replicas = Vec::with_capacity(replica_infos.len());
+
+        for replica_info in replica_infos {
+            if replica_info
+                .flags
+                .split(',')
+                .any(|flag| matches!(flag, "s_down" | "o_down" | "disconnected"))
+            {
+                debug!(
+                    "[{}] Ignoring replica {}:{} with flags `{}`",
+                    master_connection.tag(),
+                    replica_info.ip,
+                    replica_info.port,
+                    replica_info.flags
+                );
+                continue;
+            }
+
+            match StandaloneConnection::connect(&replica_info.ip, replica_info.port, config).await {
+                Ok(connection) => replicas.push(Replica {
+                    address: (replica_info.ip, replica_info.port),
+                    replication_offset: replica_info.slave_repl_offset,
+                    connection,
+                }),
+                Err(e) => warn!(
+                    "[{}] Cannot connect to replica {}:{}: {e}",
+                    master_connection.tag(),
+                    replica_info.ip,
+                    replica_info.port
+                ),
+            }
+        }
+
+        Ok(Some(
+            ReplicaSet::new(strategy, replicas, master_connection).await?,
+        ))
+    }
+
+    /// Refresh the list of Sentinel instances with `SENTINEL SENTINELS`
+    /// and move the responsive instance to the front of the list.
+    ///
+    /// Newly discovered instances are appended after the already known ones
+    async fn refresh_sentinel_instances(
+        sentinel_config: &mut SentinelConfig,
+        responsive_instance: (String, u16),
+        sentinel_connection: &mut StandaloneConnection,
+    ) {
+        let mut instances = vec![responsive_instance];
+        instances.append(&mut sentinel_config.instances);
+
+        match sentinel_connection
+            .sentinel_sentinels(sentinel_config.service_name.clone())
+            .await
+        {
+            Ok(sentinel_infos) => instances.extend(
+                sentinel_infos
+                    .into_iter()
+                    .map(|sentinel_info| (sentinel_info.ip, sentinel_info.port)),
+            ),
+            Err(e) => debug!(
+                "[{}] Cannot execute command `SENTINEL SENTINELS`: {e}",
+                sentinel_connection.tag()
+            ),
+        }
+
+        for instance in instances {
+            if !sentinel_config.instances.contains(&instance) {
+                sentinel_config.instances.push(instance);
+            }
+        }
+
+        debug!(
+            "[{}] Sentinel instances: {:?}",
+            sentinel_connection.tag(),
+            sentinel_config.instances
+        );
+    }
 
+    /// Config used to connect to Sentinel instances
+    fn sentinel_node_config(sentinel_config: &SentinelConfig, config: &Config) -> Config {
         let mut sentinel_node_config = config.clone();
         sentinel_node_config
             .username
@@ -77,10 +363,40 @@ impl SentinelConnection {
             .password
             .clone_from(&sentinel_config.password);
 
+        if let Some(connect_timeout) = sentinel_config.connect_timeout {
+            sentinel_node_config.connect_timeout = connect_timeout;
+        }
+
+        // Sentinel does not support client tracking
+        sentinel_node_config.client_side_cache = None;
+
+        #[cfg(feature = "tls")]
+        match &sentinel_config.tls_config {
+            SentinelTlsConfig::Inherit => (),
+            SentinelTlsConfig::Disabled => sentinel_node_config.tls_config = None,
+            SentinelTlsConfig::Enabled(tls_config) => {
+                sentinel_node_config.tls_config = Some(tls_config.clone())
+            }
+        }
+
+        sentinel_node_config
+    }
+
+    /// Returns a tuple made up of the connection to the master
+    /// and the connection to the Sentinel instance which gave the master address
+    async fn connect_to_sentinel(
+        sentinel_config: &mut SentinelConfig,
+        config: &Config,
+    ) -> Result<(StandaloneConnection, StandaloneConnection)> {
+        let mut restart = false;
+        let mut unreachable_sentinel = true;
+
+        let sentinel_node_config = Self::sentinel_node_config(sentinel_config, config);
+
         loop {
-            for sentinel_instance in &sentinel_config.instances {
+            for sentinel_instance in sentinel_config.instances.clone() {
                 // Step 1: connecting to Sentinel
-                let (host, port) = sentinel_instance;
+                let (host, port) = &sentinel_instance;
 
                 let mut sentinel_connection =
                     match StandaloneConnection::connect(host, *port, &sentinel_node_config).await {
@@ -122,7 +438,13 @@ impl SentinelConnection {
                     replica_infos: _,
                 } = role
                 {
-                    return Ok(master_connection);
+                    Self::refresh_sentinel_instances(
+                        sentinel_config,
+                        sentinel_instance,
+                        &mut sentinel_connection,
+                    )
+                    .await;
+                    return Ok((master_connection, sentinel_connection));
                 } else {
                     sleep(sentinel_config.wait_between_failures).await;
                     // restart from the beginning
@@ -1,21 +1,28 @@
+#[cfg(feature = "record-replay")]
+use crate::client::{redact_command, TrafficEventKind};
 use crate::{
     client::{Config, PreparedCommand},
     commands::{
-        ClusterCommands, ConnectionCommands, HelloOptions, SentinelCommands, ServerCommands,
+        ClientTrackingStatus, ClusterCommands, ConnectionCommands, HelloOptions, SentinelCommands,
+        ServerCommands,
+    },
+    resp::{
+        flush_chunks, BufferDecoder, BytesChunks, Command, CommandEncoder, RespBuf, WriteChunk,
     },
-    resp::{BufferDecoder, Command, CommandEncoder, RespBuf},
     tcp_connect, Error, Future, Result, RetryReason, TcpStreamReader, TcpStreamWriter,
 };
+#[cfg(feature = "fault-injection")]
+use crate::{resp::cmd, FaultInjector, WriteFault};
 #[cfg(feature = "tls")]
 use crate::{tcp_tls_connect, TcpTlsStreamReader, TcpTlsStreamWriter};
 use bytes::BytesMut;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::StreamExt;
 use log::{debug, log_enabled, Level};
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
 use std::future::IntoFuture;
-use tokio::io::AsyncWriteExt;
-use tokio_util::codec::{Encoder, FramedRead, FramedWrite};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio_util::codec::{FramedRead, FramedWrite};
 
 pub(crate) enum Streams {
     Tcp(
@@ -35,7 +42,8 @@ impl Streams {
         if let Some(tls_config) = &config.tls_config {
             let (reader, writer) =
                 tcp_tls_connect(host, port, tls_config, config.connect_timeout).await?;
-            let framed_read = FramedRead::new(reader, BufferDecoder);
+            let framed_read =
+                FramedRead::new(reader, BufferDecoder::with_limits(config.reply_limits));
             let framed_write = FramedWrite::new(writer, CommandEncoder);
             Ok(Streams::TcpTls(framed_read, framed_write))
         } else {
@@ -48,7 +56,7 @@ impl Streams {
 
     pub async fn connect_non_secure(host: &str, port: u16, config: &Config) -> Result<Self> {
         let (reader, writer) = tcp_connect(host, port, config).await?;
-        let framed_read = FramedRead::new(reader, BufferDecoder);
+        let framed_read = FramedRead::new(reader, BufferDecoder::with_limits(config.reply_limits));
         let framed_write = FramedWrite::new(writer, CommandEncoder);
         Ok(Streams::Tcp(framed_read, framed_write))
     }
@@ -60,8 +68,12 @@ pub struct StandaloneConnection {
     config: Config,
     streams: Streams,
     buffer: BytesMut,
+    /// The write half has been shut down after an incomplete streamed argument
+    write_closed: bool,
     version: String,
     tag: String,
+    #[cfg(feature = "fault-injection")]
+    fault_injector: FaultInjector,
 }
 
 impl StandaloneConnection {
@@ -74,35 +86,83 @@ impl StandaloneConnection {
             config: config.clone(),
             streams,
             buffer: BytesMut::new(),
+            write_closed: false,
             version: String::new(),
             tag: if config.connection_name.is_empty() {
                 format!("{}:{}", host, port)
             } else {
                 format!("{}:{}:{}", config.connection_name, host, port)
             },
+            #[cfg(feature = "fault-injection")]
+            fault_injector: FaultInjector::new(config.fault_plan.clone()),
         };
 
+        #[cfg(feature = "record-replay")]
+        connection.record(TrafficEventKind::Connect, &[]);
+
         connection.post_connect().await?;
 
         Ok(connection)
     }
 
+    /// Writes a command of the caller, on which the faults of the plan are injected
     pub async fn write(&mut self, command: &Command) -> Result<()> {
-        if log_enabled!(Level::Debug) {
-            debug!("[{}] Sending {command:?}", self.tag);
-        }
-        match &mut self.streams {
-            Streams::Tcp(_, framed_write) => framed_write.send(command).await,
-            #[cfg(feature = "tls")]
-            Streams::TcpTls(_, framed_write) => framed_write.send(command).await,
-        }
+        self.write_commands(&[command], true).await
+    }
+
+    /// Writes a command sent by the client itself (handshake, discovery, ...)
+    pub async fn write_internal(&mut self, command: &Command) -> Result<()> {
+        self.write_commands(&[command], false).await
     }
 
     pub async fn write_batch(
         &mut self,
-        commands: SmallVec<[&mut Command; 10]>,
+        #[allow(unused_mut)] mut commands: SmallVec<[&mut Command; 10]>,
         _retry_reasons: &[RetryReason],
     ) -> Result<()> {
+        #[cfg(debug_assertions)]
+        {
+            let mut kill_connection = false;
+            for command in commands.iter_mut() {
+                if command.kill_connection_on_write > 0 {
+                    kill_connection = true;
+                    command.kill_connection_on_write -= 1;
+                }
+            }
+
+            if kill_connection {
+                let client_id = self.client_id().await?;
+                let mut config = self.config.clone();
+                "killer".clone_into(&mut config.connection_name);
+                let mut connection =
+                    StandaloneConnection::connect(&self.host, self.port, &config).await?;
+                connection
+                    .client_kill(crate::commands::ClientKillOptions::default().id(client_id))
+                    .await?;
+            }
+        }
+
+        let commands: SmallVec<[&Command; 10]> =
+            commands.into_iter().map(|command| &*command).collect();
+        self.write_commands(&commands, true).await
+    }
+
+    #[cfg_attr(not(feature = "fault-injection"), allow(unused_variables))]
+    async fn write_commands(&mut self, commands: &[&Command], inject_faults: bool) -> Result<()> {
+        #[cfg(feature = "fault-injection")]
+        if self.fault_injector.is_killed() {
+            return Err(killed_connection_error());
+        }
+
+        // the server closes the connection after an incomplete streamed argument
+        if self.write_closed {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "connection closed after an incomplete streamed argument",
+            )
+            .into());
+        }
+
         self.buffer.clear();
 
         let command_encoder = match &mut self.streams {
@@ -111,47 +171,109 @@ impl StandaloneConnection {
             Streams::TcpTls(_, framed_write) => framed_write.encoder_mut(),
         };
 
-        #[cfg(debug_assertions)]
-        let mut kill_connection = false;
+        #[cfg(feature = "fault-injection")]
+        let mut kill_before_write = false;
+        #[cfg(feature = "fault-injection")]
+        let mut kill_after_write = false;
+        let mut chunks = Vec::new();
+        #[cfg(feature = "record-replay")]
+        let mut recorded_commands = SmallVec::<[Vec<u8>; 10]>::new();
 
-        for command in commands {
+        for &command in commands {
             if log_enabled!(Level::Debug) {
                 debug!("[{}] Sending {command:?}", self.tag);
             }
 
-            #[cfg(debug_assertions)]
-            if command.kill_connection_on_write > 0 {
-                kill_connection = true;
-                command.kill_connection_on_write -= 1;
+            #[cfg(feature = "fault-injection")]
+            let write_fault = if inject_faults {
+                self.fault_injector.on_write(command)
+            } else {
+                self.fault_injector.on_internal_write(command);
+                WriteFault::None
+            };
+
+            #[cfg(feature = "fault-injection")]
+            match write_fault {
+                WriteFault::None => (),
+                WriteFault::ReplaceWithPing => {
+                    debug!("[{}] Injecting error reply to {command:?}", self.tag);
+                    let ping = cmd("PING");
+                    command_encoder.encode_chunks(&ping, &mut self.buffer, &mut chunks);
+                    #[cfg(feature = "record-replay")]
+                    if self.config.traffic_recorder.is_some() {
+                        recorded_commands.push(encode_for_record(&ping));
+                    }
+                    continue;
+                }
+                WriteFault::KillBeforeWrite => kill_before_write = true,
+                WriteFault::KillAfterWrite => kill_after_write = true,
             }
 
-            command_encoder.encode(command, &mut self.buffer)?;
+            command_encoder.encode_chunks(command, &mut self.buffer, &mut chunks);
+            #[cfg(feature = "record-replay")]
+            if self.config.traffic_recorder.is_some() {
+                recorded_commands.push(encode_for_record(command));
+            }
         }
 
-        #[cfg(debug_assertions)]
-        if kill_connection {
-            let client_id = self.client_id().await?;
-            let mut config = self.config.clone();
-            "killer".clone_into(&mut config.connection_name);
-            let mut connection =
-                StandaloneConnection::connect(&self.host, self.port, &config).await?;
-            connection
-                .client_kill(crate::commands::ClientKillOptions::default().id(client_id))
-                .await?;
+        flush_chunks(&mut self.buffer, &mut chunks);
+
+        #[cfg(feature = "fault-injection")]
+        if kill_before_write {
+            debug!("[{}] Injecting connection kill before write", self.tag);
+            self.fault_injector.kill();
+            return Err(killed_connection_error());
         }
 
         match &mut self.streams {
-            Streams::Tcp(_, framed_write) => framed_write.get_mut().write_all(&self.buffer).await?,
+            Streams::Tcp(_, framed_write) => {
+                write_chunks(framed_write.get_mut(), chunks, &mut self.write_closed).await?
+            }
             #[cfg(feature = "tls")]
             Streams::TcpTls(_, framed_write) => {
-                framed_write.get_mut().write_all(&self.buffer).await?
+                write_chunks(framed_write.get_mut(), chunks, &mut self.write_closed).await?
             }
         }
 
+        #[cfg(feature = "record-replay")]
+        for command in recorded_commands {
+            self.record(TrafficEventKind::Command, &command);
+        }
+
+        #[cfg(feature = "fault-injection")]
+        if kill_after_write {
+            debug!("[{}] Injecting connection kill after write", self.tag);
+            self.fault_injector.kill();
+        }
+
         Ok(())
     }
 
+    #[cfg(not(feature = "fault-injection"))]
     pub async fn read(&mut self) -> Option<Result<RespBuf>> {
+        self.read_frame().await
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub async fn read(&mut self) -> Option<Result<RespBuf>> {
+        // a killed connection is seen as closed by the server
+        if self.fault_injector.is_killed() {
+            return None;
+        }
+
+        loop {
+            if self.fault_injector.has_delayed_reply() {
+                return self.fault_injector.delayed_reply().await;
+            }
+
+            let result = self.read_frame().await?;
+            if let Some(result) = self.fault_injector.on_read(result) {
+                return Some(result);
+            }
+        }
+    }
+
+    async fn read_frame(&mut self) -> Option<Result<RespBuf>> {
         if let Some(result) = match &mut self.streams {
             Streams::Tcp(framed_read, _) => framed_read.next().await,
             #[cfg(feature = "tls")]
@@ -163,15 +285,31 @@ impl StandaloneConnection {
                     Err(err) => debug!("[{}] Received result {err:?}", self.tag),
                 }
             }
+            #[cfg(feature = "record-replay")]
+            if let Ok(resp_buf) = &result {
+                let kind = if resp_buf.is_push_message() {
+                    TrafficEventKind::Push
+                } else {
+                    TrafficEventKind::Reply
+                };
+                self.record(kind, resp_buf);
+            }
             Some(result)
         } else {
             debug!("[{}] Socked is closed", self.tag);
+            #[cfg(feature = "record-replay")]
+            self.record(TrafficEventKind::Disconnect, &[]);
             None
         }
     }
 
     pub async fn reconnect(&mut self) -> Result<()> {
         self.streams = Streams::connect(&self.host, self.port, &self.config).await?;
+        self.write_closed = false;
+        #[cfg(feature = "fault-injection")]
+        self.fault_injector.reset();
+        #[cfg(feature = "record-replay")]
+        self.record(TrafficEventKind::Connect, &[]);
         self.post_connect().await?;
 
         Ok(())
@@ -207,9 +345,23 @@ impl StandaloneConnection {
             self.select(self.config.database).await?;
         }
 
+        // invalidation messages of the client side cache
+        if let Some(client_side_cache) = &self.config.client_side_cache {
+            let options = client_side_cache.tracking_options();
+            self.client_tracking(ClientTrackingStatus::On, options)
+                .await?;
+        }
+
         Ok(())
     }
 
+    #[cfg(feature = "record-replay")]
+    fn record(&self, kind: TrafficEventKind, data: &[u8]) {
+        if let Some(traffic_recorder) = &self.config.traffic_recorder {
+            traffic_recorder.record(&format!("{}:{}", self.host, self.port), kind, data);
+        }
+    }
+
     pub fn get_version(&self) -> &str {
         &self.version
     }
@@ -228,7 +380,7 @@ where
 
     fn into_future(self) -> Self::IntoFuture {
         Box::pin(async move {
-            self.executor.write(&self.command).await?;
+            self.executor.write_internal(&self.command).await?;
 
             let resp_buf = self.executor.read().await.ok_or_else(|| {
                 Error::Client(format!("[{}] disconnected by peer", self.executor.tag()))
@@ -243,3 +395,81 @@ impl<'a> ClusterCommands<'a> for &'a mut StandaloneConnection {}
 impl<'a> ConnectionCommands<'a> for &'a mut StandaloneConnection {}
 impl<'a> SentinelCommands<'a> for &'a mut StandaloneConnection {}
 impl<'a> ServerCommands<'a> for &'a mut StandaloneConnection {}
+
+/// Writes the encoded commands, streaming the content of the [`BulkReader`](crate::resp::BulkReader) arguments
+async fn write_chunks<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    chunks: Vec<WriteChunk>,
+    write_closed: &mut bool,
+) -> Result<()> {
+    // readers are taken before writing anything: a command cannot be sent twice with the same reader
+    let mut readers = SmallVec::<[_; 1]>::new();
+    for chunk in &chunks {
+        if let WriteChunk::Reader(bulk_reader) = chunk {
+            let Some(reader) = bulk_reader.take() else {
+                return Err(Error::Client(
+                    "Streamed argument has already been consumed".to_owned(),
+                ));
+            };
+            readers.push((reader, bulk_reader.len()));
+        }
+    }
+
+    let mut readers = readers.into_iter();
+    let mut bytes_chunks = BytesChunks::default();
+
+    for chunk in chunks {
+        match chunk {
+            WriteChunk::Bytes(bytes) => bytes_chunks.push(bytes),
+            WriteChunk::Reader(_) => {
+                writer.write_all_buf(&mut bytes_chunks).await?;
+                let (reader, len) = readers.next().expect("reader taken");
+                let result = tokio::io::copy(&mut reader.take(len as u64), writer).await;
+                if !matches!(result, Ok(written) if written == len as u64) {
+                    // the bulk string is incomplete: the server must close the connection
+                    let _ = writer.shutdown().await;
+                    *write_closed = true;
+                    return Err(match result {
+                        Ok(written) => Error::Client(format!(
+                            "Streamed argument ended after {written} bytes instead of {len}"
+                        )),
+                        Err(e) => e.into(),
+                    });
+                }
+            }
+        }
+    }
+
+    writer.write_all_buf(&mut bytes_chunks).await?;
+    Ok(())
+}
+
+/// Error of the commands written on a connection killed by the [`FaultPlan`](crate::client::FaultPlan)
+#[cfg(feature = "fault-injection")]
+fn killed_connection_error() -> Error {
+    std::io::Error::new(
+        std::io::ErrorKind::ConnectionAborted,
+        "connection killed by the fault plan",
+    )
+    .into()
+}
+
+/// Encodes a command as written to the network, without its password and streamed arguments
+#[cfg(feature = "record-replay")]
+fn encode_for_record(command: &Command) -> Vec<u8> {
+    let redacted = redact_command(command);
+    let command = redacted.as_ref().unwrap_or(command);
+
+    let mut buffer = BytesMut::new();
+    let mut chunks = Vec::new();
+    CommandEncoder.encode_chunks(command, &mut buffer, &mut chunks);
+    flush_chunks(&mut buffer, &mut chunks);
+
+    let mut encoded = Vec::new();
+    for chunk in chunks {
+        if let WriteChunk::Bytes(bytes) = chunk {
+            encoded.extend_from_slice(&bytes);
+        }
+    }
+    encoded
+}
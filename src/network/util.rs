@@ -1,4 +1,4 @@
-use crate::resp::{BytesSeed, RespBuf, RespDeserializer};
+use crate::resp::{BytesSeed, Command, RespBuf, RespDeserializer};
 use serde::{de::Visitor, Deserializer};
 use std::fmt;
 
@@ -138,3 +138,13 @@ impl<'a> RefPubSubMessage<'a> {
         }
     }
 }
+
+/// `CLIENT CACHING` applies to the next command sent on the same connection
+pub(crate) fn is_client_caching(command: &Command) -> bool {
+    command.name == "CLIENT"
+        && command
+            .args
+            .into_iter()
+            .next()
+            .is_some_and(|arg| arg.eq_ignore_ascii_case(b"CACHING"))
+}
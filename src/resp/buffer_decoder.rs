@@ -1,29 +1,428 @@
-use super::RespDeserializer;
-use crate::{resp::RespBuf, Error, Result};
-use bytes::BytesMut;
-use serde::{de::IgnoredAny, Deserialize};
+use crate::{
+    client::ReplyLimits,
+    resp::{
+        RespBuf, ARRAY_TAG, ATTRIBUTE_TAG, BIG_NUMBER_TAG, BLOB_ERROR_TAG, BOOL_TAG,
+        BULK_STRING_TAG, DOUBLE_TAG, ERROR_TAG, INTEGER_TAG, MAP_TAG, NIL_TAG, PUSH_TAG, SET_TAG,
+        SIMPLE_STRING_TAG, STREAMED_AGGREGATE_END_TAG, STREAMED_LENGTH, STREAMED_STRING_CHUNK_TAG,
+        VERBATIM_STRING_TAG,
+    },
+    Error, Result,
+};
+use bytes::{BufMut, BytesMut};
+use memchr::memchr;
+use smallvec::SmallVec;
 use tokio_util::codec::Decoder;
 
-pub(crate) struct BufferDecoder;
+/// Tokio codec decoder splitting a stream of bytes into [`RespBuf`] frames
+///
+/// The parse state (position, nesting of aggregates) is kept between calls to `decode`,
+/// so that the bytes of a frame received in several reads are scanned only once.
+///
+/// Malformed frames and frames exceeding the [`ReplyLimits`] of the decoder
+/// fail with an [`Error::Protocol`].
+#[derive(Default)]
+pub struct BufferDecoder {
+    limits: ReplyLimits,
+    /// position of the next value to scan in the current frame
+    pos: usize,
+    /// aggregates being scanned in the current frame, outermost first
+    stack: SmallVec<[Aggregate; 8]>,
+    /// the current frame contains streamed strings or aggregates
+    has_streamed_values: bool,
+    /// length of the chunks already scanned of the current streamed string
+    streamed_string_len: usize,
+}
+
+/// Aggregate being scanned by a [`BufferDecoder`]
+struct Aggregate {
+    /// number of remaining values, `None` for a streamed aggregate or string
+    remaining: Option<usize>,
+    kind: AggregateKind,
+}
+
+#[derive(PartialEq)]
+enum AggregateKind {
+    /// array, set, push or map
+    Collection,
+    /// attributes, which are followed by the value they are attached to
+    Attributes,
+    /// chunks of a streamed string
+    StreamedString,
+}
+
+impl BufferDecoder {
+    /// Creates a new `BufferDecoder` with limits on the decoded frames
+    #[must_use]
+    pub fn with_limits(limits: ReplyLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    fn push(&mut self, remaining: Option<usize>, kind: AggregateKind) -> Result<()> {
+        check_limit(self.stack.len() + 1, self.limits.max_depth, "Nesting depth")?;
+        self.stack.push(Aggregate { remaining, kind });
+        Ok(())
+    }
+
+    /// Check the size of the frame being scanned, even incomplete
+    fn check_reply_size(&self, size: usize) -> Result<()> {
+        check_limit(size, self.limits.max_reply_size, "Reply size")
+    }
+
+    fn check_bulk_size(&self, len: usize) -> Result<()> {
+        check_limit(len, self.limits.max_bulk_size, "Bulk string length")
+    }
+
+    /// Account for a complete value in the enclosing aggregates
+    ///
+    /// Returns `true` if the frame is complete
+    fn value_done(&mut self) -> bool {
+        loop {
+            let Some(aggregate) = self.stack.last_mut() else {
+                return true;
+            };
+
+            match &mut aggregate.remaining {
+                Some(remaining) => {
+                    *remaining -= 1;
+                    if *remaining > 0 {
+                        return false;
+                    }
+                }
+                // streamed aggregates end with a specific marker
+                None => return false,
+            }
+
+            if let Some(true) = self.close() {
+                return false;
+            }
+        }
+    }
+
+    /// Pop the innermost aggregate after its last value
+    ///
+    /// Returns `Some(true)` if these are attributes:
+    /// the value they are attached to is still to be scanned
+    fn close(&mut self) -> Option<bool> {
+        self.stack
+            .pop()
+            .map(|aggregate| aggregate.kind == AggregateKind::Attributes)
+    }
+
+    /// Scan the buffer from the saved position
+    ///
+    /// Returns `true` once a complete frame has been scanned
+    fn scan(&mut self, buf: &[u8]) -> Result<bool> {
+        loop {
+            let Some((tag, header, next)) = read_line(buf, self.pos) else {
+                self.check_reply_size(buf.len())?;
+                return Ok(false);
+            };
+
+            let in_streamed_string = matches!(
+                self.stack.last(),
+                Some(Aggregate {
+                    kind: AggregateKind::StreamedString,
+                    ..
+                })
+            );
+
+            let frame_complete = match tag {
+                STREAMED_STRING_CHUNK_TAG if in_streamed_string => {
+                    let len = parse_len(header)?;
+                    // the whole string is bounded, not only each chunk
+                    let streamed_string_len = checked_add(self.streamed_string_len, len)?;
+                    self.check_bulk_size(streamed_string_len)?;
+                    let end = content_end(next, len)?;
+                    self.check_reply_size(end)?;
+                    if len == 0 {
+                        self.pos = next;
+                        self.streamed_string_len = 0;
+                        self.close();
+                        self.value_done()
+                    } else if buf.len() < end {
+                        return Ok(false);
+                    } else {
+                        check_crlf(buf, end - 2)?;
+                        self.pos = end;
+                        self.streamed_string_len = streamed_string_len;
+                        false
+                    }
+                }
+                _ if in_streamed_string => {
+                    return Err(Error::Protocol(
+                        "Expected chunk in streamed string".to_owned(),
+                    ))
+                }
+                BULK_STRING_TAG if header == [STREAMED_LENGTH] => {
+                    self.has_streamed_values = true;
+                    self.push(None, AggregateKind::StreamedString)?;
+                    self.pos = next;
+                    false
+                }
+                BULK_STRING_TAG | BLOB_ERROR_TAG | VERBATIM_STRING_TAG => {
+                    // null bulk string ($-1)
+                    let end = if header.first() == Some(&b'-') {
+                        next
+                    } else {
+                        let len = parse_len(header)?;
+                        self.check_bulk_size(len)?;
+                        content_end(next, len)?
+                    };
+                    self.check_reply_size(end)?;
+                    if buf.len() < end {
+                        return Ok(false);
+                    }
+                    if end > next {
+                        check_crlf(buf, end - 2)?;
+                    }
+                    self.pos = end;
+                    self.value_done()
+                }
+                ARRAY_TAG | SET_TAG | PUSH_TAG | MAP_TAG | ATTRIBUTE_TAG => {
+                    self.pos = next;
+                    let kind = if tag == ATTRIBUTE_TAG {
+                        AggregateKind::Attributes
+                    } else {
+                        AggregateKind::Collection
+                    };
+                    if header == [STREAMED_LENGTH] {
+                        self.has_streamed_values = true;
+                        self.push(None, kind)?;
+                        false
+                    } else {
+                        // null array (*-1)
+                        let mut len = if header.first() == Some(&b'-') {
+                            0
+                        } else {
+                            parse_len(header)?
+                        };
+                        check_limit(len, self.limits.max_aggregate_len, "Aggregate length")?;
+                        if tag == MAP_TAG || tag == ATTRIBUTE_TAG {
+                            len = len.checked_mul(2).ok_or_else(length_overflow)?;
+                        }
+                        if len > 0 {
+                            self.push(Some(len), kind)?;
+                            false
+                        } else if kind == AggregateKind::Attributes {
+                            false
+                        } else {
+                            self.value_done()
+                        }
+                    }
+                }
+                STREAMED_AGGREGATE_END_TAG => {
+                    match self.stack.last() {
+                        Some(Aggregate {
+                            remaining: None, ..
+                        }) => (),
+                        _ => {
+                            return Err(Error::Protocol(
+                                "Unexpected end of streamed aggregate".to_owned(),
+                            ))
+                        }
+                    }
+                    self.pos = next;
+                    if let Some(true) = self.close() {
+                        false
+                    } else {
+                        self.value_done()
+                    }
+                }
+                SIMPLE_STRING_TAG | ERROR_TAG | INTEGER_TAG | DOUBLE_TAG | NIL_TAG | BOOL_TAG
+                | BIG_NUMBER_TAG => {
+                    // malformed lines are not checked here but when the frame is deserialized
+                    self.check_reply_size(next)?;
+                    self.pos = next;
+                    self.value_done()
+                }
+                _ => {
+                    return Err(Error::Protocol(format!(
+                        "Cannot parse tag '{}' (0x{tag:02x})",
+                        tag as char
+                    )))
+                }
+            };
+
+            if frame_complete {
+                return Ok(true);
+            }
+        }
+    }
+}
 
 impl Decoder for BufferDecoder {
     type Item = RespBuf;
     type Error = Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
-        if src.is_empty() {
-            return Ok(None);
+        match self.scan(src) {
+            Ok(true) => (),
+            Ok(false) => return Ok(None),
+            Err(e) => {
+                // the stream cannot be decoded any further
+                self.pos = 0;
+                self.stack.clear();
+                self.has_streamed_values = false;
+                self.streamed_string_len = 0;
+                return Err(e);
+            }
         }
 
-        let bytes = src.as_ref();
-        let mut deserializer = RespDeserializer::new(bytes);
-        let result = IgnoredAny::deserialize(&mut deserializer);
-        match result {
-            Ok(_) => Ok(Some(RespBuf::new(
-                src.split_to(deserializer.get_pos()).freeze(),
-            ))),
-            Err(Error::EOF) => Ok(None),
-            Err(e) => Err(e),
+        let frame = src.split_to(self.pos);
+        self.pos = 0;
+
+        if std::mem::take(&mut self.has_streamed_values) {
+            let mut normalized = BytesMut::with_capacity(frame.len());
+            normalize_value(&frame, 0, &mut normalized)?;
+            Ok(Some(RespBuf::new(normalized.freeze())))
+        } else {
+            Ok(Some(RespBuf::new(frame.freeze())))
+        }
+    }
+}
+
+/// Copy a complete RESP3 frame, starting at `pos`, to `out`,
+/// converting streamed strings and aggregates to their regular counterparts,
+/// so that they can be deserialized as any other value.
+///
+/// Returns the position following the copied value.
+fn normalize_value(frame: &[u8], pos: usize, out: &mut BytesMut) -> Result<usize> {
+    let (tag, header, next) = read_line(frame, pos).ok_or(Error::EOF)?;
+
+    match tag {
+        BULK_STRING_TAG if header == [STREAMED_LENGTH] => {
+            let mut content = BytesMut::new();
+            let mut pos = next;
+            loop {
+                // chunk: ;<len>\r\n<data>\r\n
+                let (_, len, next) = read_line(frame, pos).ok_or(Error::EOF)?;
+                let len = parse_len(len)?;
+                if len == 0 {
+                    pos = next;
+                    break;
+                }
+                pos = content_end(next, len)?;
+                content.put_slice(&frame[next..pos - 2]);
+            }
+            put_header(out, BULK_STRING_TAG, content.len());
+            out.put_slice(&content);
+            out.put_slice(b"\r\n");
+            Ok(pos)
+        }
+        BULK_STRING_TAG | BLOB_ERROR_TAG | VERBATIM_STRING_TAG => {
+            let end = match parse_len(header) {
+                Ok(len) => content_end(next, len)?,
+                // null bulk string ($-1)
+                Err(_) => next,
+            };
+            out.put_slice(&frame[pos..end]);
+            Ok(end)
         }
+        ARRAY_TAG | SET_TAG | PUSH_TAG | MAP_TAG | ATTRIBUTE_TAG if header == [STREAMED_LENGTH] => {
+            let mut elements = BytesMut::new();
+            let mut num_elements = 0;
+            let mut pos = next;
+            while frame.get(pos) != Some(&STREAMED_AGGREGATE_END_TAG) {
+                pos = normalize_value(frame, pos, &mut elements)?;
+                num_elements += 1;
+            }
+            let (_, _, mut pos) = read_line(frame, pos).ok_or(Error::EOF)?;
+            if tag == MAP_TAG || tag == ATTRIBUTE_TAG {
+                num_elements /= 2;
+            }
+            put_header(out, tag, num_elements);
+            out.put_slice(&elements);
+            if tag == ATTRIBUTE_TAG {
+                // the attributes and the value they precede form a single element
+                pos = normalize_value(frame, pos, out)?;
+            }
+            Ok(pos)
+        }
+        ARRAY_TAG | SET_TAG | PUSH_TAG | MAP_TAG | ATTRIBUTE_TAG => {
+            out.put_slice(&frame[pos..next]);
+            // null array (*-1) has no element
+            let mut num_elements = parse_len(header).unwrap_or_default();
+            if tag == MAP_TAG || tag == ATTRIBUTE_TAG {
+                num_elements = num_elements.checked_mul(2).ok_or_else(length_overflow)?;
+            }
+            let mut pos = next;
+            for _ in 0..num_elements {
+                pos = normalize_value(frame, pos, out)?;
+            }
+            if tag == ATTRIBUTE_TAG {
+                pos = normalize_value(frame, pos, out)?;
+            }
+            Ok(pos)
+        }
+        _ => {
+            out.put_slice(&frame[pos..next]);
+            Ok(next)
+        }
+    }
+}
+
+/// Returns the tag, the rest of the line and the position of the next line,
+/// or `None` if the line is incomplete
+fn read_line(buf: &[u8], pos: usize) -> Option<(u8, &[u8], usize)> {
+    let tag = *buf.get(pos)?;
+    let idx = memchr(b'\r', &buf[pos..])?;
+    (buf.len() > pos + idx + 1).then(|| (tag, &buf[pos + 1..pos + idx], pos + idx + 2))
+}
+
+fn parse_len(len: &[u8]) -> Result<usize> {
+    std::str::from_utf8(len)
+        .ok()
+        .and_then(|len| len.parse::<usize>().ok())
+        .ok_or_else(|| {
+            Error::Protocol(format!(
+                "Cannot parse length `{}`",
+                String::from_utf8_lossy(len)
+            ))
+        })
+}
+
+/// Check the `\r\n` terminating the content of a bulk string at `pos`
+fn check_crlf(buf: &[u8], pos: usize) -> Result<()> {
+    if &buf[pos..pos + 2] == b"\r\n" {
+        Ok(())
+    } else {
+        Err(Error::Protocol(
+            "Expected CRLF after bulk string content".to_owned(),
+        ))
+    }
+}
+
+/// Returns the position following `len` bytes of content starting at `pos` and their `\r\n`
+fn content_end(pos: usize, len: usize) -> Result<usize> {
+    checked_add(pos, len).and_then(|end| checked_add(end, 2))
+}
+
+fn checked_add(a: usize, b: usize) -> Result<usize> {
+    a.checked_add(b).ok_or_else(length_overflow)
+}
+
+/// Lengths so large that their arithmetic overflows cannot be those of a valid frame
+fn length_overflow() -> Error {
+    Error::Protocol("Length overflows the size of a frame".to_owned())
+}
+
+/// `0` means unlimited
+fn check_limit(value: usize, limit: usize, name: &str) -> Result<()> {
+    if limit != 0 && value > limit {
+        Err(Error::Protocol(format!(
+            "{name} {value} exceeds the limit of {limit}"
+        )))
+    } else {
+        Ok(())
     }
 }
+
+fn put_header(out: &mut BytesMut, tag: u8, len: usize) {
+    let mut temp = itoa::Buffer::new();
+    out.put_u8(tag);
+    out.put_slice(temp.format(len).as_bytes());
+    out.put_slice(b"\r\n");
+}
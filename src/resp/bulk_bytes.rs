@@ -0,0 +1,204 @@
+use bytes::Bytes;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{cell::Cell, fmt, ops::Deref};
+
+/// Name of the newtype struct requested by [`BulkBytes`]
+/// so that [`RespDeserializer`](crate::resp::RespDeserializer) can share its source buffer
+pub(crate) const BULK_BYTES_FAKE_NAME: &str = ">>>BULK_BYTES>>>";
+
+thread_local! {
+    /// Shared view of the bytes being visited by [`visit_shared_bytes`] on this thread
+    static SHARED_BYTES: Cell<Option<Bytes>> = const { Cell::new(None) };
+}
+
+/// Visit `slice` as borrowed bytes, making `shared`, a view of the same bytes,
+/// available to the [`BulkBytes`] visitor so that it does not copy them.
+///
+/// The shared view is cleared when the visit ends, even if the visitor panics.
+pub(crate) fn visit_shared_bytes<'de, V, E>(
+    shared: Bytes,
+    slice: &'de [u8],
+    visitor: V,
+) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error,
+{
+    struct ClearGuard;
+
+    impl Drop for ClearGuard {
+        fn drop(&mut self) {
+            SHARED_BYTES.with(|shared| shared.take());
+        }
+    }
+
+    let _guard = ClearGuard;
+    SHARED_BYTES.with(|cell| cell.set(Some(shared)));
+    visitor.visit_borrowed_bytes(slice)
+}
+
+/// Represents a [Bulk String](https://redis.io/docs/reference/protocol-spec/#resp-bulk-strings) RESP type
+/// sharing the buffer of the response instead of copying it.
+///
+/// When deserialized from a [`RespBuf`](crate::resp::RespBuf),
+/// which is the case for the replies of the commands sent by a [`Client`](crate::client::Client),
+/// one by one or in a [`Pipeline`](crate::client::Pipeline),
+/// `BulkBytes` is a reference-counted view of the network read buffer:
+/// large payloads are not copied.
+/// Deserialized from any other source, the content is copied.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::Client,
+///     commands::{StringCommands},
+///     resp::BulkBytes,
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///
+///     client.set("key", vec![0u8; 1024 * 1024]).await?;
+///     let value: BulkBytes = client.get("key").await?;
+///     assert_eq!(1024 * 1024, value.len());
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BulkBytes(Bytes);
+
+impl BulkBytes {
+    /// Constructs a new `BulkBytes` from a `Bytes` buffer
+    #[inline]
+    pub fn new(bytes: Bytes) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the internal buffer as a byte slice
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the internal `Bytes` buffer
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+}
+
+impl Deref for BulkBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<BulkBytes> for Bytes {
+    #[inline]
+    fn from(bb: BulkBytes) -> Self {
+        bb.0
+    }
+}
+
+impl From<Bytes> for BulkBytes {
+    #[inline]
+    fn from(bytes: Bytes) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Debug for BulkBytes {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BulkBytes").field(&self.0).finish()
+    }
+}
+
+impl<'de> Deserialize<'de> for BulkBytes {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BulkBytesVisitor;
+
+        impl BulkBytesVisitor {
+            fn share_or_copy(v: &[u8]) -> BulkBytes {
+                let shared = SHARED_BYTES
+                    .with(|shared| shared.take())
+                    .filter(|shared| shared.as_ptr() == v.as_ptr() && shared.len() == v.len());
+
+                BulkBytes(shared.unwrap_or_else(|| Bytes::copy_from_slice(v)))
+            }
+        }
+
+        impl<'de> Visitor<'de> for BulkBytesVisitor {
+            type Value = BulkBytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("BulkBytes")
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_bytes(self)
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Self::share_or_copy(v))
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(Self::share_or_copy(v.as_bytes()))
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(BulkBytes(Bytes::copy_from_slice(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(BulkBytes(Bytes::copy_from_slice(v.as_bytes())))
+            }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(BulkBytes(Bytes::from(v)))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(BulkBytes(Bytes::from(v)))
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(BULK_BYTES_FAKE_NAME, BulkBytesVisitor)
+    }
+}
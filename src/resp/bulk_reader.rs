@@ -0,0 +1,90 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::io::AsyncRead;
+
+type BoxedReader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Bulk string argument streamed from an [`AsyncRead`] of known length.
+///
+/// The content is copied from the reader to the network connection while the command is written,
+/// without being buffered whole in memory.
+///
+/// The reader is consumed by the first write of the command:
+/// * commands with a `BulkReader` argument cannot be retried
+///   and cannot be sent to several nodes of a cluster,
+/// * if the reader ends before `len` bytes or fails,
+///   the command fails and the connection is closed, then reconnected.
+///
+/// The streamed content is not recorded by a
+/// [`TrafficRecorder`](crate::client::TrafficRecorder).
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::Client,
+///     commands::{FlushingMode, ServerCommands, StringCommands},
+///     resp::BulkReader,
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///     client.flushdb(FlushingMode::Sync).await?;
+///
+///     let content = vec![0u8; 1024 * 1024];
+///     let reader = std::io::Cursor::new(content);
+///     client.set("key", BulkReader::new(reader, 1024 * 1024)).await?;
+///
+///     let len = client.strlen("key").await?;
+///     assert_eq!(1024 * 1024, len);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BulkReader {
+    reader: Arc<Mutex<Option<BoxedReader>>>,
+    len: usize,
+}
+
+impl BulkReader {
+    /// Creates a bulk string argument of `len` bytes, read from `reader`
+    pub fn new(reader: impl AsyncRead + Send + 'static, len: usize) -> Self {
+        Self {
+            reader: Arc::new(Mutex::new(Some(Box::pin(reader)))),
+            len,
+        }
+    }
+
+    /// Length of the bulk string
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bulk string is empty
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Takes the reader, `None` if it has already been consumed
+    pub(crate) fn take(&self) -> Option<BoxedReader> {
+        self.reader.lock().unwrap().take()
+    }
+}
+
+impl fmt::Debug for BulkReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkReader")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
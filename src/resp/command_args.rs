@@ -1,12 +1,21 @@
 use smallvec::SmallVec;
 
-use crate::resp::ToArgs;
-use std::fmt;
+use crate::resp::{BulkReader, ToArgs};
+use bytes::Bytes;
+use std::{collections::HashMap, fmt};
 
 /// Collection of arguments of [`Command`](crate::resp::Command).
+///
+/// Arguments are stored as [`Bytes`]: arguments already stored in a `Bytes` buffer
+/// (`Bytes`, [`BulkBytes`](crate::resp::BulkBytes), `Arc<[u8]>`) are shared instead of copied.
+///
+/// Dereferences to `[Bytes]`, like [`bytes`](CommandArgs::bytes), without copy.
+/// Iterating over `&CommandArgs` yields `&[u8]`.
 #[derive(Clone, Default)]
 pub struct CommandArgs {
-    args: SmallVec<[Vec<u8>; 10]>,
+    args: SmallVec<[Bytes; 10]>,
+    /// Arguments streamed from a reader, with their index in `args`
+    readers: Vec<(usize, BulkReader)>,
 }
 
 impl CommandArgs {
@@ -47,9 +56,7 @@ impl CommandArgs {
     /// helper to build a CommandArgs in one line.
     #[inline]
     pub fn build(&mut self) -> Self {
-        let mut args = CommandArgs::default();
-        std::mem::swap(&mut args.args, &mut self.args);
-        args
+        std::mem::take(self)
     }
 
     /// Number of arguments of the collection
@@ -68,14 +75,75 @@ impl CommandArgs {
 
     #[inline]
     pub(crate) fn write_arg(&mut self, buf: &[u8]) {
-        self.args.push(buf.to_vec());
+        self.args.push(Bytes::copy_from_slice(buf));
     }
 
+    #[inline]
+    pub(crate) fn write_bytes(&mut self, bytes: Bytes) {
+        self.args.push(bytes);
+    }
+
+    /// The argument is left empty in `args`, its content is only available while writing the command
+    pub(crate) fn write_reader(&mut self, reader: BulkReader) {
+        self.readers.push((self.args.len(), reader));
+        self.args.push(Bytes::new());
+    }
+
+    /// Appends the arguments of `other`, sharing their buffers
+    pub(crate) fn extend(&mut self, other: &CommandArgs) {
+        let offset = self.args.len();
+        self.args.extend(other.args.iter().cloned());
+        self.readers.extend(
+            other
+                .readers
+                .iter()
+                .map(|(index, reader)| (offset + index, reader.clone())),
+        );
+    }
+
+    /// Arguments as [`Bytes`] buffers, shared without copy
+    ///
+    /// Streamed arguments are empty.
+    #[must_use]
+    #[inline]
+    pub fn bytes(&self) -> &[Bytes] {
+        &self.args
+    }
+
+    /// Arguments streamed from a reader, with their index
+    #[inline]
+    pub(crate) fn readers(&self) -> &[(usize, BulkReader)] {
+        &self.readers
+    }
+
+    /// Keeps the arguments for which `f` returns `true`, streamed arguments being seen as empty
     pub(crate) fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&[u8]) -> bool,
     {
-        self.args.retain(|arg| f(arg))
+        if self.readers.is_empty() {
+            self.args.retain(|arg| f(arg));
+            return;
+        }
+
+        // new index of each argument, if kept
+        let mut new_indices = Vec::with_capacity(self.args.len());
+        let mut len = 0;
+        self.args.retain(|arg| {
+            let keep = f(arg);
+            new_indices.push(keep.then_some(len));
+            len += usize::from(keep);
+            keep
+        });
+
+        self.readers
+            .retain_mut(|(index, _)| match new_indices[*index] {
+                Some(new_index) => {
+                    *index = new_index;
+                    true
+                }
+                None => false,
+            });
     }
 }
 
@@ -93,7 +161,7 @@ impl<'a> IntoIterator for &'a CommandArgs {
 
 /// [`CommandArgs`] iterator
 pub struct CommandArgsIterator<'a> {
-    iter: std::slice::Iter<'a, Vec<u8>>,
+    iter: std::slice::Iter<'a, Bytes>,
 }
 
 impl<'a> Iterator for CommandArgsIterator<'a> {
@@ -101,12 +169,12 @@ impl<'a> Iterator for CommandArgsIterator<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|v| v.as_slice())
+        self.iter.next().map(|v| v.as_ref())
     }
 }
 
 impl std::ops::Deref for CommandArgs {
-    type Target = [Vec<u8>];
+    type Target = [Bytes];
 
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -116,13 +184,23 @@ impl std::ops::Deref for CommandArgs {
 
 impl fmt::Debug for CommandArgs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let readers = self
+            .readers
+            .iter()
+            .map(|(index, reader)| (*index, reader))
+            .collect::<HashMap<_, _>>();
+
         f.debug_struct("CommandArgs")
             .field(
                 "args",
                 &self
                     .args
                     .iter()
-                    .map(|a| String::from_utf8_lossy(a.as_slice()))
+                    .enumerate()
+                    .map(|(i, a)| match readers.get(&i) {
+                        Some(reader) => format!("<{} streamed bytes>", reader.len()).into(),
+                        None => String::from_utf8_lossy(a),
+                    })
                     .collect::<Vec<_>>(),
             )
             .finish()
@@ -1,10 +1,15 @@
 use crate::{
-    resp::{Command, CommandArgs},
+    resp::{BulkReader, Command, CommandArgs},
     Error, Result,
 };
-use bytes::{BufMut, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::{collections::VecDeque, io::IoSlice};
 use tokio_util::codec::Encoder;
 
+/// Arguments from this length are written directly from their buffer
+/// instead of being copied to the write buffer
+const ZERO_COPY_MIN_LEN: usize = 16 * 1024;
+
 pub(crate) struct CommandEncoder;
 
 impl Encoder<&Command> for CommandEncoder {
@@ -12,7 +17,14 @@ impl Encoder<&Command> for CommandEncoder {
 
     #[inline]
     fn encode(&mut self, command: &Command, buf: &mut BytesMut) -> Result<()> {
-        buf.reserve(calculate_buf_size(command));
+        if !command.args.readers().is_empty() {
+            return Err(Error::Client(format!(
+                "Cannot buffer streamed argument of command {}",
+                command.name
+            )));
+        }
+
+        buf.reserve(calculate_buf_size(command, usize::MAX));
 
         buf.put_u8(b'*');
         encode_integer(command.args.len() as i64 + 1, buf);
@@ -23,8 +35,110 @@ impl Encoder<&Command> for CommandEncoder {
     }
 }
 
+/// Part of the encoded commands to write to the network
+pub(crate) enum WriteChunk {
+    Bytes(Bytes),
+    Reader(BulkReader),
+}
+
+impl CommandEncoder {
+    /// Encodes a command into `buf`, except the large and streamed arguments
+    /// which are added to `chunks` as is, after the content of `buf` encoded so far.
+    ///
+    /// The remaining content of `buf` must be added to `chunks` with [`flush_chunks`]
+    /// once all the commands have been encoded.
+    pub fn encode_chunks(
+        &mut self,
+        command: &Command,
+        buf: &mut BytesMut,
+        chunks: &mut Vec<WriteChunk>,
+    ) {
+        buf.reserve(calculate_buf_size(command, ZERO_COPY_MIN_LEN));
+
+        buf.put_u8(b'*');
+        encode_integer(command.args.len() as i64 + 1, buf);
+        encode_crlf(buf);
+        encode_bulkstring(command.name.as_bytes(), buf);
+
+        let mut readers = command.args.readers().iter().peekable();
+        for (index, arg) in command.args.bytes().iter().enumerate() {
+            if let Some((_, reader)) = readers.next_if(|(i, _)| *i == index) {
+                encode_bulkstring_header(reader.len(), buf);
+                flush_chunks(buf, chunks);
+                chunks.push(WriteChunk::Reader(reader.clone()));
+                encode_crlf(buf);
+            } else if arg.len() >= ZERO_COPY_MIN_LEN {
+                encode_bulkstring_header(arg.len(), buf);
+                flush_chunks(buf, chunks);
+                chunks.push(WriteChunk::Bytes(arg.clone()));
+                encode_crlf(buf);
+            } else {
+                encode_bulkstring(arg, buf);
+            }
+        }
+    }
+}
+
+/// Moves the content of `buf` to `chunks`
+pub(crate) fn flush_chunks(buf: &mut BytesMut, chunks: &mut Vec<WriteChunk>) {
+    if !buf.is_empty() {
+        chunks.push(WriteChunk::Bytes(buf.split().freeze()));
+    }
+}
+
+/// Consecutive [`WriteChunk::Bytes`] chunks, written with vectored writes
+#[derive(Default)]
+pub(crate) struct BytesChunks {
+    chunks: VecDeque<Bytes>,
+    remaining: usize,
+}
+
+impl BytesChunks {
+    pub fn push(&mut self, bytes: Bytes) {
+        self.remaining += bytes.len();
+        self.chunks.push_back(bytes);
+    }
+}
+
+impl Buf for BytesChunks {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.chunks.front().map(|b| b.as_ref()).unwrap_or_default()
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        self.remaining -= cnt;
+        while cnt > 0 {
+            let Some(front) = self.chunks.front_mut() else {
+                break;
+            };
+            if cnt < front.len() {
+                front.advance(cnt);
+                break;
+            }
+            cnt -= front.len();
+            self.chunks.pop_front();
+        }
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let mut count = 0;
+        for (slice, chunk) in dst.iter_mut().zip(&self.chunks) {
+            *slice = IoSlice::new(chunk);
+            count += 1;
+        }
+        count
+    }
+}
+
+/// Size of the encoded command, without the arguments from `max_arg_len`
 #[inline]
-fn calculate_buf_size(command: &Command) -> usize {
+fn calculate_buf_size(command: &Command, max_arg_len: usize) -> usize {
     let mut buf_size = 0;
 
     // *<num_args>\r\n
@@ -43,8 +157,11 @@ fn calculate_buf_size(command: &Command) -> usize {
         // $<arg_len>\r\n<arg>\r\n
         buf_size += if arg.len() <= 9 {
             6 + arg.len()
-        } else {
+        } else if arg.len() < max_arg_len {
             7 + arg.len()
+        } else {
+            // header and trailing crlf only
+            25
         };
     }
 
@@ -53,13 +170,18 @@ fn calculate_buf_size(command: &Command) -> usize {
 
 #[inline]
 fn encode_bulkstring(arg: &[u8], buf: &mut BytesMut) {
-    buf.put_u8(b'$');
-    encode_integer(arg.len() as i64, buf);
-    encode_crlf(buf);
+    encode_bulkstring_header(arg.len(), buf);
     buf.put(arg);
     encode_crlf(buf);
 }
 
+#[inline]
+fn encode_bulkstring_header(len: usize, buf: &mut BytesMut) {
+    buf.put_u8(b'$');
+    encode_integer(len as i64, buf);
+    encode_crlf(buf);
+}
+
 #[inline]
 fn encode_command_args(args: &CommandArgs, buf: &mut BytesMut) {
     for arg in args {
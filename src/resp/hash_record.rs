@@ -0,0 +1,913 @@
+use crate::{
+    resp::{BulkString, CommandArgs, KeyValueArgsCollection, KeyValueCollectionResponse, ToArgs},
+    Error, Result,
+};
+use serde::{
+    de::{
+        self, value::StrDeserializer, DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any, ser, Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    str,
+};
+
+/// Key naming convention of the hashes storing a type of records: `{prefix}:{id}`
+///
+/// # Example
+/// ```
+/// use rustis::resp::KeyPrefix;
+///
+/// struct User;
+///
+/// impl KeyPrefix for User {
+///     const PREFIX: &'static str = "user";
+/// }
+///
+/// assert_eq!("user:42", User::key(42));
+/// ```
+pub trait KeyPrefix {
+    /// Prefix of the keys
+    const PREFIX: &'static str;
+
+    /// Key of the hash storing the record with the given id
+    fn key(id: impl Display) -> String {
+        format!("{}:{id}", Self::PREFIX)
+    }
+}
+
+/// Field/value pairs of a hash, mapped from and to any struct with [serde](https://serde.rs/)
+///
+/// * Fields are named after the struct fields (or map keys).
+/// * Primitive values are encoded like command arguments: numbers in decimal,
+///   booleans as `1`/`0`, unit enum variants by their name.
+/// * `None` fields are skipped: a struct of `Option` fields
+///   can be used to update only some fields of a hash.
+/// * Nested values (sequences, maps, structs, enum variants with data) are rejected:
+///   they must be wrapped in [`Json`](crate::resp::Json), [`MsgPack`](crate::resp::MsgPack)
+///   or [`Cbor`](crate::resp::Cbor) to be stored in a single field value.
+///
+/// A `HashRecord` can be passed to [`hset`](crate::commands::HashCommands::hset)
+/// and read back from [`hgetall`](crate::commands::HashCommands::hgetall)
+/// or from the fields returned by [`hmget`](crate::commands::HashCommands::hmget).
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::Client,
+///     commands::{FlushingMode, HashCommands, ServerCommands},
+///     resp::{BulkString, HashRecord},
+///     Result,
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct User {
+///     name: String,
+///     age: u32,
+///     email: Option<String>,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct UserPatch {
+///     age: Option<u32>,
+///     email: Option<String>,
+/// }
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///     client.flushdb(FlushingMode::Sync).await?;
+///
+///     let user = User { name: "Alice".to_owned(), age: 30, email: None };
+///     client.hset("user:1", HashRecord::new(&user)?).await?;
+///
+///     // partial update
+///     let patch = UserPatch { age: Some(31), email: None };
+///     client.hset("user:1", HashRecord::new(&patch)?).await?;
+///
+///     let record: HashRecord = client.hgetall("user:1").await?;
+///     let user: User = record.to()?;
+///     assert_eq!(31, user.age);
+///
+///     // read only some fields
+///     let fields = HashRecord::field_names::<User>();
+///     let values: Vec<Option<BulkString>> = client.hmget("user:1", fields).await?;
+///     let record: HashRecord = HashRecord::from_fields(fields.iter().copied().zip(values));
+///     assert_eq!(user, record.to()?);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct HashRecord {
+    fields: Vec<(String, Vec<u8>)>,
+}
+
+impl HashRecord {
+    /// Builds a record from a struct or a map
+    pub fn new<T: Serialize + ?Sized>(value: &T) -> Result<Self> {
+        let mut fields = Vec::new();
+        value.serialize(RecordSerializer {
+            fields: &mut fields,
+        })?;
+        Ok(Self::from_pairs(fields))
+    }
+
+    /// Names of the fields of a struct, typically to pass to [`hmget`](crate::commands::HashCommands::hmget)
+    ///
+    /// Returns an empty slice if `T` is not a struct.
+    #[must_use]
+    pub fn field_names<T: Deserialize<'static>>() -> &'static [&'static str] {
+        let fields = Cell::new(&[][..]);
+        let _ = T::deserialize(FieldNamesDeserializer(&fields));
+        fields.get()
+    }
+
+    /// Builds a record from field/value pairs, skipping missing values,
+    /// typically from the fields passed to [`hmget`](crate::commands::HashCommands::hmget) and its result
+    pub fn from_fields<F: Into<String>, V: Into<Vec<u8>>>(
+        fields: impl IntoIterator<Item = (F, Option<V>)>,
+    ) -> Self {
+        Self::from_pairs(
+            fields
+                .into_iter()
+                .filter_map(|(field, value)| value.map(|value| (field.into(), value.into())))
+                .collect(),
+        )
+    }
+
+    /// Field/value pairs of the record
+    #[must_use]
+    pub fn fields(&self) -> &[(String, Vec<u8>)] {
+        &self.fields
+    }
+
+    /// Returns `true` if the record has no field
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Deserializes the record into a struct or a map
+    ///
+    /// Missing fields can be mapped to `Option` fields or to fields with a serde default.
+    pub fn to<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(RecordDeserializer {
+            fields: self.fields.iter(),
+            value: None,
+        })
+    }
+
+    fn from_pairs(fields: Vec<(String, Vec<u8>)>) -> Self {
+        Self { fields }
+    }
+}
+
+impl fmt::Debug for HashRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.fields
+                    .iter()
+                    .map(|(field, value)| (field, String::from_utf8_lossy(value))),
+            )
+            .finish()
+    }
+}
+
+impl ToArgs for HashRecord {
+    fn write_args(&self, args: &mut CommandArgs) {
+        for (field, value) in &self.fields {
+            args.arg(field).arg(value.as_slice());
+        }
+    }
+
+    fn num_args(&self) -> usize {
+        self.fields.len() * 2
+    }
+}
+
+impl KeyValueArgsCollection<String, Vec<u8>> for HashRecord {}
+
+impl<'de> Deserialize<'de> for HashRecord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct RecordVisitor;
+
+        impl<'de> Visitor<'de> for RecordVisitor {
+            type Value = HashRecord;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("HashRecord")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some((field, value)) = map.next_entry::<String, BulkString>()? {
+                    fields.push((field, value.into()));
+                }
+                Ok(HashRecord::from_pairs(fields))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                // RESP2 replies are flat arrays of fields and values
+                let mut fields = Vec::with_capacity(seq.size_hint().unwrap_or_default() / 2);
+                while let Some(field) = seq.next_element::<String>()? {
+                    let Some(value) = seq.next_element::<BulkString>()? else {
+                        return Err(de::Error::invalid_length(fields.len() * 2 + 1, &self));
+                    };
+                    fields.push((field, value.into()));
+                }
+                Ok(HashRecord::from_pairs(fields))
+            }
+        }
+
+        deserializer.deserialize_map(RecordVisitor)
+    }
+}
+
+impl KeyValueCollectionResponse<String, BulkString> for HashRecord {}
+
+/// Encodes a primitive value like a command argument
+fn to_arg(value: impl ToArgs) -> Vec<u8> {
+    let mut args = CommandArgs::default();
+    value.write_args(&mut args);
+    args.into_iter().next().unwrap_or_default().to_vec()
+}
+
+struct RecordSerializer<'a> {
+    fields: &'a mut Vec<(String, Vec<u8>)>,
+}
+
+impl RecordSerializer<'_> {
+    fn push<T: Serialize + ?Sized>(&mut self, field: String, value: &T) -> Result<()> {
+        let value = match value.serialize(FieldSerializer) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(()),
+            Err(FieldError::Nested) => {
+                return Err(Error::Client(format!(
+                    "Cannot store nested value in hash field `{field}`: \
+                    wrap it in `Json`, `MsgPack` or `Cbor`"
+                )))
+            }
+            Err(FieldError::Custom(e)) => return Err(Error::Client(e)),
+        };
+        self.fields.push((field, value));
+        Ok(())
+    }
+
+    fn unsupported() -> Error {
+        Error::Client("A hash record can only be built from a struct or a map".to_owned())
+    }
+}
+
+impl<'a> Serializer for RecordSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = RecordMapSerializer<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(RecordMapSerializer {
+            record: self,
+            field: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(Self::unsupported())
+    }
+}
+
+impl ser::SerializeStruct for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct RecordMapSerializer<'a> {
+    record: RecordSerializer<'a>,
+    field: Option<String>,
+}
+
+impl ser::SerializeMap for RecordMapSerializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let field = match key.serialize(FieldSerializer) {
+            Ok(Some(field)) => String::from_utf8(field)?,
+            _ => {
+                return Err(Error::Client(
+                    "Hash record fields must be strings or numbers".to_owned(),
+                ))
+            }
+        };
+        self.field = Some(field);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let Some(field) = self.field.take() else {
+            return Err(Error::Client("Missing hash record field".to_owned()));
+        };
+        self.record.push(field, value)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Error of the [`FieldSerializer`]
+#[derive(Debug)]
+enum FieldError {
+    /// The value cannot be stored in a single field value
+    Nested,
+    Custom(String),
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Nested => f.write_str("nested value"),
+            FieldError::Custom(e) => f.write_str(e),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl ser::Error for FieldError {
+    fn custom<T: Display>(msg: T) -> Self {
+        FieldError::Custom(msg.to_string())
+    }
+}
+
+/// Serializes a primitive field value, `None` if the field must be skipped
+struct FieldSerializer;
+
+type FieldResult = std::result::Result<Option<Vec<u8>>, FieldError>;
+
+impl Serializer for FieldSerializer {
+    type Ok = Option<Vec<u8>>;
+    type Error = FieldError;
+    type SerializeSeq = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeTuple = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeTupleStruct = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeTupleVariant = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeMap = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeStruct = ser::Impossible<Self::Ok, FieldError>;
+    type SerializeStructVariant = ser::Impossible<Self::Ok, FieldError>;
+
+    fn serialize_bool(self, v: bool) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> FieldResult {
+        Ok(Some(to_arg(u16::from(v))))
+    }
+
+    fn serialize_u16(self, v: u16) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_u32(self, v: u32) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> FieldResult {
+        Ok(Some(to_arg(v)))
+    }
+
+    fn serialize_char(self, v: char) -> FieldResult {
+        Ok(Some(v.to_string().into_bytes()))
+    }
+
+    fn serialize_str(self, v: &str) -> FieldResult {
+        Ok(Some(v.as_bytes().to_vec()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> FieldResult {
+        Ok(Some(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> FieldResult {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> FieldResult {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> FieldResult {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> FieldResult {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> FieldResult {
+        Ok(Some(variant.as_bytes().to_vec()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> FieldResult {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> FieldResult {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeSeq, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> std::result::Result<Self::SerializeTuple, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleStruct, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleVariant, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeMap, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStruct, FieldError> {
+        Err(FieldError::Nested)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStructVariant, FieldError> {
+        Err(FieldError::Nested)
+    }
+}
+
+struct RecordDeserializer<'a> {
+    fields: std::slice::Iter<'a, (String, Vec<u8>)>,
+    value: Option<&'a [u8]>,
+}
+
+impl<'de, 'a: 'de> Deserializer<'de> for RecordDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a: 'de> MapAccess<'de> for RecordDeserializer<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some((field, value)) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        let field: StrDeserializer<'_, Error> = field.as_str().into_deserializer();
+        seed.deserialize(field).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let Some(value) = self.value.take() else {
+            return Err(Error::Client("Missing hash record value".to_owned()));
+        };
+        seed.deserialize(FieldDeserializer { value })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+/// Deserializes a field value encoded by the [`FieldSerializer`]
+struct FieldDeserializer<'de> {
+    value: &'de [u8],
+}
+
+impl<'de> FieldDeserializer<'de> {
+    fn as_str(&self) -> Result<&'de str> {
+        Ok(str::from_utf8(self.value)?)
+    }
+
+    fn parse<T: str::FromStr>(&self) -> Result<T> {
+        self.as_str()?.parse::<T>().map_err(|_| {
+            Error::Client(format!(
+                "Cannot parse hash field value `{}`",
+                String::from_utf8_lossy(self.value)
+            ))
+        })
+    }
+
+    fn nested(&self) -> Error {
+        Error::Client(format!(
+            "Cannot read nested value from hash field value `{}`: \
+            wrap it in `Json`, `MsgPack` or `Cbor`",
+            String::from_utf8_lossy(self.value)
+        ))
+    }
+}
+
+macro_rules! deserialize_number {
+    ($deserialize:ident, $visit:ident) => {
+        fn $deserialize<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            visitor.$visit(self.parse()?)
+        }
+    };
+}
+
+impl<'de> Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match str::from_utf8(self.value) {
+            Ok(str) => visitor.visit_borrowed_str(str),
+            Err(_) => visitor.visit_borrowed_bytes(self.value),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            b"1" | b"true" => visitor.visit_bool(true),
+            b"0" | b"false" => visitor.visit_bool(false),
+            _ => Err(Error::Client(format!(
+                "Cannot parse hash field value `{}` as bool",
+                String::from_utf8_lossy(self.value)
+            ))),
+        }
+    }
+
+    deserialize_number!(deserialize_i8, visit_i8);
+    deserialize_number!(deserialize_i16, visit_i16);
+    deserialize_number!(deserialize_i32, visit_i32);
+    deserialize_number!(deserialize_i64, visit_i64);
+    deserialize_number!(deserialize_u8, visit_u8);
+    deserialize_number!(deserialize_u16, visit_u16);
+    deserialize_number!(deserialize_u32, visit_u32);
+    deserialize_number!(deserialize_u64, visit_u64);
+    deserialize_number!(deserialize_f32, visit_f32);
+    deserialize_number!(deserialize_f64, visit_f64);
+    deserialize_number!(deserialize_char, visit_char);
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.as_str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_bytes(self.value)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.value.to_vec())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // missing fields are skipped by the record
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(self.nested())
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value> {
+        Err(self.nested())
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value> {
+        Err(self.nested())
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(self.nested())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value> {
+        Err(self.nested())
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        // only unit variants are stored, by their name
+        match str::from_utf8(self.value) {
+            Ok(variant)
+                if variant
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'_') =>
+            {
+                visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(variant))
+            }
+            _ => Err(self.nested()),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// Captures the field names of a struct, without deserializing anything
+struct FieldNamesDeserializer<'a>(&'a Cell<&'static [&'static str]>);
+
+impl<'de, 'a> Deserializer<'de> for FieldNamesDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::Client("Not a struct".to_owned()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value> {
+        self.0.set(fields);
+        Err(Error::Client("Field names captured".to_owned()))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
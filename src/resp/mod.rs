@@ -34,7 +34,10 @@ Current implementation provides the following conversions:
 * `f32`, `f64`,
 * `bool`,
 * `String`, `&String`, `char`, `&str`, [`BulkString`], `Vec<u8>`, `&[u8; N]`, `[u8; N]`, `&[u8]`
+* `Bytes`, [`BulkBytes`], `Arc<[u8]>`, shared without copy
+* [`BulkReader`], streamed from an `AsyncRead`
 * `Option<T>` where `T: SingleArg`
+* `Json<T>`, `MsgPack<T>`, `Cbor<T>` and `Compressed<C, T>` (features `json`, `msgpack`, `cbor`, `lz4` and `zstd`)
 * `(T, U)`
 * `(T, U, V)`
 * `Vec<T>`
@@ -62,7 +65,10 @@ Current implementation provides the following conversions:
 * `f32`, `f64`,
 * `bool`,
 * `String`, `&String`, `char`, `&str`, [`BulkString`], `Vec<u8>`, `&[u8; N]`, `[u8; N]`, `&[u8]`
+* `Bytes`, [`BulkBytes`], `Arc<[u8]>`, shared without copy
+* [`BulkReader`], streamed from an `AsyncRead`
 * `Option<T>` where `T: SingleArg`
+* `Json<T>`, `MsgPack<T>`, `Cbor<T>` and `Compressed<C, T>` (features `json`, `msgpack`, `cbor`, `lz4` and `zstd`)
 
 #### Example
 ```
@@ -292,7 +298,9 @@ Current implementation provides the following deserializations from a RESP Buffe
 * `bool`,
 * `String`,
 * [`BulkString`],
+* [`BulkBytes`],
 * `Option<T>`
+* `Json<T>`, `MsgPack<T>`, `Cbor<T>` and `Compressed<C, T>` (features `json`, `msgpack`, `cbor`, `lz4` and `zstd`)
 
 #### Example
 ```
@@ -433,33 +441,61 @@ async fn main() -> Result<()> {
 */
 
 mod buffer_decoder;
+mod bulk_bytes;
+mod bulk_reader;
 mod bulk_string;
 mod command;
 mod command_args;
 mod command_encoder;
+mod hash_record;
 mod resp_batch_deserializer;
 mod resp_buf;
 mod resp_deserializer;
 mod resp_serializer;
 mod response;
+mod server_codec;
 mod to_args;
+#[cfg(any(
+    feature = "json",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "lz4",
+    feature = "zstd"
+))]
+mod typed_value;
 mod util;
 mod value;
 mod value_deserialize;
 mod value_deserializer;
 mod value_serialize;
 
+#[cfg(not(feature = "bench-internals"))]
 pub(crate) use buffer_decoder::*;
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub use buffer_decoder::*;
+pub use bulk_bytes::*;
+pub use bulk_reader::*;
 pub use bulk_string::*;
 pub use command::*;
 pub use command_args::*;
 pub(crate) use command_encoder::*;
+pub use hash_record::*;
 pub(crate) use resp_batch_deserializer::*;
 pub use resp_buf::*;
 pub use resp_deserializer::*;
 pub use resp_serializer::*;
 pub use response::*;
+pub use server_codec::*;
 pub use to_args::*;
+#[cfg(any(
+    feature = "json",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "lz4",
+    feature = "zstd"
+))]
+pub use typed_value::*;
 pub use util::*;
 pub use value::*;
 pub(crate) use value_deserialize::*;
@@ -1,7 +1,4 @@
-use crate::{
-    resp::{RespBuf, RespDeserializer},
-    Error, Result,
-};
+use crate::{resp::RespBuf, Error, Result};
 use serde::{de::DeserializeSeed, forward_to_deserialize_any, Deserializer};
 use std::slice;
 
@@ -72,7 +69,7 @@ impl<'de> serde::de::SeqAccess<'de> for SeqAccess<'de> {
         T: DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(buf) => seed.deserialize(&mut RespDeserializer::new(buf)).map(Some),
+            Some(buf) => seed.deserialize(&mut buf.deserializer()).map(Some),
             None => Ok(None),
         }
     }
@@ -62,10 +62,29 @@ impl RespBuf {
     }
 
     /// Convert the RESP Buffer to a Rust type `T` by using serde deserialization
+    ///
+    /// `T` can borrow from the buffer (e.g. `&str`, `&[u8]` or a struct with borrowed fields),
+    /// and [`BulkBytes`](crate::resp::BulkBytes) share the buffer instead of copying it.
     #[inline]
     pub fn to<'de, T: Deserialize<'de>>(&'de self) -> Result<T> {
+        T::deserialize(&mut self.deserializer())
+    }
+
+    /// Deserializer of the RESP Buffer,
+    /// shared instead of copied by the deserialized [`BulkBytes`](crate::resp::BulkBytes)
+    #[inline]
+    pub(crate) fn deserializer(&self) -> RespDeserializer<'_> {
+        RespDeserializer::from_bytes(&self.0)
+    }
+
+    /// Deserialize the [attributes](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md#attribute-type)
+    /// preceding the reply, if any
+    ///
+    /// Attributes are ignored by [`to`](RespBuf::to).
+    #[inline]
+    pub fn attributes<'de, A: Deserialize<'de>>(&'de self) -> Result<Option<A>> {
         let mut deserializer = RespDeserializer::new(&self.0);
-        T::deserialize(&mut deserializer)
+        deserializer.attributes()
     }
 
     /// Returns the internal buffer as a byte slice
@@ -74,6 +93,13 @@ impl RespBuf {
         &self.0
     }
 
+    /// Constructs a new `RespBuf` as a RESP3 push message
+    /// invalidating all the keys tracked by the client (>2\r\n$10\r\ninvalidate\r\n_\r\n)
+    #[inline]
+    pub(crate) fn invalidate_all() -> RespBuf {
+        RespBuf(Bytes::from_static(b">2\r\n$10\r\ninvalidate\r\n_\r\n"))
+    }
+
     /// Constructs a new `RespBuf` as a RESP Ok message (+OK\r\n)
     #[inline]
     pub fn ok() -> RespBuf {
@@ -1,8 +1,18 @@
-use crate::{resp::PUSH_FAKE_FIELD, Error, RedisError, Result};
+use crate::{
+    resp::{
+        visit_shared_bytes, ATTRIBUTE_FAKE_FIELD, BIG_NUMBER_FAKE_FIELD, BULK_BYTES_FAKE_NAME,
+        PUSH_FAKE_FIELD, VALUE_FAKE_NAME,
+    },
+    Error, RedisError, Result,
+};
+use bytes::Bytes;
 use memchr::memchr;
 use serde::{
-    de::{DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor},
-    forward_to_deserialize_any, Deserializer,
+    de::{
+        value::MapDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
 };
 use std::str::{self, FromStr};
 
@@ -19,6 +29,12 @@ pub(crate) const BOOL_TAG: u8 = b'#';
 pub(crate) const VERBATIM_STRING_TAG: u8 = b'=';
 pub(crate) const PUSH_TAG: u8 = b'>';
 pub(crate) const BLOB_ERROR_TAG: u8 = b'!';
+pub(crate) const BIG_NUMBER_TAG: u8 = b'(';
+pub(crate) const ATTRIBUTE_TAG: u8 = b'|';
+pub(crate) const STREAMED_STRING_CHUNK_TAG: u8 = b';';
+pub(crate) const STREAMED_AGGREGATE_END_TAG: u8 = b'.';
+/// Length marker of streamed strings and aggregates (e.g. `$?` or `*?`)
+pub(crate) const STREAMED_LENGTH: u8 = b'?';
 
 #[inline(always)]
 fn eof<T>() -> Result<T> {
@@ -26,10 +42,27 @@ fn eof<T>() -> Result<T> {
 }
 
 /// Serde deserializer for [`RESP3`](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md)
+///
+/// [Attributes](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md#attribute-type)
+/// are skipped, unless [`keep_attributes`](RespDeserializer::keep_attributes) is set
+/// and a [`Value`](crate::resp::Value) is deserialized,
+/// or they are explicitly read with [`attributes`](RespDeserializer::attributes).
+///
+/// [Big numbers](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md#big-number-type)
+/// can be deserialized to a string, a [`Value`](crate::resp::Value),
+/// or to a number when they fit in it without loss of precision.
+///
+/// Streamed strings and aggregates are only skipped:
+/// they are converted to regular strings and aggregates when read from the network.
 pub struct RespDeserializer<'de> {
     buf: &'de [u8],
+    /// reference-counted buffer of `buf`, if any, shared by [`BulkBytes`](crate::resp::BulkBytes)
+    source: Option<&'de Bytes>,
     pos: usize,
     eat_error: bool,
+    keep_attributes: bool,
+    /// the next value is deserialized as a [`Value`](crate::resp::Value)
+    as_value: bool,
 }
 
 impl<'de> RespDeserializer<'de> {
@@ -38,20 +71,59 @@ impl<'de> RespDeserializer<'de> {
     pub fn new(buf: &'de [u8]) -> Self {
         RespDeserializer {
             buf,
+            source: None,
             pos: 0,
             eat_error: true,
+            keep_attributes: false,
+            as_value: false,
         }
     }
 
+    /// Creates a new `RespDeserializer` on a reference-counted buffer,
+    /// shared instead of copied by the deserialized [`BulkBytes`](crate::resp::BulkBytes)
+    #[inline]
+    pub(crate) fn from_bytes(source: &'de Bytes) -> Self {
+        RespDeserializer {
+            source: Some(source),
+            ..Self::new(source)
+        }
+    }
+
+    /// Keep the attributes when deserializing a [`Value`](crate::resp::Value),
+    /// as [`Value::Attribute`](crate::resp::Value::Attribute)
+    #[must_use]
+    pub fn keep_attributes(mut self) -> Self {
+        self.keep_attributes = true;
+        self
+    }
+
     /// Get current position in the input byte buffer
     #[inline]
     pub fn get_pos(&self) -> usize {
         self.pos
     }
 
+    /// Deserialize the attributes preceding the next value, if any
+    ///
+    /// # Errors
+    /// Any parsing error of the attributes
+    pub fn attributes<A: Deserialize<'de>>(&mut self) -> Result<Option<A>> {
+        if self.buf.get(self.pos) != Some(&ATTRIBUTE_TAG) {
+            return Ok(None);
+        }
+
+        self.advance();
+        let len = self.parse_integer()?;
+        A::deserialize(AttributesDeserializer { de: self, len }).map(Some)
+    }
+
     // Look at the first byte in the input without consuming it.
     #[inline]
     fn peek(&mut self) -> Result<u8> {
+        while self.buf.get(self.pos) == Some(&ATTRIBUTE_TAG) {
+            self.ignore_attributes()?;
+        }
+
         if let Some(&byte) = self.buf.get(self.pos) {
             if self.eat_error {
                 match byte {
@@ -233,8 +305,17 @@ impl<'de> RespDeserializer<'de> {
         T: atoi::FromRadix10SignedChecked + Default,
     {
         match self.next()? {
-            INTEGER_TAG => self.parse_integer::<T>(),
-            DOUBLE_TAG => self.parse_integer::<T>(),
+            INTEGER_TAG | DOUBLE_TAG => self.parse_integer::<T>(),
+            BIG_NUMBER_TAG => {
+                let big_number = self.next_line()?;
+                atoi::atoi(big_number).ok_or_else(|| {
+                    Error::Client(format!(
+                        "Big number {} does not fit in {}",
+                        String::from_utf8_lossy(big_number),
+                        std::any::type_name::<T>()
+                    ))
+                })
+            }
             NIL_TAG => {
                 self.parse_nil()?;
                 Ok(Default::default())
@@ -278,10 +359,26 @@ impl<'de> RespDeserializer<'de> {
     #[inline]
     fn parse_float_ex<T>(&mut self) -> Result<T>
     where
-        T: fast_float::FastFloat + Default,
+        T: fast_float::FastFloat + Default + Into<f64>,
     {
         match self.next()? {
             INTEGER_TAG | DOUBLE_TAG => self.parse_float::<T>(),
+            BIG_NUMBER_TAG => {
+                let big_number = self.next_line()?;
+                // only integers exactly represented by the floating point type are accepted
+                atoi::atoi::<i128>(big_number)
+                    .and_then(|integer| {
+                        let float = fast_float::parse::<T, _>(big_number).ok()?;
+                        (float.into() as i128 == integer).then_some(float)
+                    })
+                    .ok_or_else(|| {
+                        Error::Client(format!(
+                            "Big number {} cannot be converted to {} without losing precision",
+                            String::from_utf8_lossy(big_number),
+                            std::any::type_name::<T>()
+                        ))
+                    })
+            }
             NIL_TAG => {
                 self.parse_nil()?;
                 Ok(Default::default())
@@ -349,14 +446,78 @@ impl<'de> RespDeserializer<'de> {
         }
     }
 
+    #[inline]
+    fn is_streamed(&self) -> bool {
+        self.buf.get(self.pos) == Some(&STREAMED_LENGTH)
+    }
+
+    /// Ignore the chunks of a streamed string (`$?`) up to the empty chunk (`;0`)
+    fn ignore_streamed_string(&mut self) -> Result<()> {
+        self.ignore_line()?;
+        loop {
+            match self.buf.get(self.pos) {
+                Some(&STREAMED_STRING_CHUNK_TAG) => {
+                    self.advance();
+                    // the last chunk (;0) has no data
+                    if self.peek_line()? == b"0" {
+                        return self.ignore_line();
+                    }
+                    self.ignore_bulk_string()?;
+                }
+                Some(_) => {
+                    return Err(Error::Client(
+                        "Expected chunk in streamed string".to_owned(),
+                    ))
+                }
+                None => return eof(),
+            }
+        }
+    }
+
+    /// Ignore the elements of a streamed aggregate (e.g. `*?`) up to the end marker (`.`)
+    fn ignore_streamed_aggregate(&mut self) -> Result<()> {
+        self.ignore_line()?;
+        loop {
+            match self.buf.get(self.pos) {
+                Some(&STREAMED_AGGREGATE_END_TAG) => {
+                    self.advance();
+                    return self.parse_nil();
+                }
+                Some(_) => self.ignore_value()?,
+                None => return eof(),
+            }
+        }
+    }
+
+    /// Ignore the attributes (`|`) preceding a value
+    fn ignore_attributes(&mut self) -> Result<()> {
+        let eat_error = self.eat_error;
+        self.advance();
+        let result = if self.is_streamed() {
+            self.ignore_streamed_aggregate()
+        } else {
+            self.parse_integer::<usize>().and_then(|len| {
+                for _ in 0..len * 2 {
+                    self.ignore_value()?;
+                }
+                Ok(())
+            })
+        };
+        self.eat_error = eat_error;
+        result
+    }
+
     #[inline]
     fn ignore_value(&mut self) -> Result<()> {
         self.eat_error = false;
         match self.next()? {
-            SIMPLE_STRING_TAG | ERROR_TAG | INTEGER_TAG | DOUBLE_TAG | NIL_TAG | BOOL_TAG => {
-                self.ignore_line()
-            }
+            SIMPLE_STRING_TAG | ERROR_TAG | INTEGER_TAG | DOUBLE_TAG | NIL_TAG | BOOL_TAG
+            | BIG_NUMBER_TAG => self.ignore_line(),
+            BULK_STRING_TAG if self.is_streamed() => self.ignore_streamed_string(),
             BULK_STRING_TAG | BLOB_ERROR_TAG | VERBATIM_STRING_TAG => self.ignore_bulk_string(),
+            ARRAY_TAG | SET_TAG | PUSH_TAG | MAP_TAG if self.is_streamed() => {
+                self.ignore_streamed_aggregate()
+            }
             ARRAY_TAG | SET_TAG | PUSH_TAG => {
                 let len = self.parse_integer::<usize>()?;
                 for _ in 0..len {
@@ -394,9 +555,33 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        let as_value = std::mem::take(&mut self.as_value);
+
+        if self.keep_attributes && self.buf.get(self.pos) == Some(&ATTRIBUTE_TAG) {
+            self.advance();
+            let len = self.parse_integer()?;
+            return visitor.visit_map(AttributeMapAccess {
+                de: self,
+                len,
+                visited: false,
+            });
+        }
+
         let first_byte = self.peek()?;
 
         match first_byte {
+            BIG_NUMBER_TAG => {
+                self.advance();
+                let big_number = self.parse_string()?;
+                if as_value {
+                    visitor.visit_map(MapDeserializer::new(std::iter::once((
+                        BIG_NUMBER_FAKE_FIELD,
+                        big_number,
+                    ))))
+                } else {
+                    visitor.visit_borrowed_str(big_number)
+                }
+            }
             BULK_STRING_TAG => self.deserialize_bytes(visitor),
             ARRAY_TAG => self.deserialize_seq(visitor),
             MAP_TAG => self.deserialize_map(visitor),
@@ -510,6 +695,22 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
         visitor.visit_u64(self.parse_integer_ex()?)
     }
 
+    #[inline]
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i128(self.parse_integer_ex()?)
+    }
+
+    #[inline]
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u128(self.parse_integer_ex()?)
+    }
+
     #[inline]
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
     where
@@ -573,7 +774,7 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
                 let bs = self.parse_verbatim_string()?;
                 str::from_utf8(bs)?
             }
-            SIMPLE_STRING_TAG => self.parse_string()?,
+            SIMPLE_STRING_TAG | BIG_NUMBER_TAG => self.parse_string()?,
             NIL_TAG => {
                 self.parse_nil()?;
                 ""
@@ -609,7 +810,7 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
                 self.parse_nil()?;
                 String::from("")
             }
-            SIMPLE_STRING_TAG => self.parse_string()?.to_owned(),
+            SIMPLE_STRING_TAG | BIG_NUMBER_TAG => self.parse_string()?.to_owned(),
             ERROR_TAG => return Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => return Err(Error::Redis(self.parse_blob_error()?)),
             _ => {
@@ -634,7 +835,7 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
                 self.parse_nil()?;
                 &[]
             }
-            SIMPLE_STRING_TAG => self.parse_string()?.as_bytes(),
+            SIMPLE_STRING_TAG | BIG_NUMBER_TAG => self.parse_string()?.as_bytes(),
             ERROR_TAG => return Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => return Err(Error::Redis(self.parse_blob_error()?)),
             _ => return Err(Error::Client("Cannot parse to bytes".to_owned())),
@@ -654,7 +855,7 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
                 self.parse_nil()?;
                 vec![]
             }
-            SIMPLE_STRING_TAG => self.parse_string()?.as_bytes().to_vec(),
+            SIMPLE_STRING_TAG | BIG_NUMBER_TAG => self.parse_string()?.as_bytes().to_vec(),
             ERROR_TAG => return Err(Error::Redis(self.parse_error()?)),
             BLOB_ERROR_TAG => return Err(Error::Redis(self.parse_blob_error()?)),
             _ => return Err(Error::Client("Cannot parse to byte buffer".to_owned())),
@@ -717,10 +918,36 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
     // insignificant wrappers around the data they contain. That means not
     // parsing anything other than the contained value.
     #[inline]
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
+        if name == VALUE_FAKE_NAME {
+            self.as_value = true;
+            return self.deserialize_any(visitor);
+        }
+
+        if name == BULK_BYTES_FAKE_NAME {
+            if let Some(source) = self.source {
+                let slice = match self.peek()? {
+                    BULK_STRING_TAG => {
+                        self.advance();
+                        self.parse_bulk_string()?
+                    }
+                    VERBATIM_STRING_TAG => {
+                        self.advance();
+                        self.parse_verbatim_string()?
+                    }
+                    SIMPLE_STRING_TAG => {
+                        self.advance();
+                        self.parse_string()?.as_bytes()
+                    }
+                    _ => return self.deserialize_bytes(visitor),
+                };
+                return visit_shared_bytes(source.slice_ref(slice), slice, visitor);
+            }
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -807,7 +1034,7 @@ impl<'de> Deserializer<'de> for &mut RespDeserializer<'de> {
             Ok(if array_len > fields.len() {
                 true
             } else if let Some(s) = de.peek_string()? {
-                fields.iter().any(|f| s == *f)
+                fields.contains(&s)
             } else {
                 false
             })
@@ -1233,6 +1460,127 @@ impl<'de> Deserializer<'de> for PushDeserializer<'de, '_> {
     }
 }
 
+/// Map with a single fake field giving access to the attributes and the value they precede
+struct AttributeMapAccess<'de, 'a> {
+    de: &'a mut RespDeserializer<'de>,
+    len: usize,
+    visited: bool,
+}
+
+impl<'de> serde::de::MapAccess<'de> for AttributeMapAccess<'de, '_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.visited {
+            return Ok(None);
+        }
+
+        self.visited = true;
+        seed.deserialize(ATTRIBUTE_FAKE_FIELD.into_deserializer())
+            .map(Some)
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(AttributeDeserializer {
+            de: self.de,
+            len: self.len,
+        })
+    }
+}
+
+/// Deserialize a pair of attributes and the value they precede
+struct AttributeDeserializer<'de, 'a> {
+    de: &'a mut RespDeserializer<'de>,
+    len: usize,
+}
+
+impl<'de> Deserializer<'de> for AttributeDeserializer<'de, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        struct AttributeSeqAccess<'de, 'a> {
+            de: &'a mut RespDeserializer<'de>,
+            len: usize,
+            idx: usize,
+        }
+
+        impl<'de> serde::de::SeqAccess<'de> for AttributeSeqAccess<'de, '_> {
+            type Error = Error;
+
+            fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+            where
+                T: DeserializeSeed<'de>,
+            {
+                self.idx += 1;
+                match self.idx {
+                    1 => seed
+                        .deserialize(AttributesDeserializer {
+                            de: self.de,
+                            len: self.len,
+                        })
+                        .map(Some),
+                    2 => seed.deserialize(&mut *self.de).map(Some),
+                    _ => Ok(None),
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> Option<usize> {
+                Some(2 - self.idx.min(2))
+            }
+        }
+
+        visitor.visit_seq(AttributeSeqAccess {
+            de: self.de,
+            len: self.len,
+            idx: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bytes byte_buf map struct option unit newtype_struct
+        ignored_any unit_struct tuple_struct tuple enum identifier
+    }
+}
+
+/// Deserialize the map of attributes, whose header has already been parsed
+struct AttributesDeserializer<'de, 'a> {
+    de: &'a mut RespDeserializer<'de>,
+    len: usize,
+}
+
+impl<'de> Deserializer<'de> for AttributesDeserializer<'de, '_> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(MapAccess {
+            de: self.de,
+            len: self.len,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bytes byte_buf map struct option unit newtype_struct
+        ignored_any unit_struct tuple_struct tuple enum identifier
+    }
+}
+
 /// An iterator over a RESP Array in byte slices
 ///
 /// # See
@@ -1,7 +1,8 @@
 use crate::{
     resp::{
-        ARRAY_TAG, BULK_STRING_TAG, DOUBLE_TAG, ERROR_TAG, INTEGER_TAG, MAP_TAG, PUSH_FAKE_FIELD,
-        PUSH_TAG, SET_TAG, SIMPLE_STRING_TAG,
+        ARRAY_TAG, ATTRIBUTE_FAKE_FIELD, ATTRIBUTE_TAG, BIG_NUMBER_FAKE_FIELD, BIG_NUMBER_TAG,
+        BULK_STRING_TAG, DOUBLE_TAG, ERROR_TAG, INTEGER_TAG, MAP_TAG, PUSH_FAKE_FIELD, PUSH_TAG,
+        SET_TAG, SIMPLE_STRING_TAG, STREAMED_STRING_CHUNK_TAG,
     },
     Error,
 };
@@ -20,9 +21,22 @@ pub(crate) const SET_FAKE_FIELD: &str = "~~~SET~~~";
 pub(crate) const ERROR_FAKE_FIELD: &str = "---ERROR---";
 
 /// Serde serializer for [`RESP3`](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md)
+///
+/// Sequences and maps of unknown length are serialized
+/// as [streamed aggregates](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md#streamed-aggregate-data-types).
+///
+/// A serializer created with [`new_resp2`](RespSerializer::new_resp2) writes
+/// [RESP2](https://redis.io/docs/reference/protocol-spec/) instead.
 pub struct RespSerializer {
     output: BytesMut,
+    resp2: bool,
     is_error: bool,
+    is_big_number: bool,
+    is_attribute: bool,
+    /// for each sequence or map being serialized, `true` if it is streamed
+    streamed: Vec<bool>,
+    /// RESP2 has no attributes: output position and nesting level of the attributes to discard
+    discarded_attributes: Option<(usize, usize)>,
 }
 
 impl RespSerializer {
@@ -30,7 +44,29 @@ impl RespSerializer {
     pub fn new() -> Self {
         Self {
             output: BytesMut::new(),
+            resp2: false,
             is_error: false,
+            is_big_number: false,
+            is_attribute: false,
+            streamed: Vec::new(),
+            discarded_attributes: None,
+        }
+    }
+
+    /// Creates a new `RespSerializer` writing RESP2
+    ///
+    /// RESP3 types are converted to their RESP2 counterparts:
+    /// * maps to arrays of keys and values,
+    /// * sets and push messages to arrays,
+    /// * nulls to null bulk strings,
+    /// * booleans to integers,
+    /// * doubles and big numbers to bulk strings.
+    ///
+    /// Attributes are skipped.
+    pub fn new_resp2() -> Self {
+        Self {
+            resp2: true,
+            ..Self::new()
         }
     }
 
@@ -40,6 +76,59 @@ impl RespSerializer {
         self.output
     }
 
+    /// Serialize chunks of bytes as a
+    /// [streamed string](https://github.com/redis/redis-specifications/blob/master/protocol/RESP3.md#streamed-strings)
+    ///
+    /// Empty chunks are skipped since an empty chunk marks the end of the string.
+    pub fn serialize_streamed_bytes<'a>(&mut self, chunks: impl IntoIterator<Item = &'a [u8]>) {
+        self.output.put_u8(BULK_STRING_TAG);
+        self.output.put_slice(b"?\r\n");
+        for chunk in chunks.into_iter().filter(|c| !c.is_empty()) {
+            self.output.put_u8(STREAMED_STRING_CHUNK_TAG);
+            self.serialize_raw_integer(chunk.len());
+            self.output.put_slice(chunk);
+            self.output.put_slice(b"\r\n");
+        }
+        self.output.put_slice(b";0\r\n");
+    }
+
+    fn serialize_aggregate_header(&mut self, tag: u8, len: Option<usize>) {
+        self.output.put_u8(tag);
+        match len {
+            Some(len) => self.serialize_raw_integer(len),
+            None => self.output.put_slice(b"?\r\n"),
+        }
+    }
+
+    /// Header of a map (or attributes) of `len` entries
+    fn serialize_map_header(&mut self, len: Option<usize>) {
+        if self.resp2 {
+            if self.is_attribute {
+                self.is_attribute = false;
+                self.discarded_attributes = Some((self.output.len(), self.streamed.len()));
+            }
+            self.serialize_aggregate_header(ARRAY_TAG, len.map(|len| len * 2));
+        } else if self.is_attribute {
+            self.is_attribute = false;
+            self.serialize_aggregate_header(ATTRIBUTE_TAG, len);
+        } else {
+            self.serialize_aggregate_header(MAP_TAG, len);
+        }
+    }
+
+    fn serialize_bulk_string(&mut self, v: &[u8]) {
+        self.output.put_u8(BULK_STRING_TAG);
+        self.serialize_raw_integer(v.len());
+        self.output.put_slice(v);
+        self.output.put_slice(b"\r\n");
+    }
+
+    fn end_streamed(&mut self) {
+        if let Some(true) = self.streamed.pop() {
+            self.output.put_slice(b".\r\n");
+        }
+    }
+
     fn serialize_raw_integer<I: Integer>(&mut self, i: I) {
         let mut temp = itoa::Buffer::new();
         let str = temp.format(i);
@@ -55,9 +144,13 @@ impl RespSerializer {
     fn serialize_float<F: Float>(&mut self, f: F) {
         let mut temp = dtoa::Buffer::new();
         let str = temp.format(f);
-        self.output.put_u8(DOUBLE_TAG);
-        self.output.put_slice(str.as_bytes());
-        self.output.put_slice(b"\r\n");
+        if self.resp2 {
+            self.serialize_bulk_string(str.as_bytes());
+        } else {
+            self.output.put_u8(DOUBLE_TAG);
+            self.output.put_slice(str.as_bytes());
+            self.output.put_slice(b"\r\n");
+        }
     }
 }
 
@@ -79,7 +172,12 @@ impl Serializer for &mut RespSerializer {
     type SerializeStructVariant = Self;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        self.output.put_slice(if v { b"#t\r\n" } else { b"#f\r\n" });
+        match (self.resp2, v) {
+            (false, true) => self.output.put_slice(b"#t\r\n"),
+            (false, false) => self.output.put_slice(b"#f\r\n"),
+            (true, true) => self.output.put_slice(b":1\r\n"),
+            (true, false) => self.output.put_slice(b":0\r\n"),
+        }
         Ok(())
     }
 
@@ -143,6 +241,13 @@ impl Serializer for &mut RespSerializer {
         if self.is_error {
             self.is_error = false;
             self.output.put_u8(ERROR_TAG);
+        } else if self.is_big_number {
+            self.is_big_number = false;
+            if self.resp2 {
+                self.serialize_bulk_string(v.as_bytes());
+                return Ok(());
+            }
+            self.output.put_u8(BIG_NUMBER_TAG);
         } else {
             self.output.put_u8(SIMPLE_STRING_TAG);
         }
@@ -152,10 +257,7 @@ impl Serializer for &mut RespSerializer {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        self.output.put_u8(BULK_STRING_TAG);
-        self.serialize_raw_integer(v.len());
-        self.output.put_slice(v);
-        self.output.put_slice(b"\r\n");
+        self.serialize_bulk_string(v);
         Ok(())
     }
 
@@ -171,7 +273,11 @@ impl Serializer for &mut RespSerializer {
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        self.output.put_slice(b"_\r\n");
+        if self.resp2 {
+            self.output.put_slice(b"$-1\r\n");
+        } else {
+            self.output.put_slice(b"_\r\n");
+        }
         Ok(())
     }
 
@@ -196,8 +302,10 @@ impl Serializer for &mut RespSerializer {
     where
         T: serde::Serialize + ?Sized,
     {
-        if name == ERROR_FAKE_FIELD {
-            self.is_error = true;
+        match name {
+            ERROR_FAKE_FIELD => self.is_error = true,
+            BIG_NUMBER_FAKE_FIELD => self.is_big_number = true,
+            _ => (),
         }
         value.serialize(self)
     }
@@ -212,27 +320,27 @@ impl Serializer for &mut RespSerializer {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.output.put_u8(MAP_TAG);
-        self.output.put_slice(b"1\r\n");
+        self.serialize_map_header(Some(1));
         variant.serialize(&mut *self)?;
         value.serialize(&mut *self)?;
         Ok(())
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        let Some(len) = len else {
+        if self.resp2 && len.is_none() {
             return Err(ser::Error::custom(
                 "expecting len on sequence serialization",
             ));
-        };
+        }
 
-        self.output.put_u8(ARRAY_TAG);
-        self.serialize_raw_integer(len);
+        self.streamed.push(len.is_none());
+        self.serialize_aggregate_header(ARRAY_TAG, len);
         Ok(self)
     }
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        self.serialize_seq(Some(len))
+        self.serialize_aggregate_header(ARRAY_TAG, Some(len));
+        Ok(self)
     }
 
     fn serialize_tuple_struct(
@@ -241,6 +349,7 @@ impl Serializer for &mut RespSerializer {
         len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
         match name {
+            PUSH_FAKE_FIELD | SET_FAKE_FIELD if self.resp2 => self.serialize_tuple(len),
             PUSH_FAKE_FIELD => {
                 self.output.put_u8(PUSH_TAG);
                 self.serialize_raw_integer(len);
@@ -251,7 +360,12 @@ impl Serializer for &mut RespSerializer {
                 self.serialize_raw_integer(len);
                 Ok(self)
             }
-            _ => self.serialize_seq(Some(len)),
+            ATTRIBUTE_FAKE_FIELD => {
+                // the attributes, then the value they are attached to
+                self.is_attribute = true;
+                Ok(self)
+            }
+            _ => self.serialize_tuple(len),
         }
     }
 
@@ -262,8 +376,7 @@ impl Serializer for &mut RespSerializer {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        self.output.put_u8(MAP_TAG);
-        self.output.put_slice(b"1\r\n");
+        self.serialize_map_header(Some(1));
         variant.serialize(&mut *self)?;
         self.output.put_u8(ARRAY_TAG);
         self.serialize_raw_integer(len);
@@ -271,12 +384,12 @@ impl Serializer for &mut RespSerializer {
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        let Some(len) = len else {
+        if self.resp2 && len.is_none() {
             return Err(ser::Error::custom("expecting len on map serialization"));
-        };
+        }
 
-        self.output.put_u8(MAP_TAG);
-        self.serialize_raw_integer(len);
+        self.serialize_map_header(len);
+        self.streamed.push(len.is_none());
         Ok(self)
     }
 
@@ -285,7 +398,8 @@ impl Serializer for &mut RespSerializer {
         _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        self.serialize_map(Some(len))
+        self.serialize_map_header(Some(len));
+        Ok(self)
     }
 
     fn serialize_struct_variant(
@@ -295,11 +409,9 @@ impl Serializer for &mut RespSerializer {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        self.output.put_u8(MAP_TAG);
-        self.output.put_slice(b"1\r\n");
+        self.serialize_map_header(Some(1));
         variant.serialize(&mut *self)?;
-        self.output.put_u8(MAP_TAG);
-        self.serialize_raw_integer(len);
+        self.serialize_map_header(Some(len));
         Ok(self)
     }
 }
@@ -318,6 +430,7 @@ impl SerializeSeq for &mut RespSerializer {
 
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.end_streamed();
         Ok(())
     }
 }
@@ -398,6 +511,13 @@ impl SerializeMap for &mut RespSerializer {
 
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.end_streamed();
+        if let Some((pos, level)) = self.discarded_attributes {
+            if level == self.streamed.len() {
+                self.output.truncate(pos);
+                self.discarded_attributes = None;
+            }
+        }
         Ok(())
     }
 }
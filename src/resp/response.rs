@@ -1,4 +1,4 @@
-use crate::resp::{BulkString, Value};
+use crate::resp::{BulkBytes, BulkString, Value};
 use serde::de::DeserializeOwned;
 use smallvec::SmallVec;
 use std::{
@@ -30,6 +30,7 @@ impl PrimitiveResponse for f64 {}
 impl PrimitiveResponse for bool {}
 impl PrimitiveResponse for String {}
 impl PrimitiveResponse for BulkString {}
+impl PrimitiveResponse for BulkBytes {}
 impl<T: PrimitiveResponse + DeserializeOwned> PrimitiveResponse for Option<T> {}
 
 /// Marker for a collection response
@@ -0,0 +1,590 @@
+use crate::{
+    client::ReplyLimits,
+    resp::{BufferDecoder, Command, CommandArgs, RespSerializer, ARRAY_TAG},
+    Error, Result,
+};
+use bytes::BytesMut;
+use serde::Serialize;
+use std::{collections::HashSet, str};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Maximum length of an inline command
+const MAX_INLINE_COMMAND_LEN: usize = 64 * 1024;
+
+/// Version of the [RESP](https://redis.io/docs/reference/protocol-spec/) protocol
+/// used to encode the replies of a [`ServerCodec`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RespVersion {
+    /// RESP2, the default protocol of a new connection
+    #[default]
+    Resp2,
+    /// RESP3, negotiated with the [`HELLO`](https://redis.io/commands/hello/) command
+    Resp3,
+}
+
+/// Command decoded by a [`ServerCodec`]
+#[derive(Debug, Clone)]
+pub enum ServerCommand {
+    /// Command supported by this crate or [registered](ServerCodec::register_command)
+    Known(Command),
+    /// Other command, with its name converted to uppercase
+    Unknown {
+        /// Name of the command
+        name: String,
+        /// Arguments of the command
+        args: CommandArgs,
+    },
+}
+
+impl ServerCommand {
+    /// Name of the command, converted to uppercase
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ServerCommand::Known(command) => command.name,
+            ServerCommand::Unknown { name, .. } => name,
+        }
+    }
+
+    /// Arguments of the command
+    #[must_use]
+    pub fn args(&self) -> &CommandArgs {
+        match self {
+            ServerCommand::Known(command) => &command.args,
+            ServerCommand::Unknown { args, .. } => args,
+        }
+    }
+}
+
+/// Tokio codec for the server side of the [RESP](https://redis.io/docs/reference/protocol-spec/) protocol,
+/// to build Redis proxies, sidecars or test doubles
+///
+/// * Decodes the commands sent by clients, either as RESP arrays of bulk strings
+///   or as [inline commands](https://redis.io/docs/reference/protocol-spec/#inline-commands),
+///   into [`ServerCommand`] instances.
+///   Command names are converted to uppercase.
+///   The commands supported by this crate and the [registered](ServerCodec::register_command) ones
+///   are decoded as [`Command`] instances, other commands as [`ServerCommand::Unknown`],
+///   so that a server can reply with an `unknown command` error.
+/// * Encodes replies, typically [`Value`](crate::resp::Value) instances,
+///   with the [`RespSerializer`] in RESP2 or RESP3, depending on the [`RespVersion`] of the codec.
+///
+/// The version is not switched automatically:
+/// call [`set_version`](ServerCodec::set_version) when handling a `HELLO 3` command.
+///
+/// # Example
+/// ```
+/// use bytes::BytesMut;
+/// use rustis::{
+///     resp::{ServerCodec, ServerCommand, Value},
+///     Result,
+/// };
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// fn main() -> Result<()> {
+///     let mut codec = ServerCodec::default();
+///
+///     let mut buf = BytesMut::from("*2\r\n$3\r\nget\r\n$3\r\nkey\r\n");
+///     let Some(ServerCommand::Known(command)) = codec.decode(&mut buf)? else {
+///         panic!("GET is supported");
+///     };
+///     assert_eq!("GET", command.name);
+///
+///     let mut buf = BytesMut::new();
+///     codec.encode(&Value::BulkString(b"value".to_vec()), &mut buf)?;
+///     assert_eq!(b"$5\r\nvalue\r\n", &buf[..]);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+pub struct ServerCodec {
+    decoder: BufferDecoder,
+    version: RespVersion,
+    registered_commands: HashSet<&'static str>,
+}
+
+impl ServerCodec {
+    /// Creates a new `ServerCodec` encoding replies with the given protocol version
+    #[must_use]
+    pub fn new(version: RespVersion) -> Self {
+        Self {
+            version,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `ServerCodec` encoding replies with the given protocol version,
+    /// and decoding commands within the given limits
+    /// (e.g. [`max_bulk_size`](ReplyLimits::max_bulk_size) for the size of an argument)
+    #[must_use]
+    pub fn with_limits(version: RespVersion, limits: ReplyLimits) -> Self {
+        Self {
+            decoder: BufferDecoder::with_limits(limits),
+            version,
+            ..Default::default()
+        }
+    }
+
+    /// Decode a command which is not supported by this crate, e.g. the command of a module
+    ///
+    /// `name` is expected in uppercase, like the names of the other decoded commands,
+    /// but is matched case insensitively.
+    pub fn register_command(&mut self, name: &'static str) {
+        self.registered_commands.insert(name);
+    }
+
+    /// Protocol version used to encode the replies
+    #[must_use]
+    pub fn version(&self) -> RespVersion {
+        self.version
+    }
+
+    /// Switch the protocol version used to encode the next replies
+    pub fn set_version(&mut self, version: RespVersion) {
+        self.version = version;
+    }
+
+    fn decode_inline(&self, src: &mut BytesMut) -> Result<Option<ServerCommand>> {
+        loop {
+            let Some(idx) = memchr::memchr(b'\n', src) else {
+                if src.len() > MAX_INLINE_COMMAND_LEN {
+                    return Err(Error::Protocol("Too big inline command".to_owned()));
+                }
+                return Ok(None);
+            };
+
+            let line = src.split_to(idx + 1);
+            let mut parts = line[..]
+                .split(u8::is_ascii_whitespace)
+                .filter(|p| !p.is_empty());
+
+            // empty lines are ignored
+            if let Some(name) = parts.next() {
+                return Ok(Some(self.to_command(name, parts)?));
+            }
+        }
+    }
+
+    fn to_command<'a>(
+        &self,
+        name: &[u8],
+        args: impl Iterator<Item = &'a [u8]>,
+    ) -> Result<ServerCommand> {
+        let name = str::from_utf8(name)?.to_ascii_uppercase();
+        let mut command_args = CommandArgs::default();
+        for arg in args {
+            command_args.arg(arg);
+        }
+
+        Ok(match self.command_name(&name) {
+            Some(name) => {
+                let mut command = Command::new(name);
+                command.args = command_args;
+                ServerCommand::Known(command)
+            }
+            None => ServerCommand::Unknown {
+                name,
+                args: command_args,
+            },
+        })
+    }
+
+    /// [`Command::name`] is `&'static str`: the names of the decoded commands
+    /// are looked up in the known and registered commands instead of being allocated.
+    fn command_name(&self, uppercase_name: &str) -> Option<&'static str> {
+        if let Some(name) = self
+            .registered_commands
+            .iter()
+            .find(|registered| registered.eq_ignore_ascii_case(uppercase_name))
+        {
+            return Some(name);
+        }
+
+        KNOWN_COMMANDS
+            .binary_search(&uppercase_name)
+            .ok()
+            .map(|idx| KNOWN_COMMANDS[idx])
+    }
+}
+
+impl Decoder for ServerCodec {
+    type Item = ServerCommand;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        match src.first() {
+            None => Ok(None),
+            Some(&ARRAY_TAG) => {
+                let Some(frame) = self.decoder.decode(src)? else {
+                    return Ok(None);
+                };
+
+                let parts: Vec<&[u8]> = frame.to()?;
+                let mut parts = parts.into_iter();
+                let Some(name) = parts.next() else {
+                    return Err(Error::Protocol("Empty command".to_owned()));
+                };
+                self.to_command(name, parts).map(Some)
+            }
+            Some(_) => self.decode_inline(src),
+        }
+    }
+}
+
+impl<T: Serialize + ?Sized> Encoder<&T> for ServerCodec {
+    type Error = Error;
+
+    fn encode(&mut self, reply: &T, dst: &mut BytesMut) -> Result<()> {
+        let mut serializer = match self.version {
+            RespVersion::Resp2 => RespSerializer::new_resp2(),
+            RespVersion::Resp3 => RespSerializer::new(),
+        };
+        reply.serialize(&mut serializer)?;
+        dst.extend_from_slice(&serializer.get_output());
+        Ok(())
+    }
+}
+
+/// Names of the commands supported by this crate, sorted
+pub(crate) const KNOWN_COMMANDS: &[&str] = &[
+    "ACL",
+    "APPEND",
+    "ASKING",
+    "AUTH",
+    "BF.ADD",
+    "BF.EXISTS",
+    "BF.INFO",
+    "BF.INSERT",
+    "BF.LOADCHUNK",
+    "BF.MADD",
+    "BF.MEXISTS",
+    "BF.RESERVE",
+    "BF.SCANDUMP",
+    "BGREWRITEAOF",
+    "BGSAVE",
+    "BITCOUNT",
+    "BITFIELD",
+    "BITFIELD_RO",
+    "BITOP",
+    "BITPOS",
+    "BLMOVE",
+    "BLMPOP",
+    "BLPOP",
+    "BRPOP",
+    "BZMPOP",
+    "BZPOPMAX",
+    "BZPOPMIN",
+    "CF.ADD",
+    "CF.ADDNX",
+    "CF.COUNT",
+    "CF.DEL",
+    "CF.EXISTS",
+    "CF.INFO",
+    "CF.INSERT",
+    "CF.INSERTNX",
+    "CF.LOADCHUNK",
+    "CF.MEXISTS",
+    "CF.RESERVE",
+    "CF.SCANDUMP",
+    "CLIENT",
+    "CLUSTER",
+    "CMS.INCRBY",
+    "CMS.INFO",
+    "CMS.INITBYDIM",
+    "CMS.INITBYPROB",
+    "CMS.MERGE",
+    "CMS.QUERY",
+    "COMMAND",
+    "CONFIG",
+    "COPY",
+    "DBSIZE",
+    "DEBUG",
+    "DECR",
+    "DECRBY",
+    "DEL",
+    "DUMP",
+    "ECHO",
+    "EVAL",
+    "EVALSHA",
+    "EVALSHA_RO",
+    "EVAL_RO",
+    "EXEC",
+    "EXISTS",
+    "EXPIRE",
+    "EXPIREAT",
+    "EXPIRETIME",
+    "FAILOVER",
+    "FCALL",
+    "FCALL_RO",
+    "FLUSHALL",
+    "FLUSHDB",
+    "FT.AGGREGATE",
+    "FT.ALIASADD",
+    "FT.ALIASDEL",
+    "FT.ALIASUPDATE",
+    "FT.ALTER",
+    "FT.CONFIG",
+    "FT.CREATE",
+    "FT.CURSOR",
+    "FT.DICTADD",
+    "FT.DICTDEL",
+    "FT.DICTDUMP",
+    "FT.DROPINDEX",
+    "FT.EXPLAIN",
+    "FT.EXPLAINCLI",
+    "FT.INFO",
+    "FT.PROFILE",
+    "FT.SEARCH",
+    "FT.SPELLCHECK",
+    "FT.SUGADD",
+    "FT.SUGDEL",
+    "FT.SUGGET",
+    "FT.SUGLEN",
+    "FT.SYNDUMP",
+    "FT.SYNUPDATE",
+    "FT.TAGVALS",
+    "FT._LIST",
+    "FUNCTION",
+    "GEOADD",
+    "GEODIST",
+    "GEOHASH",
+    "GEOPOS",
+    "GEOSEARCH",
+    "GEOSEARCHSTORE",
+    "GET",
+    "GETBIT",
+    "GETDEL",
+    "GETEX",
+    "GETRANGE",
+    "GETSET",
+    "GRAPH.CONFIG",
+    "GRAPH.DELETE",
+    "GRAPH.EXPLAIN",
+    "GRAPH.LIST",
+    "GRAPH.QUERY",
+    "GRAPH.RO_QUERY",
+    "GRAPH.SLOWLOG",
+    "HDEL",
+    "HELLO",
+    "HEXISTS",
+    "HGET",
+    "HGETALL",
+    "HINCRBY",
+    "HINCRBYFLOAT",
+    "HKEYS",
+    "HLEN",
+    "HMGET",
+    "HRANDFIELD",
+    "HSCAN",
+    "HSET",
+    "HSETNX",
+    "HSTRLEN",
+    "HVALS",
+    "INCR",
+    "INCRBY",
+    "INCRBYFLOAT",
+    "INFO",
+    "JSON.ARRAPPEND",
+    "JSON.ARRINDEX",
+    "JSON.ARRINSERT",
+    "JSON.ARRLEN",
+    "JSON.ARRPOP",
+    "JSON.ARRTRIM",
+    "JSON.CLEAR",
+    "JSON.DEBUG",
+    "JSON.DEL",
+    "JSON.FORGET",
+    "JSON.GET",
+    "JSON.MGET",
+    "JSON.NUMINCRBY",
+    "JSON.NUMMULTBY",
+    "JSON.OBJKEYS",
+    "JSON.OBJLEN",
+    "JSON.RESP",
+    "JSON.SET",
+    "JSON.STRAPPEND",
+    "JSON.STRLEN",
+    "JSON.TOGGLE",
+    "JSON.TYPE",
+    "KEYS",
+    "LASTSAVE",
+    "LATENCY",
+    "LCS",
+    "LINDEX",
+    "LINSERT",
+    "LLEN",
+    "LMOVE",
+    "LMPOP",
+    "LOLWUT",
+    "LPOP",
+    "LPOS",
+    "LPUSH",
+    "LPUSHX",
+    "LRANGE",
+    "LREM",
+    "LSET",
+    "LTRIM",
+    "MEMORY",
+    "MGET",
+    "MIGRATE",
+    "MODULE",
+    "MONITOR",
+    "MOVE",
+    "MSET",
+    "MSETNX",
+    "MULTI",
+    "OBJECT",
+    "PERSIST",
+    "PEXPIRE",
+    "PEXPIREAT",
+    "PEXPIRETIME",
+    "PFADD",
+    "PFCOUNT",
+    "PFMERGE",
+    "PING",
+    "PSETEX",
+    "PSUBSCRIBE",
+    "PTTL",
+    "PUBLISH",
+    "PUBSUB",
+    "PUNSUBSCRIBE",
+    "QUIT",
+    "RANDOMKEY",
+    "READONLY",
+    "READWRITE",
+    "RENAME",
+    "RENAMENX",
+    "REPLICAOF",
+    "RESET",
+    "RESTORE",
+    "ROLE",
+    "RPOP",
+    "RPUSH",
+    "RPUSHX",
+    "SADD",
+    "SAVE",
+    "SCAN",
+    "SCARD",
+    "SCRIPT",
+    "SDIFF",
+    "SDIFFSTORE",
+    "SELECT",
+    "SENTINEL",
+    "SET",
+    "SETBIT",
+    "SETEX",
+    "SETNX",
+    "SETRANGE",
+    "SHUTDOWN",
+    "SINTER",
+    "SINTERCARD",
+    "SINTERSTORE",
+    "SISMEMBER",
+    "SLOWLOG",
+    "SMEMBERS",
+    "SMISMEMBER",
+    "SMOVE",
+    "SORT",
+    "SORT_RO",
+    "SPOP",
+    "SPUBLISH",
+    "SRANDMEMBER",
+    "SREM",
+    "SSCAN",
+    "SSUBSCRIBE",
+    "STRLEN",
+    "SUBSCRIBE",
+    "SUNION",
+    "SUNIONSTORE",
+    "SUNSUBSCRIBE",
+    "SWAPDB",
+    "TDIGEST.ADD",
+    "TDIGEST.BYRANK",
+    "TDIGEST.BYREVRANK",
+    "TDIGEST.CDF",
+    "TDIGEST.CREATE",
+    "TDIGEST.INFO",
+    "TDIGEST.MAX",
+    "TDIGEST.MERGE",
+    "TDIGEST.MIN",
+    "TDIGEST.QUANTILE",
+    "TDIGEST.RANK",
+    "TDIGEST.RESET",
+    "TDIGEST.REVRANK",
+    "TDIGEST.TRIMMED_MEAN",
+    "TIME",
+    "TOPK.ADD",
+    "TOPK.INCRBY",
+    "TOPK.INFO",
+    "TOPK.LIST",
+    "TOPK.QUERY",
+    "TOPK.RESERVE",
+    "TOUCH",
+    "TS.ADD",
+    "TS.ALTER",
+    "TS.CREATE",
+    "TS.CREATERULE",
+    "TS.DECRBY",
+    "TS.DEL",
+    "TS.DELETERULE",
+    "TS.GET",
+    "TS.INCRBY",
+    "TS.INFO",
+    "TS.MADD",
+    "TS.MGET",
+    "TS.MRANGE",
+    "TS.MREVRANGE",
+    "TS.QUERYINDEX",
+    "TS.RANGE",
+    "TS.REVRANGE",
+    "TTL",
+    "TYPE",
+    "UNLINK",
+    "UNSUBSCRIBE",
+    "UNWATCH",
+    "WAIT",
+    "WAITAOF",
+    "WATCH",
+    "XACK",
+    "XADD",
+    "XAUTOCLAIM",
+    "XCLAIM",
+    "XDEL",
+    "XGROUP",
+    "XINFO",
+    "XLEN",
+    "XPENDING",
+    "XRANGE",
+    "XREAD",
+    "XREADGROUP",
+    "XREVRANGE",
+    "XTRIM",
+    "ZADD",
+    "ZCARD",
+    "ZCOUNT",
+    "ZDIFF",
+    "ZDIFFSTORE",
+    "ZINCRBY",
+    "ZINTER",
+    "ZINTERCARD",
+    "ZINTERSTORE",
+    "ZLEXCOUNT",
+    "ZMPOP",
+    "ZMSCORE",
+    "ZPOPMAX",
+    "ZPOPMIN",
+    "ZRANDMEMBER",
+    "ZRANGE",
+    "ZRANGESTORE",
+    "ZRANK",
+    "ZREM",
+    "ZREMRANGEBYLEX",
+    "ZREMRANGEBYRANK",
+    "ZREMRANGEBYSCORE",
+    "ZREVRANK",
+    "ZSCAN",
+    "ZSCORE",
+    "ZUNION",
+    "ZUNIONSTORE",
+];
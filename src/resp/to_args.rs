@@ -1,10 +1,12 @@
-use crate::resp::{BulkString, CommandArgs};
+use crate::resp::{BulkBytes, BulkReader, BulkString, CommandArgs};
+use bytes::Bytes;
 use dtoa::Float;
 use itoa::Integer;
 use smallvec::SmallVec;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     hash::BuildHasher,
+    sync::Arc,
 };
 
 /// Types compatible with command args
@@ -127,6 +129,38 @@ impl ToArgs for BulkString {
     }
 }
 
+/// Shared without copy
+impl ToArgs for Bytes {
+    #[inline]
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.write_bytes(self.clone());
+    }
+}
+
+/// Shared without copy
+impl ToArgs for BulkBytes {
+    #[inline]
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.write_bytes(self.clone().into_bytes());
+    }
+}
+
+/// Shared without copy
+impl ToArgs for Arc<[u8]> {
+    #[inline]
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.write_bytes(Bytes::from_owner(self.clone()));
+    }
+}
+
+/// Streamed while the command is written
+impl ToArgs for BulkReader {
+    #[inline]
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.write_reader(self.clone());
+    }
+}
+
 impl ToArgs for Vec<u8> {
     #[inline]
     fn write_args(&self, args: &mut CommandArgs) {
@@ -375,9 +409,7 @@ where
 impl ToArgs for CommandArgs {
     #[inline]
     fn write_args(&self, args: &mut CommandArgs) {
-        for arg in self {
-            args.write_arg(arg);
-        }
+        args.extend(self);
     }
 
     #[inline]
@@ -389,9 +421,7 @@ impl ToArgs for CommandArgs {
 impl ToArgs for &CommandArgs {
     #[inline]
     fn write_args(&self, args: &mut CommandArgs) {
-        for arg in self.into_iter() {
-            args.write_arg(arg);
-        }
+        args.extend(self);
     }
 
     #[inline]
@@ -424,6 +454,10 @@ impl<const N: usize> SingleArg for [u8; N] {}
 impl SingleArg for &[u8] {}
 impl SingleArg for Vec<u8> {}
 impl SingleArg for BulkString {}
+impl SingleArg for Bytes {}
+impl SingleArg for BulkBytes {}
+impl SingleArg for Arc<[u8]> {}
+impl SingleArg for BulkReader {}
 impl<T: SingleArg> SingleArg for Option<T> {}
 
 /// Generic Marker for Collections of `ToArgs`
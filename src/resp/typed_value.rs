@@ -0,0 +1,423 @@
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+use crate::client::PayloadCodec;
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+use crate::resp::Value;
+use crate::resp::{BulkString, CommandArgs, PrimitiveResponse, SingleArg, ToArgs};
+use crate::{Error, Result};
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+use serde::{Serialize, Serializer};
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+use std::marker::PhantomData;
+use std::{fmt, ops::Deref};
+
+/// Defines a wrapper storing a typed value in a serialization format
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+macro_rules! serde_wrapper {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $format:literal, $encode:expr, $decode:expr
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name<T> {
+            value: T,
+            encoded: Bytes,
+        }
+
+        impl<T: Serialize> $name<T> {
+            #[doc = concat!("Wraps a value, encoding it in ", $format)]
+            ///
+            /// # Errors
+            /// If the value cannot be serialized
+            pub fn new(value: T) -> Result<Self> {
+                let encoded = $encode(&value)
+                    .map_err(|e| Error::Client(format!("Cannot encode {} value: {e}", $format)))?;
+                Ok(Self {
+                    value,
+                    encoded: Bytes::from(encoded),
+                })
+            }
+        }
+
+        impl<T> $name<T> {
+            /// Unwraps the value
+            #[inline]
+            pub fn into_inner(self) -> T {
+                self.value
+            }
+
+            /// Encoded value
+            #[inline]
+            pub fn encoded(&self) -> &[u8] {
+                &self.encoded
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.value).finish()
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for $name<T> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+
+        impl<T: Eq> Eq for $name<T> {}
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            #[inline]
+            fn deref(&self) -> &T {
+                &self.value
+            }
+        }
+
+        impl<T> ToArgs for $name<T> {
+            #[inline]
+            fn write_args(&self, args: &mut CommandArgs) {
+                args.write_bytes(self.encoded.clone());
+            }
+        }
+
+        impl<T> SingleArg for $name<T> {}
+
+        /// Serialized as the bytes of its encoding
+        impl<T> Serialize for $name<T> {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.encoded)
+            }
+        }
+
+        impl<'de, T: DeserializeOwned> Deserialize<'de> for $name<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let bytes = BulkString::deserialize(deserializer)?;
+                let value = $decode(&bytes).map_err(|e| {
+                    serde::de::Error::custom(format!("Cannot decode {} value: {e}", $format))
+                })?;
+                Ok(Self {
+                    value,
+                    encoded: Bytes::from(Vec::from(bytes)),
+                })
+            }
+        }
+
+        impl<T: DeserializeOwned> PrimitiveResponse for $name<T> {}
+
+        impl<T: Serialize + DeserializeOwned> PayloadCodec for $name<T> {
+            type Value = T;
+
+            #[inline]
+            fn encode(value: &T) -> Result<Vec<u8>> {
+                $encode(value)
+                    .map_err(|e| Error::Client(format!("Cannot encode {} payload: {e}", $format)))
+            }
+
+            #[inline]
+            fn decode(payload: &[u8]) -> Result<T> {
+                $decode(payload)
+                    .map_err(|e| Error::Client(format!("Cannot decode {} payload: {e}", $format)))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "json")]
+serde_wrapper! {
+    /// Value stored in JSON with [`serde_json`](https://docs.rs/serde_json)
+    ///
+    /// Can be passed as an argument to any command expecting a [`SingleArg`]
+    /// and read back from any command returning a [`PrimitiveResponse`].
+    /// Also a [`PayloadCodec`] for pub/sub messages,
+    /// and a field of a [`HashRecord`](crate::resp::HashRecord) storing a nested value.
+    ///
+    /// The value is encoded once by [`Json::new`], which fails if it cannot be serialized
+    /// (e.g. a map with non-string keys).
+    ///
+    /// # Example
+    /// ```
+    /// use rustis::{
+    ///     client::Client,
+    ///     commands::{FlushingMode, ServerCommands, StringCommands},
+    ///     resp::Json,
+    ///     Result,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+    /// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+    /// async fn main() -> Result<()> {
+    ///     let client = Client::connect("127.0.0.1:6379").await?;
+    ///     client.flushdb(FlushingMode::Sync).await?;
+    ///
+    ///     client.set("key", Json::new(Point { x: 1, y: 2 })?).await?;
+    ///     let point: Json<Point> = client.get("key").await?;
+    ///     assert_eq!(Point { x: 1, y: 2 }, point.into_inner());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    Json, "JSON", serde_json::to_vec, serde_json::from_slice
+}
+
+#[cfg(feature = "msgpack")]
+serde_wrapper! {
+    /// Value stored in [MessagePack](https://msgpack.org/) with [`rmp_serde`](https://docs.rs/rmp-serde)
+    ///
+    /// Structs are encoded as maps, so that fields can be added or reordered.
+    ///
+    /// Can be passed as an argument to any command expecting a [`SingleArg`]
+    /// and read back from any command returning a [`PrimitiveResponse`].
+    /// Also a [`PayloadCodec`] for pub/sub messages,
+    /// and a field of a [`HashRecord`](crate::resp::HashRecord) storing a nested value.
+    ///
+    /// The value is encoded once by [`MsgPack::new`], which fails if it cannot be serialized.
+    #[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+    MsgPack, "MessagePack", rmp_serde::to_vec_named, rmp_serde::from_slice
+}
+
+#[cfg(feature = "cbor")]
+serde_wrapper! {
+    /// Value stored in [CBOR](https://cbor.io/) with [`ciborium`](https://docs.rs/ciborium)
+    ///
+    /// Can be passed as an argument to any command expecting a [`SingleArg`]
+    /// and read back from any command returning a [`PrimitiveResponse`].
+    /// Also a [`PayloadCodec`] for pub/sub messages,
+    /// and a field of a [`HashRecord`](crate::resp::HashRecord) storing a nested value.
+    ///
+    /// The value is encoded once by [`Cbor::new`], which fails if it cannot be serialized.
+    #[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+    Cbor, "CBOR", cbor_to_vec, cbor_from_slice
+}
+
+#[cfg(feature = "cbor")]
+fn cbor_to_vec<T: Serialize>(value: &T) -> std::result::Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+#[cfg(feature = "cbor")]
+fn cbor_from_slice<T: DeserializeOwned>(bytes: &[u8]) -> std::result::Result<T, String> {
+    ciborium::from_reader(bytes).map_err(|e| e.to_string())
+}
+
+/// Compression algorithm of a [`Compressed`] value
+#[cfg_attr(docsrs, doc(cfg(any(feature = "lz4", feature = "zstd"))))]
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+pub trait Compression {
+    /// Compresses the encoded value
+    fn compress(data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Decompresses the encoded value
+    fn decompress(data: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// [LZ4](https://lz4.org/) compression with [`lz4_flex`](https://docs.rs/lz4_flex),
+/// the uncompressed size being prepended to the compressed data
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+#[cfg(feature = "lz4")]
+pub struct Lz4;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+#[cfg(feature = "lz4")]
+impl Compression for Lz4 {
+    fn compress(data: &[u8]) -> Result<Vec<u8>> {
+        Ok(lz4_flex::compress_prepend_size(data))
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+        let (size, block) = lz4_flex::block::uncompressed_size(data)
+            .map_err(|e| Error::Client(format!("Cannot decompress LZ4 value: {e}")))?;
+        // LZ4 cannot expand data more than 255 times: a larger size is not allocated
+        if size > block.len().saturating_mul(255) {
+            return Err(Error::Client(format!(
+                "Cannot decompress LZ4 value: invalid uncompressed size {size}"
+            )));
+        }
+        lz4_flex::decompress(block, size)
+            .map_err(|e| Error::Client(format!("Cannot decompress LZ4 value: {e}")))
+    }
+}
+
+/// [Zstandard](https://facebook.github.io/zstd/) compression with [`zstd`](https://docs.rs/zstd),
+/// at the default compression level
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[cfg(feature = "zstd")]
+pub struct Zstd;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[cfg(feature = "zstd")]
+impl Compression for Zstd {
+    fn compress(data: &[u8]) -> Result<Vec<u8>> {
+        zstd::bulk::compress(data, 0)
+            .map_err(|e| Error::Client(format!("Cannot compress Zstandard value: {e}")))
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+        zstd_decompress(data, ZSTD_MAX_DECOMPRESSED_SIZE)
+    }
+}
+
+/// Maximum size of a decompressed Zstandard value, the maximum size of a Redis string
+///
+/// Unlike LZ4, the ratio of Zstandard is not bounded:
+/// the decompression of a crafted value stops at this size instead of exhausting the memory.
+#[cfg(feature = "zstd")]
+const ZSTD_MAX_DECOMPRESSED_SIZE: u64 = 512 * 1024 * 1024;
+
+#[cfg(feature = "zstd")]
+pub(crate) fn zstd_decompress(data: &[u8], max_size: u64) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let error =
+        |e: std::io::Error| Error::Client(format!("Cannot decompress Zstandard value: {e}"));
+    let decoder = zstd::stream::read::Decoder::new(data).map_err(error)?;
+    let mut decompressed = Vec::new();
+    decoder
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut decompressed)
+        .map_err(error)?;
+
+    if decompressed.len() as u64 > max_size {
+        return Err(Error::Client(format!(
+            "Cannot decompress Zstandard value: decompressed size exceeds {max_size} bytes"
+        )));
+    }
+
+    Ok(decompressed)
+}
+
+/// Value compressed with the algorithm `C` once encoded as a single argument
+///
+/// `T` can be any [`SingleArg`], typically a string, a byte buffer, or another wrapper like `Json<T>`:
+/// `Compressed<Lz4, Json<T>>` stores a value in JSON compressed with LZ4.
+///
+/// The value is compressed once by [`Compressed::new`], which fails if it cannot be compressed.
+///
+/// # Example
+/// ```
+/// use rustis::{
+///     client::Client,
+///     commands::{FlushingMode, ServerCommands, StringCommands},
+///     resp::{Compressed, Zstd},
+///     Result,
+/// };
+///
+/// #[cfg_attr(feature = "tokio-runtime", tokio::main)]
+/// #[cfg_attr(feature = "async-std-runtime", async_std::main)]
+/// async fn main() -> Result<()> {
+///     let client = Client::connect("127.0.0.1:6379").await?;
+///     client.flushdb(FlushingMode::Sync).await?;
+///
+///     let text = "Lorem ipsum ".repeat(1000);
+///     client.set("key", Compressed::<Zstd, _>::new(text.clone())?).await?;
+///     let value: Compressed<Zstd, String> = client.get("key").await?;
+///     assert_eq!(text, *value);
+///
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(any(feature = "lz4", feature = "zstd"))))]
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+pub struct Compressed<C, T> {
+    value: T,
+    compressed: Bytes,
+    compression: PhantomData<fn() -> C>,
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C: Compression, T: SingleArg> Compressed<C, T> {
+    /// Wraps a value, compressing it with the algorithm `C`
+    ///
+    /// # Errors
+    /// If the value cannot be compressed
+    pub fn new(value: T) -> Result<Self> {
+        let mut encoded = CommandArgs::default();
+        value.write_args(&mut encoded);
+        let data = encoded.into_iter().next().unwrap_or_default();
+        let compressed = C::compress(data)?;
+        Ok(Self {
+            value,
+            compressed: Bytes::from(compressed),
+            compression: PhantomData,
+        })
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C, T> Compressed<C, T> {
+    /// Unwraps the value
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Compressed value
+    #[inline]
+    pub fn compressed(&self) -> &[u8] {
+        &self.compressed
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C, T: fmt::Debug> fmt::Debug for Compressed<C, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Compressed").field(&self.value).finish()
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C, T> Deref for Compressed<C, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C, T> ToArgs for Compressed<C, T> {
+    #[inline]
+    fn write_args(&self, args: &mut CommandArgs) {
+        args.write_bytes(self.compressed.clone());
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C, T> SingleArg for Compressed<C, T> {}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<'de, C: Compression, T: DeserializeOwned> Deserialize<'de> for Compressed<C, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let bytes = BulkString::deserialize(deserializer)?;
+        let data = C::decompress(&bytes).map_err(serde::de::Error::custom)?;
+        // decoded like the reply of the uncompressed value
+        let value = Value::BulkString(data)
+            .into::<T>()
+            .map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            value,
+            compressed: Bytes::from(Vec::from(bytes)),
+            compression: PhantomData,
+        })
+    }
+}
+
+#[cfg(any(feature = "lz4", feature = "zstd"))]
+impl<C: Compression, T: DeserializeOwned> PrimitiveResponse for Compressed<C, T> {}
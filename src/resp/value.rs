@@ -31,6 +31,19 @@ pub enum Value {
     Push(Vec<Value>),
     /// [RESP Error](https://redis.io/docs/reference/protocol-spec/#resp-errors)
     Error(RedisError),
+    /// [RESP3](https://github.com/antirez/RESP3/blob/master/spec.md) Big number
+    BigNumber(String),
+    /// [RESP3](https://github.com/antirez/RESP3/blob/master/spec.md) Attribute type
+    ///
+    /// Only produced by a [`RespDeserializer`](crate::resp::RespDeserializer)
+    /// with [`keep_attributes`](crate::resp::RespDeserializer::keep_attributes) set.
+    /// Otherwise attributes are ignored.
+    Attribute {
+        /// Auxiliary data attached to the value
+        attributes: HashMap<Value, Value>,
+        /// Value the attributes are attached to
+        value: Box<Value>,
+    },
     /// [RESP Null](https://redis.io/docs/reference/protocol-spec/#resp-bulk-strings)
     #[default]
     Nil,
@@ -58,6 +71,8 @@ impl Hash for Value {
             Value::Double(d) => d.to_string().hash(state),
             Value::BulkString(bs) => bs.hash(state),
             Value::Error(e) => e.hash(state),
+            Value::BigNumber(n) => n.hash(state),
+            Value::Attribute { value, .. } => value.hash(state),
             Value::Nil => "_\r\n".hash(state),
             _ => unimplemented!("Hash not implemented for {self}"),
         }
@@ -76,6 +91,17 @@ impl PartialEq for Value {
             (Self::Set(l0), Self::Set(r0)) => l0 == r0,
             (Self::Push(l0), Self::Push(r0)) => l0 == r0,
             (Self::Error(l0), Self::Error(r0)) => l0 == r0,
+            (Self::BigNumber(l0), Self::BigNumber(r0)) => l0 == r0,
+            (
+                Self::Attribute {
+                    attributes: l0,
+                    value: l1,
+                },
+                Self::Attribute {
+                    attributes: r0,
+                    value: r1,
+                },
+            ) => l0 == r0 && l1 == r1,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -142,6 +168,8 @@ impl Display for Value {
                 f.write_char(']')
             }
             Value::Error(e) => e.fmt(f),
+            Value::BigNumber(n) => n.fmt(f),
+            Value::Attribute { value, .. } => value.fmt(f),
             Value::Nil => f.write_str("Nil"),
         }
     }
@@ -163,6 +191,12 @@ impl fmt::Debug for Value {
             Self::Set(arg0) => f.debug_tuple("Set").field(arg0).finish(),
             Self::Push(arg0) => f.debug_tuple("Push").field(arg0).finish(),
             Self::Error(arg0) => f.debug_tuple("Error").field(arg0).finish(),
+            Self::BigNumber(arg0) => f.debug_tuple("BigNumber").field(arg0).finish(),
+            Self::Attribute { attributes, value } => f
+                .debug_struct("Attribute")
+                .field("attributes", attributes)
+                .field("value", value)
+                .finish(),
             Self::Nil => write!(f, "Nil"),
         }
     }
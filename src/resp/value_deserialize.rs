@@ -3,9 +3,15 @@ use serde::{
     de::{MapAccess, SeqAccess, Visitor},
     Deserialize, Deserializer,
 };
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 pub(crate) const PUSH_FAKE_FIELD: &str = ">>>PUSH>>>";
+pub(crate) const BIG_NUMBER_FAKE_FIELD: &str = ">>>BIG_NUMBER>>>";
+pub(crate) const ATTRIBUTE_FAKE_FIELD: &str = ">>>ATTRIBUTE>>>";
+/// Name of the newtype struct requested by [`Value`],
+/// so that [`RespDeserializer`](crate::resp::RespDeserializer) only exposes
+/// the fake fields of big numbers to it
+pub(crate) const VALUE_FAKE_NAME: &str = ">>>VALUE>>>";
 
 /// Implementation meant to be used with [`RespDeserializer`](crate::resp::RespDeserializer)
 impl<'de> Deserialize<'de> for Value {
@@ -14,7 +20,7 @@ impl<'de> Deserialize<'de> for Value {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(ValueVisitor)
+        deserializer.deserialize_newtype_struct(VALUE_FAKE_NAME, ValueVisitor)
     }
 }
 
@@ -27,6 +33,14 @@ impl<'de> Visitor<'de> for ValueVisitor {
         formatter.write_str("any valid resp::Value")
     }
 
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
     #[inline]
     fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
         Ok(Value::Integer(i64::from(v)))
@@ -116,6 +130,15 @@ impl<'de> Visitor<'de> for ValueVisitor {
                             return Ok(Value::Push(values));
                         }
                     }
+                    Some(PushOrKey::BigNumber) => return Ok(Value::BigNumber(map.next_value()?)),
+                    Some(PushOrKey::Attribute) => {
+                        let (attributes, value): (HashMap<Value, Value>, Value) =
+                            map.next_value()?;
+                        return Ok(Value::Attribute {
+                            attributes,
+                            value: Box::new(value),
+                        });
+                    }
                     Some(PushOrKey::Key(value)) => values.push(value),
                 };
 
@@ -128,6 +151,8 @@ impl<'de> Visitor<'de> for ValueVisitor {
 
 enum PushOrKey {
     Push,
+    BigNumber,
+    Attribute,
     Key(Value),
 }
 
@@ -173,6 +198,10 @@ impl<'de> Visitor<'de> for PushOrKeyVisitor {
     fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<PushOrKey, E> {
         if v == PUSH_FAKE_FIELD {
             Ok(PushOrKey::Push)
+        } else if v == BIG_NUMBER_FAKE_FIELD {
+            Ok(PushOrKey::BigNumber)
+        } else if v == ATTRIBUTE_FAKE_FIELD {
+            Ok(PushOrKey::Attribute)
         } else {
             let value_visitor = ValueVisitor;
             value_visitor.visit_borrowed_str(v).map(PushOrKey::Key)
@@ -183,6 +212,10 @@ impl<'de> Visitor<'de> for PushOrKeyVisitor {
     fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<PushOrKey, E> {
         if v == PUSH_FAKE_FIELD {
             Ok(PushOrKey::Push)
+        } else if v == BIG_NUMBER_FAKE_FIELD {
+            Ok(PushOrKey::BigNumber)
+        } else if v == ATTRIBUTE_FAKE_FIELD {
+            Ok(PushOrKey::Attribute)
         } else {
             let value_visitor = ValueVisitor;
             value_visitor.visit_str(v).map(PushOrKey::Key)
@@ -16,7 +16,8 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         match self {
-            Value::SimpleString(s) => visitor.visit_borrowed_str(s),
+            Value::Attribute { value, .. } => value.deserialize_any(visitor),
+            Value::SimpleString(s) | Value::BigNumber(s) => visitor.visit_borrowed_str(s),
             Value::Integer(i) => visitor.visit_i64(*i),
             Value::Double(d) => visitor.visit_f64(*d),
             Value::BulkString(bs) => visitor.visit_borrowed_bytes(bs),
@@ -36,6 +37,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_bool(visitor),
             Value::Integer(i) => *i != 0,
             Value::Double(d) => *d != 0.,
             Value::SimpleString(s) if s == "OK" => true,
@@ -60,11 +62,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_i8(visitor),
             Value::Integer(i) => *i as i8,
             Value::Double(d) => *d as i8,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<i8>()?,
-            Value::SimpleString(s) => s.parse::<i8>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<i8>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -82,11 +85,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_i16(visitor),
             Value::Integer(i) => *i as i16,
             Value::Double(d) => *d as i16,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<i16>()?,
-            Value::SimpleString(s) => s.parse::<i16>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<i16>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -104,11 +108,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_i32(visitor),
             Value::Integer(i) => *i as i32,
             Value::Double(d) => *d as i32,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<i32>()?,
-            Value::SimpleString(s) => s.parse::<i32>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<i32>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -127,11 +132,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_i64(visitor),
             Value::Integer(i) => *i,
             Value::Double(d) => *d as i64,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<i64>()?,
-            Value::SimpleString(s) => s.parse::<i64>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<i64>()?,
             Value::Array(a) if a.len() == 1 => i64::deserialize(&a[0])?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
@@ -150,11 +156,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_u8(visitor),
             Value::Integer(i) => *i as u8,
             Value::Double(d) => *d as u8,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<u8>()?,
-            Value::SimpleString(s) => s.parse::<u8>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<u8>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -172,11 +179,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_u16(visitor),
             Value::Integer(i) => *i as u16,
             Value::Double(d) => *d as u16,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<u16>()?,
-            Value::SimpleString(s) => s.parse::<u16>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<u16>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -194,11 +202,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_u32(visitor),
             Value::Integer(i) => *i as u32,
             Value::Double(d) => *d as u32,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<u32>()?,
-            Value::SimpleString(s) => s.parse::<u32>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<u32>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -216,11 +225,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_u64(visitor),
             Value::Integer(i) => *i as u64,
             Value::Double(d) => *d as u64,
             Value::Nil => 0,
             Value::BulkString(s) => str::from_utf8(s)?.parse::<u64>()?,
-            Value::SimpleString(s) => s.parse::<u64>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<u64>()?,
             Value::Array(a) if a.len() == 1 => u64::deserialize(&a[0])?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
@@ -239,11 +249,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_f32(visitor),
             Value::Integer(i) => *i as f32,
             Value::Double(d) => *d as f32,
             Value::BulkString(bs) => str::from_utf8(bs)?.parse::<f32>()?,
             Value::Nil => 0.,
-            Value::SimpleString(s) => s.parse::<f32>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<f32>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -261,11 +272,12 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_f64(visitor),
             Value::Integer(i) => *i as f64,
             Value::Double(d) => *d,
             Value::BulkString(bs) => str::from_utf8(bs)?.parse::<f64>()?,
             Value::Nil => 0.,
-            Value::SimpleString(s) => s.parse::<f64>()?,
+            Value::SimpleString(s) | Value::BigNumber(s) => s.parse::<f64>()?,
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -283,6 +295,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result: char = match self {
+            Value::Attribute { value, .. } => return value.deserialize_char(visitor),
             Value::BulkString(bs) => {
                 let str = str::from_utf8(bs)?;
                 if str.len() == 1 {
@@ -291,13 +304,7 @@ impl<'de> Deserializer<'de> for &'de Value {
                     return Err(Error::Client("Cannot parse to char".to_owned()));
                 }
             }
-            Value::SimpleString(str) => {
-                if str.len() == 1 {
-                    str.chars().next().unwrap()
-                } else {
-                    return Err(Error::Client("Cannot parse to char".to_owned()));
-                }
-            }
+            Value::SimpleString(str) if str.len() == 1 => str.chars().next().unwrap(),
             Value::Nil => '\0',
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => return Err(Error::Client("Cannot parse to char".to_owned())),
@@ -311,9 +318,10 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_str(visitor),
             Value::BulkString(s) => str::from_utf8(s)?,
             Value::Nil => "",
-            Value::SimpleString(s) => s.as_str(),
+            Value::SimpleString(s) | Value::BigNumber(s) => s.as_str(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -332,10 +340,11 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_string(visitor),
             Value::Double(d) => d.to_string(),
             Value::BulkString(s) => str::from_utf8(s)?.to_owned(),
             Value::Nil => String::from(""),
-            Value::SimpleString(s) => s.clone(),
+            Value::SimpleString(s) | Value::BigNumber(s) => s.clone(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -353,9 +362,10 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_bytes(visitor),
             Value::BulkString(s) => s.as_slice(),
             Value::Nil => &[],
-            Value::SimpleString(s) => s.as_bytes(),
+            Value::SimpleString(s) | Value::BigNumber(s) => s.as_bytes(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -373,9 +383,10 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         let result = match self {
+            Value::Attribute { value, .. } => return value.deserialize_byte_buf(visitor),
             Value::BulkString(s) => s.clone(),
             Value::Nil => vec![],
-            Value::SimpleString(s) => s.as_bytes().to_vec(),
+            Value::SimpleString(s) | Value::BigNumber(s) => s.as_bytes().to_vec(),
             Value::Error(e) => return Err(Error::Redis(e.clone())),
             _ => {
                 return Err(Error::Client(format!(
@@ -394,6 +405,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         match self {
+            Value::Attribute { value, .. } => value.deserialize_option(visitor),
             Value::Nil => visitor.visit_none(),
             Value::Array(values) if values.is_empty() => visitor.visit_none(),
             Value::Error(e) => Err(Error::Redis(e.clone())),
@@ -407,6 +419,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         match self {
+            Value::Attribute { value, .. } => value.deserialize_unit(visitor),
             Value::Nil => visitor.visit_unit(),
             Value::Integer(_) => visitor.visit_unit(),
             Value::SimpleString(_) => visitor.visit_unit(),
@@ -444,6 +457,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         match self {
+            Value::Attribute { value, .. } => value.deserialize_seq(visitor),
             Value::Nil => visitor.visit_seq(NilSeqAccess),
             Value::Array(values) | Value::Set(values) | Value::Push(values) => {
                 visitor.visit_seq(SeqAccess::new(values))
@@ -482,6 +496,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         V: Visitor<'de>,
     {
         match self {
+            Value::Attribute { value, .. } => value.deserialize_map(visitor),
             Value::Array(values) => visitor.visit_map(SeqAccess::new(values)),
             Value::Map(values) => visitor.visit_map(MapAccess::new(values)),
             Value::Error(e) => Err(Error::Redis(e.clone())),
@@ -491,7 +506,7 @@ impl<'de> Deserializer<'de> for &'de Value {
 
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
@@ -509,6 +524,7 @@ impl<'de> Deserializer<'de> for &'de Value {
         }
 
         match self {
+            Value::Attribute { value, .. } => value.deserialize_struct(name, fields, visitor),
             Value::Array(values) => {
                 if check_resp2_array(values, fields) {
                     visitor.visit_map(SeqAccess::new(values))
@@ -525,19 +541,20 @@ impl<'de> Deserializer<'de> for &'de Value {
     fn deserialize_enum<V>(
         self,
         name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
         match self {
+            Value::Attribute { value, .. } => value.deserialize_enum(name, variants, visitor),
             Value::BulkString(bs) => {
                 // Visit a unit variant.
                 let str = str::from_utf8(bs)?;
                 visitor.visit_enum(str.into_deserializer())
             }
-            Value::SimpleString(str) => {
+            Value::SimpleString(str) | Value::BigNumber(str) => {
                 // Visit a unit variant.
                 visitor.visit_enum(str.as_str().into_deserializer())
             }
@@ -1,4 +1,7 @@
-use crate::resp::{Value, ERROR_FAKE_FIELD, PUSH_FAKE_FIELD, SET_FAKE_FIELD};
+use crate::resp::{
+    Value, ATTRIBUTE_FAKE_FIELD, BIG_NUMBER_FAKE_FIELD, ERROR_FAKE_FIELD, PUSH_FAKE_FIELD,
+    SET_FAKE_FIELD,
+};
 use serde::{
     ser::{SerializeMap, SerializeSeq, SerializeTupleStruct},
     Serialize,
@@ -46,6 +49,13 @@ impl Serialize for Value {
             Value::Error(e) => {
                 serializer.serialize_newtype_struct(ERROR_FAKE_FIELD, e.to_string().as_str())
             }
+            Value::BigNumber(n) => serializer.serialize_newtype_struct(BIG_NUMBER_FAKE_FIELD, n),
+            Value::Attribute { attributes, value } => {
+                let mut ts = serializer.serialize_tuple_struct(ATTRIBUTE_FAKE_FIELD, 2)?;
+                ts.serialize_field(attributes)?;
+                ts.serialize_field(value)?;
+                ts.end()
+            }
             Value::Nil => serializer.serialize_unit(),
         }
     }
@@ -1,10 +1,10 @@
 use bytes::BytesMut;
 use tokio_util::codec::Decoder;
 
-use crate::{resp::BufferDecoder, Result};
+use crate::{client::ReplyLimits, resp::BufferDecoder, Error, Result};
 
 fn decode(str: &str) -> Result<Option<Vec<u8>>> {
-    let mut buffer_decoder = BufferDecoder;
+    let mut buffer_decoder = BufferDecoder::default();
     let mut buf: BytesMut = str.into();
     buffer_decoder
         .decode(&mut buf)
@@ -211,3 +211,289 @@ fn map() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn streamed_string() -> Result<()> {
+    let result = decode("$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n;1\r\nd\r\n;0\r\n")?;
+    assert_eq!(Some("$10\r\nHello word\r\n".as_bytes().to_vec()), result);
+
+    let result = decode("$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n")?;
+    assert_eq!(None, result);
+
+    Ok(())
+}
+
+#[test]
+fn streamed_aggregate() -> Result<()> {
+    let result = decode("*?\r\n:1\r\n:2\r\n:3\r\n.\r\n")?;
+    assert_eq!(Some("*3\r\n:1\r\n:2\r\n:3\r\n".as_bytes().to_vec()), result);
+
+    let result = decode("%?\r\n+a\r\n:1\r\n+b\r\n*?\r\n:2\r\n.\r\n.\r\n")?;
+    assert_eq!(
+        Some("%2\r\n+a\r\n:1\r\n+b\r\n*1\r\n:2\r\n".as_bytes().to_vec()),
+        result
+    );
+
+    // attributes and the value they precede count as a single element
+    let result = decode("*?\r\n|1\r\n+ttl\r\n:10\r\n:1\r\n$?\r\n;1\r\na\r\n;0\r\n.\r\n")?;
+    assert_eq!(
+        Some(
+            "*2\r\n|1\r\n+ttl\r\n:10\r\n:1\r\n$1\r\na\r\n"
+                .as_bytes()
+                .to_vec()
+        ),
+        result
+    );
+
+    let result = decode("*?\r\n:1\r\n:2\r\n")?;
+    assert_eq!(None, result);
+
+    Ok(())
+}
+
+#[test]
+fn attribute() -> Result<()> {
+    let result = decode("|1\r\n+ttl\r\n:10\r\n+OK\r\n")?;
+    assert_eq!(
+        Some("|1\r\n+ttl\r\n:10\r\n+OK\r\n".as_bytes().to_vec()),
+        result
+    );
+
+    let result = decode("|1\r\n+ttl\r\n:10\r\n")?;
+    assert_eq!(None, result);
+
+    Ok(())
+}
+
+#[test]
+fn big_number() -> Result<()> {
+    let result = decode("(3492890328409238509324850943850943825024385\r\n")?;
+    assert_eq!(
+        Some(
+            "(3492890328409238509324850943850943825024385\r\n"
+                .as_bytes()
+                .to_vec()
+        ),
+        result
+    );
+
+    Ok(())
+}
+
+#[test]
+fn incremental() -> Result<()> {
+    let frame = "*3\r\n$5\r\nhello\r\n%1\r\n+a\r\n*?\r\n:1\r\n.\r\n|1\r\n+ttl\r\n:10\r\n$?\r\n;2\r\nhe\r\n;0\r\n";
+    let mut buffer_decoder = BufferDecoder::default();
+    let mut buf = BytesMut::new();
+
+    // byte by byte, with the parse state kept between calls
+    for (i, byte) in frame.bytes().enumerate() {
+        buf.extend_from_slice(&[byte]);
+        let result = buffer_decoder.decode(&mut buf)?;
+        if i < frame.len() - 1 {
+            assert!(result.is_none());
+        } else {
+            assert_eq!(
+                Some(
+                    "*3\r\n$5\r\nhello\r\n%1\r\n+a\r\n*1\r\n:1\r\n|1\r\n+ttl\r\n:10\r\n$2\r\nhe\r\n"
+                        .as_bytes()
+                        .to_vec()
+                ),
+                result.map(|b| b.to_vec())
+            );
+        }
+    }
+    assert!(buf.is_empty());
+
+    // several frames in the same buffer
+    let mut buf: BytesMut = "+OK\r\n:12\r\n*1\r\n$1\r\na".into();
+    let result = buffer_decoder.decode(&mut buf)?;
+    assert_eq!(Some(b"+OK\r\n".to_vec()), result.map(|b| b.to_vec()));
+    let result = buffer_decoder.decode(&mut buf)?;
+    assert_eq!(Some(b":12\r\n".to_vec()), result.map(|b| b.to_vec()));
+    let result = buffer_decoder.decode(&mut buf)?;
+    assert!(result.is_none());
+    buf.extend_from_slice(b"\r\n");
+    let result = buffer_decoder.decode(&mut buf)?;
+    assert_eq!(
+        Some(b"*1\r\n$1\r\na\r\n".to_vec()),
+        result.map(|b| b.to_vec())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn null_aggregates() -> Result<()> {
+    let result = decode("*-1\r\n")?;
+    assert_eq!(Some(b"*-1\r\n".to_vec()), result);
+
+    let result = decode("*0\r\n")?;
+    assert_eq!(Some(b"*0\r\n".to_vec()), result);
+
+    let result = decode("$-1\r\n")?;
+    assert_eq!(Some(b"$-1\r\n".to_vec()), result);
+
+    Ok(())
+}
+
+fn decode_with_limits(str: &str, limits: ReplyLimits) -> Result<Option<Vec<u8>>> {
+    let mut buffer_decoder = BufferDecoder::with_limits(limits);
+    let mut buf: BytesMut = str.into();
+    buffer_decoder
+        .decode(&mut buf)
+        .map(|b| b.map(|b| b.to_vec()))
+}
+
+#[test]
+fn reply_limits() -> Result<()> {
+    let limits = ReplyLimits {
+        max_bulk_size: 5,
+        max_aggregate_len: 2,
+        max_depth: 2,
+        max_reply_size: 32,
+    };
+
+    assert!(decode_with_limits("$5\r\nhello\r\n", limits)?.is_some());
+    // checked before the content is received
+    assert!(matches!(
+        decode_with_limits("$6\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+    assert!(matches!(
+        decode_with_limits("$?\r\n;6\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+    // the chunks of a streamed string are bounded as a whole
+    assert!(decode_with_limits("$?\r\n;3\r\nhel\r\n;2\r\nlo\r\n;0\r\n", limits)?.is_some());
+    assert!(matches!(
+        decode_with_limits("$?\r\n;3\r\nhel\r\n;3\r\nlo!\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+
+    assert!(decode_with_limits("*2\r\n:1\r\n:2\r\n", limits)?.is_some());
+    assert!(matches!(
+        decode_with_limits("*3\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+    assert!(matches!(
+        decode_with_limits("%3\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+
+    assert!(decode_with_limits("*1\r\n*1\r\n:1\r\n", limits)?.is_some());
+    assert!(matches!(
+        decode_with_limits("*1\r\n*1\r\n*1\r\n", limits),
+        Err(Error::Protocol(_))
+    ));
+
+    assert!(matches!(
+        decode_with_limits("*2\r\n$5\r\nhello\r\n$5\r\nhello\r\n", limits),
+        Ok(Some(_))
+    ));
+    assert!(matches!(
+        decode_with_limits(
+            "*2\r\n$5\r\nhello\r\n$5\r\nhello\r\n$5\r\nhello\r\n",
+            limits
+        ),
+        Ok(Some(_))
+    ));
+    assert!(matches!(
+        decode_with_limits(
+            "*2\r\n$5\r\nhello\r\n*2\r\n$5\r\nhello\r\n$5\r\nhello\r\n",
+            limits
+        ),
+        Err(Error::Protocol(_))
+    ));
+    assert!(matches!(
+        decode_with_limits(&format!("+{}", "a".repeat(40)), limits),
+        Err(Error::Protocol(_))
+    ));
+
+    // unlimited
+    let limits = ReplyLimits {
+        max_bulk_size: 0,
+        max_aggregate_len: 0,
+        max_depth: 0,
+        max_reply_size: 0,
+    };
+    assert!(decode_with_limits("*1\r\n*1\r\n*1\r\n:1\r\n", limits)?.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn absurd_lengths() -> Result<()> {
+    let max = usize::MAX;
+    let half = usize::MAX / 2 + 1;
+
+    for limits in [
+        ReplyLimits::default(),
+        ReplyLimits {
+            max_bulk_size: 0,
+            max_aggregate_len: 0,
+            max_depth: 0,
+            max_reply_size: 0,
+        },
+    ] {
+        // an aggregate length is only a count of the values still to be received
+        assert!(decode_with_limits(&format!("*{max}\r\n"), limits)?.is_none());
+        assert!(decode_with_limits(&format!("*1\r\n%{}\r\n", half - 1), limits)?.is_none());
+
+        for frame in [
+            format!("*1\r\n%{half}\r\n"),
+            format!("|{half}\r\n"),
+            format!("${max}\r\n"),
+            format!("!{max}\r\n"),
+            format!("$?\r\n;1\r\na\r\n;{max}\r\n"),
+        ] {
+            let result = decode_with_limits(&frame, limits);
+            assert!(
+                matches!(result, Err(Error::Protocol(_))),
+                "{frame:?}: {result:?}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn malformed_frames() {
+    for frame in [
+        "$abc\r\n",
+        "*x\r\n",
+        "@1\r\n",
+        "$?\r\n:1\r\n",
+        ".\r\n",
+        // content longer than the announced length
+        "$3\r\nhello\r\n",
+        "$?\r\n;3\r\nhello\r\n",
+    ] {
+        let result = decode_with_limits(frame, ReplyLimits::default());
+        assert!(
+            matches!(result, Err(Error::Protocol(_))),
+            "{frame:?}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn overflowing_lengths() {
+    let max = usize::MAX;
+    let half = usize::MAX / 2 + 1;
+    for frame in [
+        format!("%{half}\r\n"),
+        format!("*1\r\n%{half}\r\n"),
+        format!("|{half}\r\n"),
+        format!("${max}\r\n"),
+        format!("={max}\r\n"),
+        format!("$?\r\n;1\r\na\r\n;{max}\r\n"),
+    ] {
+        let result = decode(&frame);
+        assert!(
+            matches!(result, Err(Error::Protocol(_))),
+            "{frame:?}: {result:?}"
+        );
+    }
+}
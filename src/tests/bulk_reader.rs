@@ -0,0 +1,134 @@
+use crate::{
+    client::Client,
+    commands::StringCommands,
+    mock::MockServer,
+    resp::{BulkReader, CommandArgs},
+    tests::{log_try_init, start_get_set_cluster_server, start_get_set_server},
+    Result,
+};
+use bytes::Bytes;
+use serial_test::serial;
+use std::io::Cursor;
+
+fn received_values(server: &MockServer) -> Vec<Bytes> {
+    server
+        .received()
+        .into_iter()
+        .filter(|command| command.name == "SET")
+        .map(|command| command.args.bytes()[1].clone())
+        .collect()
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn large_bytes() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    let value = Bytes::from((0..1024 * 1024).map(|i| i as u8).collect::<Vec<_>>());
+    client.set("key1", value.clone()).await?;
+    client.set("key2", "small").await?;
+
+    assert_eq!(vec![value, Bytes::from("small")], received_values(&server));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn stream() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    let content = (0..1024 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+    let reader = BulkReader::new(Cursor::new(content.clone()), content.len());
+    client.set("key", reader.clone()).await?;
+
+    assert_eq!(vec![Bytes::from(content)], received_values(&server));
+
+    // the reader has been consumed
+    let result = client.set("key", reader).await;
+    assert!(result.is_err());
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn stream_too_short() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    let reader = BulkReader::new(Cursor::new(b"short".to_vec()), 100);
+    let result = client.set("key", reader).await;
+    assert!(result.is_err());
+
+    // the connection has been closed and reconnected
+    let value: String = client.get("key").retry_on_error(true).await?;
+    assert_eq!("value", value);
+    assert!(received_values(&server).is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn cluster() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_cluster_server().await?;
+    let client = Client::connect(format!("redis+cluster://{}", server.addr())).await?;
+
+    let value = Bytes::from((0..1024 * 1024).map(|i| i as u8).collect::<Vec<_>>());
+    client.set("key1", value.clone()).await?;
+
+    let content = (0..1024 * 1024).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+    let reader = BulkReader::new(Cursor::new(content.clone()), content.len());
+    client.set("key2", reader).await?;
+
+    assert_eq!(vec![value, Bytes::from(content)], received_values(&server));
+
+    Ok(())
+}
+
+#[test]
+fn deref() {
+    let mut args = CommandArgs::default();
+    args.arg("a").arg(Bytes::from_static(b"bc"));
+
+    // the shared buffers of the arguments are seen without copy
+    let shared: &[Bytes] = &args;
+    assert_eq!(
+        vec![Bytes::from_static(b"a"), Bytes::from_static(b"bc")],
+        shared
+    );
+    assert_eq!(b"bc", args[1].as_ref());
+
+    args.arg("d");
+    assert_eq!(3, args.len());
+    assert_eq!(Bytes::from_static(b"d"), args.bytes()[2]);
+}
+
+#[test]
+fn retain() {
+    let reader = |content: &[u8]| BulkReader::new(Cursor::new(content.to_vec()), content.len());
+    let mut args = CommandArgs::default();
+    args.arg("a").arg(reader(b"xyz")).arg("b").arg(reader(b"z"));
+
+    args.retain(|arg| arg != b"a");
+    let indices = args.readers().iter().map(|(i, _)| *i).collect::<Vec<_>>();
+    assert_eq!(vec![0, 2], indices);
+
+    // streamed arguments are seen as empty
+    args.retain(|arg| !arg.is_empty());
+    assert_eq!(1, args.len());
+    assert!(args.readers().is_empty());
+}
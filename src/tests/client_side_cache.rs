@@ -0,0 +1,285 @@
+use crate::{
+    client::{
+        CacheLookup, Client, ClientSideCache, ClientSideCacheConfig, ClientSideCacheMode,
+        ClientSideCacheStats, IntoConfig,
+    },
+    commands::{
+        ClientKillOptions, ConnectionCommands, FlushingMode, HashCommands, ServerCommands,
+        StringCommands,
+    },
+    resp::{cmd, RespBuf},
+    sleep,
+    tests::{
+        get_cluster_test_client, get_default_config, get_default_host, get_test_client,
+        log_try_init,
+    },
+    Result,
+};
+use serial_test::serial;
+use std::time::Duration;
+
+fn lookup(cache: &ClientSideCache, key: &str, value: &str) -> bool {
+    match cache.get(&cmd("GET").arg(key)) {
+        CacheLookup::Hit(_) => true,
+        CacheLookup::Miss(pending_entry) => {
+            let reply = format!("${}\r\n{value}\r\n", value.len());
+            cache.insert(pending_entry, &RespBuf::from_slice(reply.as_bytes()));
+            false
+        }
+        CacheLookup::NotCacheable => panic!("GET must be cacheable"),
+    }
+}
+
+#[test]
+fn cache_invalidation() {
+    let cache = ClientSideCache::new(ClientSideCacheConfig::default(), 0);
+
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(lookup(&cache, "key1", "value1"));
+    assert!(!lookup(&cache, "key2", "value2"));
+    assert!(matches!(
+        cache.get(&cmd("SET").arg("key1").arg("value")),
+        CacheLookup::NotCacheable
+    ));
+
+    // invalidation of a key
+    assert!(cache.handle_push_message(&RespBuf::from_slice(
+        b">2\r\n$10\r\ninvalidate\r\n*1\r\n$4\r\nkey1\r\n"
+    )));
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(lookup(&cache, "key2", "value2"));
+
+    // flush everything
+    assert!(cache.handle_push_message(&RespBuf::from_slice(b">2\r\n$10\r\ninvalidate\r\n_\r\n")));
+    assert!(!lookup(&cache, "key2", "value2"));
+
+    // not an invalidation message
+    assert!(!cache.handle_push_message(&RespBuf::from_slice(
+        b">3\r\n$7\r\nmessage\r\n$7\r\nchannel\r\n$7\r\npayload\r\n"
+    )));
+
+    // invalidation while waiting for the reply
+    let CacheLookup::Miss(pending_entry) = cache.get(&cmd("MGET").arg("key3").arg("key4")) else {
+        panic!("MGET must be a cache miss");
+    };
+    cache.invalidate(b"key4");
+    cache.insert(pending_entry, &RespBuf::from_slice(b"*2\r\n_\r\n_\r\n"));
+    assert!(matches!(
+        cache.get(&cmd("MGET").arg("key3").arg("key4")),
+        CacheLookup::Miss(_)
+    ));
+
+    let stats = cache.stats();
+    assert_eq!(2, stats.hits);
+    assert_eq!(1, stats.entries);
+}
+
+#[test]
+fn cache_select() {
+    let cache = ClientSideCache::new(ClientSideCacheConfig::default(), 0);
+
+    assert!(!lookup(&cache, "key", "value0"));
+    assert!(lookup(&cache, "key", "value0"));
+
+    // the same command in another database is another entry
+    cache.select(1);
+    assert!(!lookup(&cache, "key", "value1"));
+    assert!(lookup(&cache, "key", "value1"));
+
+    // a reply pending while another database is selected is not stored
+    let CacheLookup::Miss(pending_entry) = cache.get(&cmd("GET").arg("other_key")) else {
+        panic!("GET must be a cache miss");
+    };
+    cache.select(0);
+    cache.insert(pending_entry, &RespBuf::from_slice(b"$5\r\nvalue\r\n"));
+    cache.select(1);
+    assert!(matches!(
+        cache.get(&cmd("GET").arg("other_key")),
+        CacheLookup::Miss(_)
+    ));
+
+    // invalidation messages apply to all the databases
+    cache.invalidate(b"key");
+    assert!(!lookup(&cache, "key", "value1"));
+    cache.select(0);
+    assert!(!lookup(&cache, "key", "value0"));
+
+    // the initial database is selected again after a reconnection
+    cache.select(2);
+    cache.flush();
+    assert!(!lookup(&cache, "key", "value0"));
+    assert!(lookup(&cache, "key", "value0"));
+}
+
+#[test]
+fn cache_limits() {
+    let cache = ClientSideCache::new(
+        ClientSideCacheConfig {
+            max_entries: 2,
+            ..Default::default()
+        },
+        0,
+    );
+
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(!lookup(&cache, "key2", "value2"));
+    assert!(!lookup(&cache, "key3", "value3"));
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(lookup(&cache, "key3", "value3"));
+    assert_eq!(2, cache.stats().entries);
+
+    // database + GET + key + bulk string reply
+    let cache = ClientSideCache::new(
+        ClientSideCacheConfig {
+            max_bytes: 30,
+            ..Default::default()
+        },
+        0,
+    );
+
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(!lookup(&cache, "key2", "value2"));
+    assert!(lookup(&cache, "key2", "value2"));
+    assert_eq!(1, cache.stats().entries);
+    assert_eq!(1, cache.stats().evictions);
+
+    let cache = ClientSideCache::new(
+        ClientSideCacheConfig {
+            ttl: Some(Duration::ZERO),
+            ..Default::default()
+        },
+        0,
+    );
+
+    assert!(!lookup(&cache, "key1", "value1"));
+    assert!(!lookup(&cache, "key1", "value1"));
+
+    let cache = ClientSideCache::new(
+        ClientSideCacheConfig {
+            mode: ClientSideCacheMode::Broadcast {
+                prefixes: vec!["user:".to_owned()],
+            },
+            ..Default::default()
+        },
+        0,
+    );
+
+    assert!(!lookup(&cache, "user:1", "John"));
+    assert!(lookup(&cache, "user:1", "John"));
+    assert!(matches!(
+        cache.get(&cmd("GET").arg("order:1")),
+        CacheLookup::NotCacheable
+    ));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_side_cache() -> Result<()> {
+    log_try_init();
+    let regular_client = get_test_client().await?;
+    regular_client.flushdb(FlushingMode::Sync).await?;
+
+    for mode in [
+        ClientSideCacheMode::Default,
+        ClientSideCacheMode::OptIn,
+        ClientSideCacheMode::Broadcast { prefixes: vec![] },
+    ] {
+        let mut config = get_default_config()?;
+        "cached".clone_into(&mut config.connection_name);
+        config.client_side_cache = Some(ClientSideCacheConfig {
+            mode,
+            ..Default::default()
+        });
+        let client = Client::connect(config).await?;
+
+        regular_client.set("key", "value").await?;
+        regular_client.hset("hash", ("field", "value")).await?;
+
+        let value: String = client.get("key").await?;
+        assert_eq!("value", value);
+        let value: String = client.get("key").await?;
+        assert_eq!("value", value);
+        let _: Vec<(String, String)> = client.hgetall("hash").await?;
+        let _: Vec<(String, String)> = client.hgetall("hash").await?;
+
+        assert_eq!(
+            Some(ClientSideCacheStats {
+                hits: 2,
+                misses: 2,
+                entries: 2,
+                ..client.client_side_cache_stats().unwrap()
+            }),
+            client.client_side_cache_stats()
+        );
+
+        // invalidation from another client
+        regular_client.set("key", "new_value").await?;
+        // make sure the invalidation message has been received
+        client.ping::<()>(Default::default()).await?;
+
+        let value: String = client.get("key").await?;
+        assert_eq!("new_value", value);
+        assert_eq!(1, client.client_side_cache_stats().unwrap().invalidations);
+
+        // flushed on reconnection
+        regular_client
+            .client_kill(ClientKillOptions::default().laddr(client.client_info().await?.laddr))
+            .await?;
+        let value: String = client.get("key").await?;
+        assert_eq!("new_value", value);
+        let value: String = client.get("key").await?;
+        assert_eq!("new_value", value);
+        regular_client.set("key", "value").await?;
+        client.ping::<()>(Default::default()).await?;
+        let value: String = client.get("key").await?;
+        assert_eq!("value", value);
+
+        client.close().await?;
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_side_cache_opt_in_on_cluster() -> Result<()> {
+    let regular_client = get_cluster_test_client().await?;
+    regular_client.flushall(FlushingMode::Sync).await?;
+
+    let host = get_default_host();
+    let mut config =
+        format!("redis+cluster://{host}:7000,{host}:7001,{host}:7002").into_config()?;
+    config.client_side_cache = Some(ClientSideCacheConfig {
+        mode: ClientSideCacheMode::OptIn,
+        ..Default::default()
+    });
+    let client = Client::connect(config).await?;
+
+    // keys owned by the 3 nodes
+    let keys = ["key0", "key1", "key2"];
+    for key in keys {
+        regular_client.set(key, "value").await?;
+        let value: String = client.get(key).await?;
+        assert_eq!("value", value);
+        let value: String = client.get(key).await?;
+        assert_eq!("value", value);
+    }
+    assert_eq!(3, client.client_side_cache_stats().unwrap().hits);
+
+    // each key is tracked by the node owning it
+    for key in keys {
+        regular_client.set(key, "new_value").await?;
+    }
+    sleep(Duration::from_millis(100)).await;
+    for key in keys {
+        let value: String = client.get(key).await?;
+        assert_eq!("new_value", value);
+    }
+    assert_eq!(3, client.client_side_cache_stats().unwrap().invalidations);
+
+    client.close().await?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use crate::{
+    resp::{cmd, flush_chunks, BulkBytes, BulkReader, CommandArgs, CommandEncoder, WriteChunk},
+    Result,
+};
+use bytes::{Bytes, BytesMut};
+use std::sync::Arc;
+use tokio_util::codec::Encoder;
+
+#[test]
+fn shared_args() {
+    let bytes = Bytes::from(vec![1u8; 100]);
+    let arc: Arc<[u8]> = Arc::from(vec![2u8; 100]);
+    let bulk_bytes = BulkBytes::new(Bytes::from(vec![3u8; 100]));
+    let vec = vec![4u8; 100];
+
+    let mut args = CommandArgs::default();
+    args.arg(bytes.clone())
+        .arg(arc.clone())
+        .arg(bulk_bytes.clone())
+        .arg(vec.clone());
+
+    // shared
+    assert_eq!(bytes.as_ptr(), args.bytes()[0].as_ptr());
+    assert_eq!(arc.as_ptr(), args.bytes()[1].as_ptr());
+    assert_eq!(bulk_bytes.as_ptr(), args.bytes()[2].as_ptr());
+    // copied
+    assert_ne!(vec.as_ptr(), args.bytes()[3].as_ptr());
+    assert_eq!(vec, args.bytes()[3]);
+
+    // shared when args are appended to other args
+    let mut other_args = CommandArgs::default();
+    other_args.arg("first").arg(&args);
+    assert_eq!(bytes.as_ptr(), other_args.bytes()[1].as_ptr());
+}
+
+fn encode_chunks(args: CommandArgs) -> (Vec<WriteChunk>, BytesMut) {
+    let command = cmd("SET").arg(args);
+    let mut buffer = BytesMut::new();
+    let mut chunks = Vec::new();
+    CommandEncoder.encode_chunks(&command, &mut buffer, &mut chunks);
+    flush_chunks(&mut buffer, &mut chunks);
+
+    let mut contiguous = BytesMut::new();
+    if command.args.readers().is_empty() {
+        CommandEncoder.encode(&command, &mut contiguous).unwrap();
+    }
+    (chunks, contiguous)
+}
+
+fn concat(chunks: &[WriteChunk]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for chunk in chunks {
+        match chunk {
+            WriteChunk::Bytes(b) => bytes.extend_from_slice(b),
+            WriteChunk::Reader(_) => bytes.extend_from_slice(b"<reader>"),
+        }
+    }
+    bytes
+}
+
+#[test]
+fn small_args_are_copied() {
+    let (chunks, contiguous) =
+        encode_chunks(CommandArgs::default().arg("key").arg("value").build());
+    assert_eq!(1, chunks.len());
+    assert_eq!(
+        b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n".to_vec(),
+        concat(&chunks)
+    );
+    assert_eq!(contiguous.to_vec(), concat(&chunks));
+}
+
+#[test]
+fn large_args_are_not_copied() {
+    let value = Bytes::from(vec![b'x'; 100 * 1024]);
+    let (chunks, contiguous) = encode_chunks(
+        CommandArgs::default()
+            .arg("key")
+            .arg(value.clone())
+            .arg("GET")
+            .build(),
+    );
+
+    assert_eq!(3, chunks.len());
+    let WriteChunk::Bytes(bytes) = &chunks[1] else {
+        panic!("expected value chunk");
+    };
+    assert_eq!(value.as_ptr(), bytes.as_ptr());
+    assert_eq!(contiguous.to_vec(), concat(&chunks));
+}
+
+#[test]
+fn streamed_args() -> Result<()> {
+    let reader = BulkReader::new(std::io::Cursor::new(b"value".to_vec()), 5);
+    let args = CommandArgs::default().arg("key").arg(reader).build();
+    assert_eq!(
+        r#"CommandArgs { args: ["key", "<5 streamed bytes>"] }"#,
+        format!("{args:?}")
+    );
+
+    let (chunks, _) = encode_chunks(args);
+    assert_eq!(3, chunks.len());
+    assert_eq!(
+        b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\n<reader>\r\n".to_vec(),
+        concat(&chunks)
+    );
+
+    // a streamed argument cannot be buffered
+    let command = cmd("SET")
+        .arg("key")
+        .arg(BulkReader::new(tokio::io::empty(), 0));
+    assert!(CommandEncoder
+        .encode(&command, &mut BytesMut::new())
+        .is_err());
+
+    Ok(())
+}
@@ -163,6 +163,12 @@ fn into_config() -> Result<()> {
             .into_config()?
             .to_string()
     );
+    assert_eq!(
+        "redis://127.0.0.1?pub_sub_capacity=100&pub_sub_overflow_policy=backpressure",
+        "redis://127.0.0.1?pub_sub_capacity=100&pub_sub_overflow_policy=backpressure"
+            .into_config()?
+            .to_string()
+    );
     assert_eq!(
         "redis+sentinel://127.0.0.1:6379,127.0.0.1:6380,127.0.0.1:6381/myservice/1",
         "redis+sentinel://127.0.0.1:6379,127.0.0.1:6380,127.0.0.1:6381/myservice/1"
@@ -225,6 +231,41 @@ fn into_config() -> Result<()> {
             .to_string()
     );
 
+    assert_eq!(
+        "redis+sentinel://127.0.0.1:6379/myservice?sentinel_password=bar&read_from_replicas=lowest_lag",
+        "redis+sentinel://127.0.0.1:6379/myservice?sentinel_password=bar&read_from_replicas=lowest_lag"
+            .into_config()?
+            .to_string()
+    );
+
+    assert_eq!(
+        "redis+sentinel://127.0.0.1:6379/myservice?sentinel_connect_timeout=500&read_from_replicas=random",
+        "redis+sentinel://127.0.0.1:6379/myservice?read_from_replicas=random&sentinel_connect_timeout=500"
+            .into_config()?
+            .to_string()
+    );
+
+    assert_eq!(
+        "redis+sentinel://127.0.0.1:6379/myservice?read_from_replicas=round_robin",
+        "redis+sentinel://127.0.0.1:6379/myservice?read_from_replicas=round_robin"
+            .into_config()?
+            .to_string()
+    );
+
+    assert_eq!(
+        "redis+replicated://127.0.0.1:6381,127.0.0.1:6382",
+        "redis-replicated://127.0.0.1:6381,127.0.0.1:6382"
+            .into_config()?
+            .to_string()
+    );
+
+    assert_eq!(
+        "redis://127.0.0.1?max_bulk_size=1024&max_aggregate_len=100&max_depth=8&max_reply_size=4096",
+        "redis://127.0.0.1?max_reply_size=4096&max_depth=8&max_aggregate_len=100&max_bulk_size=1024"
+            .into_config()?
+            .to_string()
+    );
+
     assert!("127.0.0.1:xyz".into_config().is_err());
     assert!("redis://127.0.0.1:xyz".into_config().is_err());
     assert!("redis://username@127.0.0.1".into_config().is_err());
@@ -68,8 +68,9 @@ async fn client_getredir() -> Result<()> {
     Ok(())
 }
 
-#[cfg_attr(feature = "tokio-runtime", tokio::main)]
-#[cfg_attr(feature = "async-std-runtime", async_std::main)]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
 async fn client_help() -> Result<()> {
     let client = get_test_client().await?;
     let result: Vec<String> = client.client_help().await?;
@@ -117,6 +118,133 @@ async fn client_kill() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_tracking_after_reconnection() -> Result<()> {
+    log_try_init();
+    let client1 = get_test_client().await?;
+    let client2 = get_test_client().await?;
+
+    let mut invalidation_stream = client1.create_client_tracking_invalidation_stream()?;
+
+    client1
+        .client_tracking(ClientTrackingStatus::On, ClientTrackingOptions::default())
+        .await?;
+
+    let client_id = client1.client_id().await?;
+    client2
+        .client_kill(ClientKillOptions::default().id(client_id))
+        .await?;
+
+    // all the keys must be invalidated after the reconnection
+    let keys_to_invalidate: Vec<String> = invalidation_stream.next().await.unwrap();
+    assert!(keys_to_invalidate.is_empty());
+
+    // tracking has been restored
+    client2.set("key", "value").await?;
+    let _value: String = client1.get("key").await?;
+    client2.set("key", "new_value").await?;
+
+    let keys_to_invalidate: Vec<String> = invalidation_stream.next().await.unwrap();
+    assert_eq!(vec!["key".to_owned()], keys_to_invalidate);
+
+    client1
+        .client_tracking(ClientTrackingStatus::Off, ClientTrackingOptions::default())
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_tracking_restore_failure() -> Result<()> {
+    use crate::{mock::MockServer, resp::Value};
+
+    log_try_init();
+    let server = MockServer::start().await?;
+    // without expectation, the client tracking cannot be restored after the reconnection
+    server
+        .on("CLIENT", ["TRACKING", "ON"])
+        .once()
+        .reply(Value::SimpleString("OK".to_owned()));
+
+    let client = Client::connect(server.uri()).await?;
+    let mut invalidation_stream = client.create_client_tracking_invalidation_stream()?;
+    client
+        .client_tracking(ClientTrackingStatus::On, ClientTrackingOptions::default())
+        .await?;
+
+    let mut reconnect_receiver = client.on_reconnect();
+    server.disconnect_all();
+    reconnect_receiver.recv().await.unwrap();
+
+    // the failure ends the invalidation stream
+    assert!(invalidation_stream.next().await.is_none());
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn client_tracking_with_client_side_cache() -> Result<()> {
+    use crate::{
+        client::{ClientSideCacheConfig, IntoConfig},
+        mock::MockServer,
+        resp::Value,
+    };
+
+    log_try_init();
+    let server = MockServer::start().await?;
+    server
+        .on_any("CLIENT")
+        .reply(Value::SimpleString("OK".to_owned()));
+
+    let mut config = server.uri().into_config()?;
+    config.client_side_cache = Some(ClientSideCacheConfig::default());
+    let client = Client::connect(config).await?;
+    let mut invalidation_stream = client.create_client_tracking_invalidation_stream()?;
+    client
+        .client_tracking(
+            ClientTrackingStatus::On,
+            ClientTrackingOptions::default().broadcasting(),
+        )
+        .await?;
+
+    let mut reconnect_receiver = client.on_reconnect();
+    server.disconnect_all();
+    reconnect_receiver.recv().await.unwrap();
+
+    // all the keys must be invalidated after the reconnection
+    let keys_to_invalidate: Vec<String> = invalidation_stream.next().await.unwrap();
+    assert!(keys_to_invalidate.is_empty());
+
+    // the options of the cache are restored, not those of the caller
+    let tracking_commands: Vec<_> = server
+        .received()
+        .into_iter()
+        .filter(|command| command.name == "CLIENT")
+        .map(|command| {
+            command
+                .args
+                .into_iter()
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    assert_eq!(
+        vec!["TRACKING ON", "TRACKING ON BCAST", "TRACKING ON"],
+        tracking_commands
+    );
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -415,7 +543,7 @@ async fn hello_v3() -> Result<()> {
 async fn ping() -> Result<()> {
     let client = get_test_client().await?;
 
-    client.ping(PingOptions::default()).await?;
+    client.ping::<()>(PingOptions::default()).await?;
     let result: String = client.ping(PingOptions::default().message("value")).await?;
     assert_eq!("value", result);
 
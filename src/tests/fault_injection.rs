@@ -0,0 +1,277 @@
+use crate::{
+    client::{Client, Fault, FaultRule, IntoConfig},
+    commands::StringCommands,
+    mock::MockServer,
+    resp::cmd,
+    tests::{log_try_init, start_get_set_cluster_server, start_get_set_server},
+    Error, RedisError, RedisErrorKind, Result,
+};
+use futures_util::join;
+use serial_test::serial;
+use std::{
+    future::IntoFuture,
+    time::{Duration, Instant},
+};
+
+fn count_received(server: &MockServer, name: &str) -> usize {
+    server
+        .received()
+        .iter()
+        .filter(|command| command.name == name)
+        .count()
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn kill_before_write() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::KillBeforeWrite).command("SET"));
+    let result = client.set("key", "value").await;
+    assert!(matches!(result, Err(Error::IO(_))));
+    assert_eq!(0, count_received(&server, "SET"));
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::KillBeforeWrite).command("SET"));
+    client.set("key", "value").retry_on_error(true).await?;
+    assert_eq!(1, count_received(&server, "SET"));
+    assert!(client.fault_plan().is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn kill_after_write() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::KillAfterWrite).command("SET"));
+    client.set("key", "value").retry_on_error(true).await?;
+
+    // executed twice: the reply of the first attempt has been lost
+    assert_eq!(2, count_received(&server, "SET"));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn delay_read() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::DelayRead(Duration::from_millis(200))));
+    let start = Instant::now();
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn drop_reply() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let mut config = server.uri().into_config()?;
+    config.command_timeout = Duration::from_millis(200);
+    let client = Client::connect(config).await?;
+
+    client.fault_plan().add(FaultRule::new(Fault::DropReply));
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn inject_errors() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let config = server.uri().into_config()?;
+    let fault_plan = config.fault_plan.clone();
+    let client = Client::connect(config).await?;
+
+    fault_plan.add(FaultRule::new(Fault::loading()).command("get"));
+    fault_plan.add(FaultRule::new(Fault::try_again()).command("get"));
+    fault_plan.add(FaultRule::new(Fault::moved(3999, ("127.0.0.1", 6381))).command("get"));
+
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Loading,
+            ..
+        }))
+    ));
+
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::TryAgain,
+            ..
+        }))
+    ));
+
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Moved {
+                hash_slot: 3999,
+                ..
+            },
+            ..
+        }))
+    ));
+
+    // the server did not execute the commands
+    assert_eq!(0, count_received(&server, "GET"));
+
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn fault_in_batch() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::loading()).command("GET"));
+
+    // the fault applies to the reply of the matched command only
+    let results = client
+        .send_batch(
+            vec![cmd("SET").arg("key").arg("value"), cmd("GET").arg("key")],
+            None,
+        )
+        .await?;
+    assert_eq!(2, results.len());
+    assert!(!results[0].is_error());
+    assert!(results[1].is_error());
+    assert_eq!(1, count_received(&server, "SET"));
+    assert_eq!(0, count_received(&server, "GET"));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn fault_with_concurrent_callers() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::loading()).command("GET"));
+
+    let (set_result, get_result) = join!(
+        client.set("key", "value").into_future(),
+        client.get::<_, String>("key").into_future(),
+    );
+    assert!(set_result.is_ok());
+    assert!(matches!(
+        get_result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Loading,
+            ..
+        }))
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn repeated_fault() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_server().await?;
+    let client = Client::connect(server.uri()).await?;
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::try_again()).command("GET").times(0));
+
+    for _ in 0..3 {
+        let result: Result<String> = client.get("key").await;
+        assert!(result.is_err());
+    }
+
+    // other commands are not impacted
+    client.set("key", "value").await?;
+
+    client.fault_plan().clear();
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn cluster() -> Result<()> {
+    log_try_init();
+    let server = start_get_set_cluster_server().await?;
+    let client = Client::connect(format!("redis+cluster://{}", server.addr())).await?;
+
+    // the command is retried on the node of the redirect
+    client.fault_plan().add(
+        FaultRule::new(Fault::moved(12539, ("127.0.0.1", server.addr().port()))).command("GET"),
+    );
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    assert_eq!(1, count_received(&server, "GET"));
+    assert!(client.fault_plan().is_empty());
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::try_again()).command("GET"));
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::TryAgain,
+            ..
+        }))
+    ));
+    assert_eq!(1, count_received(&server, "GET"));
+
+    client
+        .fault_plan()
+        .add(FaultRule::new(Fault::KillBeforeWrite).command("SET"));
+    client.set("key", "value").retry_on_error(true).await?;
+    assert_eq!(1, count_received(&server, "SET"));
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use crate::{
     },
     resp::Value,
     tests::get_test_client,
-    Result,
+    Error, Result,
 };
 use serial_test::serial;
 use std::{collections::HashSet, time::SystemTime};
@@ -677,3 +677,36 @@ async fn unlink() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn waitaof() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key", "value").await?;
+    let (num_local, num_replicas) = client.waitaof(0, 0, 100).await?;
+    assert_eq!(0, num_local);
+    assert_eq!(0, num_replicas);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn durable() -> Result<()> {
+    let client = get_test_client().await?;
+
+    client.set("key", "value").durable(0, 0, 100).await?;
+
+    // standalone server without replica
+    let result = client.set("key", "value").durable(1, 0, 100).await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    // the command has been executed anyway
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
@@ -0,0 +1,321 @@
+#[cfg(feature = "json")]
+use crate::resp::Json;
+use crate::{
+    resp::{BulkString, HashRecord, KeyPrefix},
+    Error, Result,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "mock-server")]
+use serial_test::serial;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Status {
+    Active,
+    Disabled,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u8,
+    score: f64,
+    admin: bool,
+    status: Status,
+    email: Option<String>,
+}
+
+impl KeyPrefix for User {
+    const PREFIX: &'static str = "user";
+}
+
+#[derive(Deserialize)]
+struct UserAge {
+    age: Option<u8>,
+}
+
+#[derive(Serialize)]
+struct UserPatch {
+    age: Option<u8>,
+    email: Option<String>,
+}
+
+fn fields(record: &HashRecord) -> Vec<(&str, &str)> {
+    record
+        .fields()
+        .iter()
+        .map(|(field, value)| (field.as_str(), std::str::from_utf8(value).unwrap()))
+        .collect()
+}
+
+fn user() -> User {
+    User {
+        name: "Alice".to_owned(),
+        age: 30,
+        score: 12.5,
+        admin: true,
+        status: Status::Active,
+        email: None,
+    }
+}
+
+#[test]
+fn struct_to_fields() -> Result<()> {
+    let record = HashRecord::new(&user())?;
+    assert_eq!(
+        vec![
+            ("name", "Alice"),
+            ("age", "30"),
+            ("score", "12.5"),
+            ("admin", "1"),
+            ("status", "Active"),
+        ],
+        fields(&record)
+    );
+
+    let mut args = crate::resp::CommandArgs::default();
+    args.arg(record);
+    assert_eq!(10, args.len());
+
+    Ok(())
+}
+
+#[test]
+fn fields_to_struct() -> Result<()> {
+    let record = HashRecord::new(&user())?;
+    let deserialized: User = record.to()?;
+    assert_eq!(user(), deserialized);
+
+    let record: HashRecord = HashRecord::from_fields([
+        ("name", Some(b"Bob".to_vec())),
+        ("age", Some(b"42".to_vec())),
+        ("score", Some(b"-1".to_vec())),
+        ("admin", Some(b"0".to_vec())),
+        ("status", Some(b"Disabled".to_vec())),
+        ("email", Some(b"bob@example.com".to_vec())),
+    ]);
+    let deserialized: User = record.to()?;
+    assert_eq!(
+        User {
+            name: "Bob".to_owned(),
+            age: 42,
+            score: -1.,
+            admin: false,
+            status: Status::Disabled,
+            email: Some("bob@example.com".to_owned()),
+        },
+        deserialized
+    );
+
+    // missing values are skipped, missing required fields are rejected
+    let record: HashRecord =
+        HashRecord::from_fields([("name", Some(b"Bob".to_vec())), ("other", None)]);
+    assert!(record.to::<User>().is_err());
+
+    let record: HashRecord = HashRecord::from_fields([("age", Some(b"7".to_vec()))]);
+    assert_eq!(Some(7), record.to::<UserAge>()?.age);
+
+    let record: HashRecord = HashRecord::from_fields([("age", Some(b"abc".to_vec()))]);
+    assert!(record.to::<UserAge>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn partial_update() -> Result<()> {
+    let patch = UserPatch {
+        age: Some(31),
+        email: None,
+    };
+    let record = HashRecord::new(&patch)?;
+    assert_eq!(vec![("age", "31")], fields(&record));
+
+    let record = HashRecord::new(&UserPatch {
+        age: None,
+        email: None,
+    })?;
+    assert!(record.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn map_record() -> Result<()> {
+    let map = HashMap::from([("field1".to_owned(), 1), ("field2".to_owned(), 2)]);
+    let record = HashRecord::new(&map)?;
+    let deserialized: HashMap<String, i32> = record.to()?;
+    assert_eq!(map, deserialized);
+
+    Ok(())
+}
+
+#[test]
+fn field_names() {
+    assert_eq!(
+        &["name", "age", "score", "admin", "status", "email"],
+        HashRecord::field_names::<User>()
+    );
+    assert!(HashRecord::field_names::<String>().is_empty());
+
+    let values = vec![
+        Some(BulkString::from(b"Carol")),
+        Some(BulkString::from(b"25")),
+        Some(BulkString::from(b"0")),
+        Some(BulkString::from(b"0")),
+        Some(BulkString::from(b"Active")),
+        None,
+    ];
+    let record: HashRecord = HashRecord::from_fields(
+        HashRecord::field_names::<User>()
+            .iter()
+            .copied()
+            .zip(values),
+    );
+    let user: User = record.to().unwrap();
+    assert_eq!("Carol", user.name);
+    assert_eq!(None, user.email);
+}
+
+#[test]
+fn key_prefix() {
+    assert_eq!("user:42", User::key(42));
+    assert_eq!("user:alice", User::key("alice"));
+}
+
+#[test]
+fn nested_without_wrapper() {
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Nested {
+        tags: Vec<String>,
+    }
+
+    let result = HashRecord::new(&Nested {
+        tags: vec!["a".to_owned()],
+    });
+    assert!(matches!(result, Err(Error::Client(e)) if e.contains("`tags`")));
+
+    let record = HashRecord::from_fields([("tags", Some(r#"["a"]"#))]);
+    assert!(record.to::<Nested>().is_err());
+
+    // only structs and maps can be records
+    assert!(HashRecord::new(&12).is_err());
+    assert!(HashRecord::new(&vec![1, 2]).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn nested_in_json() -> Result<()> {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Address {
+        city: String,
+        zip: u32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Role {
+        Guest,
+        Member { since: u16 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Profile {
+        name: String,
+        tags: Json<Vec<String>>,
+        address: Json<Address>,
+        role: Json<Role>,
+        guest: Role,
+    }
+
+    let profile = Profile {
+        name: "Alice".to_owned(),
+        tags: Json::new(vec!["a".to_owned(), "b".to_owned()])?,
+        address: Json::new(Address {
+            city: "Paris".to_owned(),
+            zip: 75001,
+        })?,
+        role: Json::new(Role::Member { since: 2020 })?,
+        guest: Role::Guest,
+    };
+
+    let record = HashRecord::new(&profile)?;
+    assert_eq!(
+        vec![
+            ("name", "Alice"),
+            ("tags", r#"["a","b"]"#),
+            ("address", r#"{"city":"Paris","zip":75001}"#),
+            ("role", r#"{"Member":{"since":2020}}"#),
+            ("guest", "Guest"),
+        ],
+        fields(&record)
+    );
+
+    let deserialized: Profile = record.to()?;
+    assert_eq!(profile, deserialized);
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn nested_in_msgpack() -> Result<()> {
+    use crate::resp::MsgPack;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Profile {
+        name: String,
+        tags: MsgPack<Vec<String>>,
+    }
+
+    let profile = Profile {
+        name: "Alice".to_owned(),
+        tags: MsgPack::new(vec!["a".to_owned(), "b".to_owned()])?,
+    };
+
+    let record = HashRecord::new(&profile)?;
+    let deserialized: Profile = record.to()?;
+    assert_eq!(profile, deserialized);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn hset_hgetall() -> Result<()> {
+    use crate::{client::Client, commands::HashCommands, mock::MockServer, resp::Value};
+
+    let server = MockServer::start().await?;
+    server.on_any("HSET").reply(Value::Integer(2));
+    server.on("HGETALL", "user:1").reply(Value::Map(
+        [
+            (
+                Value::BulkString(b"age".to_vec()),
+                Value::BulkString(b"31".to_vec()),
+            ),
+            (
+                Value::BulkString(b"name".to_vec()),
+                Value::BulkString(b"Alice".to_vec()),
+            ),
+        ]
+        .into(),
+    ));
+
+    let client = Client::connect(server.uri()).await?;
+    let patch = UserPatch {
+        age: Some(31),
+        email: None,
+    };
+    client.hset(User::key(1), HashRecord::new(&patch)?).await?;
+
+    let received = server.received();
+    let hset = received.iter().find(|c| c.name == "HSET").unwrap();
+    let args: Vec<&[u8]> = hset.args.iter().map(|a| a.as_ref()).collect();
+    assert_eq!(vec![&b"user:1"[..], b"age", b"31"], args);
+
+    let record: HashRecord = client.hgetall(User::key(1)).await?;
+    let patch: UserAge = record.to()?;
+    assert_eq!(Some(31), patch.age);
+
+    Ok(())
+}
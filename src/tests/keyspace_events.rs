@@ -0,0 +1,78 @@
+use crate::{
+    client::{KeyEvent, KeyEventKind, KeyspaceEventsFilter, PubSubMessage},
+    commands::{FlushingMode, GenericCommands, ServerCommands, StringCommands},
+    tests::get_test_client,
+    Result,
+};
+use futures_util::StreamExt;
+use serial_test::serial;
+
+#[test]
+fn key_event_parsing() -> Result<()> {
+    let event = KeyEvent::from_message(&PubSubMessage {
+        pattern: b"__keyspace@0__:*".to_vec(),
+        channel: b"__keyspace@0__:user:1".to_vec(),
+        payload: b"set".to_vec(),
+    })?;
+    assert_eq!(
+        KeyEvent {
+            db: 0,
+            key: b"user:1".to_vec(),
+            kind: KeyEventKind::Set
+        },
+        event
+    );
+
+    // keys may contain the separator
+    let event = KeyEvent::from_message(&PubSubMessage {
+        pattern: b"__keyspace@12__:*".to_vec(),
+        channel: b"__keyspace@12__:a__:b".to_vec(),
+        payload: b"hexpired".to_vec(),
+    })?;
+    assert_eq!(12, event.db);
+    assert_eq!(b"a__:b".to_vec(), event.key);
+    assert_eq!(KeyEventKind::Other("hexpired".to_owned()), event.kind);
+
+    assert!(KeyEvent::from_message(&PubSubMessage {
+        pattern: vec![],
+        channel: b"mychannel".to_vec(),
+        payload: b"set".to_vec(),
+    })
+    .is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn keyspace_events() -> Result<()> {
+    let client = get_test_client().await?;
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let mut events = client
+        .keyspace_events(
+            KeyspaceEventsFilter::default()
+                .key_pattern("user:*")
+                .notify_keyspace_events("KA"),
+        )
+        .await?;
+
+    client.set("other", "value").await?;
+    client.set("user:1", "John").await?;
+    client.del("user:1").await?;
+
+    let event = events.next().await.unwrap()?;
+    assert_eq!(0, event.db);
+    assert_eq!(b"user:1".to_vec(), event.key);
+    assert_eq!(KeyEventKind::Set, event.kind);
+
+    let event = events.next().await.unwrap()?;
+    assert_eq!(b"user:1".to_vec(), event.key);
+    assert_eq!(KeyEventKind::Del, event.kind);
+
+    events.close().await?;
+    client.config_set([("notify-keyspace-events", "")]).await?;
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use crate::{
+    client::Client,
+    commands::{ConnectionCommands, StringCommands},
+    mock::MockServer,
+    resp::{cmd, Value},
+    tests::log_try_init,
+    Error, RedisError, RedisErrorKind, Result,
+};
+use futures_util::StreamExt;
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn reply() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(b"value".to_vec()));
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+
+    let client = Client::connect(server.uri()).await?;
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    // no expectation for this key
+    let result: Result<String> = client.get("other_key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Err,
+            ..
+        }))
+    ));
+
+    let received = server.received();
+    assert!(received
+        .iter()
+        .any(|command| command.name == "SET" && command.args.len() == 2));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn reply_once() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on("INCR", "key").once().reply(Value::Integer(1));
+    server.on("INCR", "key").reply(Value::Integer(2));
+
+    let client = Client::connect(server.uri()).await?;
+    let value: i64 = client.incr("key").await?;
+    assert_eq!(1, value);
+    let value: i64 = client.incr("key").await?;
+    assert_eq!(2, value);
+    let value: i64 = client.incr("key").await?;
+    assert_eq!(2, value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn reply_after() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on("GET", "key").reply_after(
+        Duration::from_millis(200),
+        Value::BulkString(b"value".to_vec()),
+    );
+
+    let client = Client::connect(server.uri()).await?;
+    let start = Instant::now();
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn redirects() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on("GET", "key1").moved(9189, ("127.0.0.1", 7001));
+    server.on("GET", "key2").ask(5798, ("127.0.0.1", 7002));
+
+    let client = Client::connect(server.uri()).await?;
+
+    let result: Result<String> = client.get("key1").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Moved {
+                hash_slot: 9189,
+                address: (host, 7001)
+            },
+            ..
+        })) if host == "127.0.0.1"
+    ));
+
+    let result: Result<String> = client.get("key2").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Ask {
+                hash_slot: 5798,
+                address: (host, 7002)
+            },
+            ..
+        })) if host == "127.0.0.1"
+    ));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn disconnect() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on("GET", "key").once().disconnect();
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(b"value".to_vec()));
+
+    let client = Client::connect(server.uri()).await?;
+
+    // the connection is closed instead of replying, the client reconnects and retries
+    let value: String = client.get("key").retry_on_error(true).await?;
+    assert_eq!("value", value);
+
+    // the server closes all the connections, the client reconnects
+    server.disconnect_all();
+    client.send(cmd("PING"), Some(true)).await?;
+
+    let hellos = server
+        .received()
+        .iter()
+        .filter(|command| command.name == "HELLO")
+        .count();
+    assert!(hellos >= 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn push() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+
+    let client = Client::connect(server.uri()).await?;
+    let mut invalidations = client.create_client_tracking_invalidation_stream()?;
+    // round trip to make sure the stream is registered
+    client.ping::<()>(Default::default()).await?;
+
+    server.push(Value::Push(vec![
+        Value::BulkString(b"invalidate".to_vec()),
+        Value::Array(vec![Value::BulkString(b"key".to_vec())]),
+    ]));
+
+    let keys = invalidations.next().await;
+    assert_eq!(Some(vec!["key".to_owned()]), keys);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn unknown_command() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on("MYMODULE.CMD", "arg").reply(Value::Integer(12));
+
+    let client = Client::connect(server.uri()).await?;
+
+    // commands of modules can be mocked without being known to the crate
+    let value: i64 = client
+        .send(cmd("MYMODULE.CMD").arg("arg"), None)
+        .await?
+        .to()?;
+    assert_eq!(12, value);
+
+    let result: Result<()> = client.send(cmd("UNKNOWN").arg("arg"), None).await?.to();
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Err,
+            description,
+        })) if description.starts_with("unknown command 'UNKNOWN'")
+    ));
+
+    // the connection is still usable
+    client.ping::<()>(Default::default()).await?;
+
+    Ok(())
+}
@@ -2,10 +2,14 @@ mod bitmap_commands;
 #[cfg(feature = "redis-bloom")]
 mod bloom_commands;
 mod buffer_decoder;
+#[cfg(feature = "mock-server")]
+mod bulk_reader;
 mod client;
+mod client_side_cache;
 mod cluster;
 mod cluster_commands;
 mod command_args;
+mod command_encoder;
 mod command_info_manager;
 mod config;
 mod connection_commands;
@@ -15,21 +19,30 @@ mod count_min_sktech_commands;
 mod cuckoo_commands;
 mod debug_commands;
 mod error;
+#[cfg(all(feature = "fault-injection", feature = "mock-server"))]
+mod fault_injection;
 mod from_value;
 mod generic_commands;
 mod geo_commands;
 #[cfg(feature = "redis-graph")]
 mod graph_commands;
 mod hash_commands;
+mod hash_record;
 mod hyper_log_log_commands;
 #[cfg(feature = "redis-json")]
 mod json_commands;
+mod keyspace_events;
 mod list_commands;
+#[cfg(feature = "mock-server")]
+mod mock_server;
 mod multiplexed_client;
 mod pipeline;
 #[cfg(feature = "pool")]
 mod pooled_client_manager;
 mod pub_sub_commands;
+#[cfg(feature = "record-replay")]
+mod record_replay;
+mod replicated;
 mod resp3;
 mod resp_deserializer;
 mod resp_serializer;
@@ -37,6 +50,7 @@ mod scripting_commands;
 #[cfg(feature = "redis-search")]
 mod search_commands;
 mod sentinel;
+mod server_codec;
 mod server_commands;
 mod set_commands;
 mod sorted_set_commands;
@@ -50,6 +64,14 @@ mod tls;
 #[cfg(feature = "redis-bloom")]
 mod top_k_commands;
 mod transaction;
+#[cfg(any(
+    feature = "json",
+    feature = "msgpack",
+    feature = "cbor",
+    feature = "lz4",
+    feature = "zstd"
+))]
+mod typed_value;
 mod util;
 mod value;
 mod value_deserialize;
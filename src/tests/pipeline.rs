@@ -3,7 +3,7 @@ use crate::{
     commands::{FlushingMode, ServerCommands, StringCommands},
     resp::{cmd, Value},
     tests::{get_cluster_test_client, get_test_client},
-    Result,
+    Error, Result,
 };
 use serial_test::serial;
 
@@ -66,3 +66,72 @@ async fn pipeline_on_cluster() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pipeline_durable() -> Result<()> {
+    let client = get_test_client().await?;
+
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key1", "value1").forget();
+    pipeline.get::<_, ()>("key1").queue();
+    pipeline.durable(0, 0, 100);
+    let value: String = pipeline.execute().await?;
+    assert_eq!("value1", value);
+
+    // standalone server without replica
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key1", "value1").forget();
+    pipeline.durable(1, 0, 100);
+    let result: Result<()> = pipeline.execute().await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn durable_commands() -> Result<()> {
+    use crate::{client::Client, mock::MockServer};
+
+    let server = MockServer::start().await?;
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    server
+        .on_any("GET")
+        .reply(Value::BulkString(b"value".to_vec()));
+    server.on("WAIT", [1, 100]).reply(Value::Integer(1));
+    server.on("WAIT", [2, 100]).reply(Value::Integer(1));
+    let client = Client::connect(server.uri()).await?;
+
+    // WAIT is queued after the durable command and filtered from the results
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key", "value").durable(1, 0, 100).forget();
+    pipeline.get::<_, ()>("key").queue();
+    let value: String = pipeline.execute().await?;
+    assert_eq!("value", value);
+    let names = server
+        .received()
+        .iter()
+        .map(|command| command.name)
+        .filter(|name| matches!(*name, "SET" | "WAIT" | "GET"))
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["SET", "WAIT", "GET"], names);
+
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key", "value").durable(2, 0, 100).queue();
+    let result: Result<()> = pipeline.execute().await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    // WAIT does not block inside MULTI
+    let mut transaction = client.create_transaction();
+    transaction.set("key", "value").durable(1, 0, 100).queue();
+    let result: Result<()> = transaction.execute().await;
+    assert!(matches!(result, Err(Error::Client(_))));
+
+    Ok(())
+}
@@ -1,21 +1,31 @@
 use crate::{
-    client::{Client, IntoConfig, ReconnectionConfig},
+    client::{
+        Client, IntoConfig, PayloadCodec, PubSubMessage, PubSubOverflowPolicy, PubSubRouter,
+        RawCodec, ReconnectionConfig, Utf8Codec,
+    },
     commands::{
         ClientKillOptions, ClusterCommands, ClusterShardResult, ConnectionCommands, FlushingMode,
-        ListCommands, PubSubChannelsOptions, PubSubCommands, ServerCommands, StringCommands,
+        ListCommands, PingOptions, PubSubChannelsOptions, PubSubCommands, ServerCommands,
+        StringCommands,
     },
-    spawn,
+    sleep, spawn,
     tests::{
         get_cluster_test_client, get_default_addr, get_default_config, get_test_client,
         get_test_client_with_config, log_try_init,
     },
     Result,
 };
+use futures_channel::mpsc;
 use futures_util::{FutureExt, StreamExt, TryStreamExt};
 use serial_test::serial;
 use std::{
     collections::{HashMap, HashSet},
     future::IntoFuture,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
@@ -61,6 +71,504 @@ async fn pubsub() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_on_shared_connection() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.flushdb(FlushingMode::Sync).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+
+    // regular commands are still available on the subscribed connection
+    client.set("key", "value").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    // a regular reply which looks like a pub/sub message is not taken for a push frame
+    client
+        .rpush("list", ["message", "mychannel", "payload"])
+        .await?;
+    let values: Vec<String> = client.lrange("list", 0, -1).await?;
+    assert_eq!(vec!["message", "mychannel", "payload"], values);
+
+    client.publish("mychannel", "mymessage").await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!(b"mymessage".to_vec(), message.payload);
+
+    pub_sub_stream.close().await?;
+
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_overflow_policy() -> Result<()> {
+    let regular_client = get_test_client().await?;
+
+    // drop oldest
+    let mut config = get_default_config()?;
+    config.pub_sub_capacity = 2;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::DropOldest;
+    let client = get_test_client_with_config(config).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+    for i in 1..=5 {
+        regular_client.publish("mychannel", i).await?;
+    }
+    // make sure all the messages have been received
+    client.ping::<()>(PingOptions::default()).await?;
+
+    assert_eq!(3, pub_sub_stream.dropped_messages());
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"4".to_vec(), message.payload);
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"5".to_vec(), message.payload);
+    pub_sub_stream.close().await?;
+
+    // drop newest
+    let mut config = get_default_config()?;
+    config.pub_sub_capacity = 2;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::DropNewest;
+    let client = get_test_client_with_config(config).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+    for i in 1..=5 {
+        regular_client.publish("mychannel", i).await?;
+    }
+    client.ping::<()>(PingOptions::default()).await?;
+
+    assert_eq!(3, pub_sub_stream.dropped_messages());
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"1".to_vec(), message.payload);
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"2".to_vec(), message.payload);
+    pub_sub_stream.close().await?;
+
+    // disconnect
+    let mut config = get_default_config()?;
+    config.pub_sub_capacity = 2;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::Disconnect;
+    let client = get_test_client_with_config(config).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+    for i in 1..=3 {
+        regular_client.publish("mychannel", i).await?;
+    }
+    client.ping::<()>(PingOptions::default()).await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"1".to_vec(), message.payload);
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"2".to_vec(), message.payload);
+    assert!(pub_sub_stream.next().await.unwrap().is_err());
+    assert!(pub_sub_stream.next().await.is_none());
+    assert_eq!(1, pub_sub_stream.dropped_messages());
+
+    // backpressure
+    let mut config = get_default_config()?;
+    config.pub_sub_capacity = 2;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::Backpressure;
+    let client = get_test_client_with_config(config).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+    for i in 1..=5 {
+        regular_client.publish("mychannel", i).await?;
+    }
+
+    for i in 1..=5 {
+        let message = pub_sub_stream.next().await.unwrap()?;
+        assert_eq!(i.to_string().into_bytes(), message.payload);
+    }
+    assert_eq!(0, pub_sub_stream.dropped_messages());
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_disconnect_policy_unsubscribes() -> Result<()> {
+    use crate::{mock::MockServer, resp::Value};
+
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+    let server = MockServer::start().await?;
+    server.on("SUBSCRIBE", "mychannel").reply(Value::Push(vec![
+        str("subscribe"),
+        str("mychannel"),
+        Value::Integer(1),
+    ]));
+    server
+        .on("UNSUBSCRIBE", "mychannel")
+        .reply(Value::Push(vec![
+            str("unsubscribe"),
+            str("mychannel"),
+            Value::Integer(0),
+        ]));
+
+    let mut config = server.uri().into_config()?;
+    config.pub_sub_capacity = 2;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::Disconnect;
+    let client = Client::connect(config).await?;
+
+    let mut pub_sub_stream = client.subscribe("mychannel").await?;
+    for i in 1..=3 {
+        server.push(Value::Push(vec![
+            str("message"),
+            str("mychannel"),
+            str(&i.to_string()),
+        ]));
+    }
+    // make sure all the messages have been received
+    client.ping::<()>(PingOptions::default()).await?;
+    client.ping::<()>(PingOptions::default()).await?;
+
+    assert!(server
+        .received()
+        .iter()
+        .any(|command| command.name == "UNSUBSCRIBE"
+            && command.args.len() == 1
+            && command.args[0] == b"mychannel"[..]));
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"1".to_vec(), message.payload);
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"2".to_vec(), message.payload);
+    assert!(pub_sub_stream.next().await.unwrap().is_err());
+    assert!(pub_sub_stream.next().await.is_none());
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_router_busy_handler() -> Result<()> {
+    use crate::{mock::MockServer, resp::Value};
+
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+    let message = |channel: &str, payload: &str| {
+        Value::Push(vec![str("message"), str(channel), str(payload)])
+    };
+
+    let server = MockServer::start().await?;
+    for (channel, count) in [("slow", 1), ("fast", 2)] {
+        server.on("SUBSCRIBE", channel).reply(Value::Push(vec![
+            str("subscribe"),
+            str(channel),
+            Value::Integer(count),
+        ]));
+    }
+
+    let client = Client::connect(server.uri()).await?;
+    let mut router = PubSubRouter::new(&client, 1);
+
+    // the handler of `slow` never ends
+    router
+        .route("slow", |_| futures_util::future::pending::<()>())
+        .await?;
+    let (sender, mut receiver) = mpsc::unbounded::<Vec<u8>>();
+    router
+        .route("fast", move |message| {
+            let _ = sender.unbounded_send(message.payload);
+            async {}
+        })
+        .await?;
+
+    server.push(message("slow", "1"));
+    server.push(message("slow", "2"));
+    server.push(message("fast", "3"));
+
+    let payload = tokio::time::timeout(Duration::from_secs(1), receiver.next())
+        .await
+        .unwrap();
+    assert_eq!(Some(b"3".to_vec()), payload);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_router_overflow_policy() -> Result<()> {
+    use crate::{mock::MockServer, resp::Value};
+    use tokio::sync::Semaphore;
+
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+    let server = MockServer::start().await?;
+    server.on("SUBSCRIBE", "mychannel").reply(Value::Push(vec![
+        str("subscribe"),
+        str("mychannel"),
+        Value::Integer(1),
+    ]));
+
+    let mut config = server.uri().into_config()?;
+    config.pub_sub_capacity = 1;
+    config.pub_sub_overflow_policy = PubSubOverflowPolicy::DropOldest;
+    let client = Client::connect(config).await?;
+    let mut router = PubSubRouter::new(&client, 1);
+
+    // executions of the handler wait for the gate to open
+    let gate = Arc::new(Semaphore::new(0));
+    let (sender, mut receiver) = mpsc::unbounded::<Vec<u8>>();
+    let handler_gate = gate.clone();
+    router
+        .route("mychannel", move |message| {
+            let gate = handler_gate.clone();
+            let sender = sender.clone();
+            async move {
+                let _permit = gate.acquire().await;
+                let _ = sender.unbounded_send(message.payload);
+            }
+        })
+        .await?;
+
+    for i in 1..=4 {
+        server.push(Value::Push(vec![
+            str("message"),
+            str("mychannel"),
+            str(&i.to_string()),
+        ]));
+        // make sure the message has been received before the next one,
+        // the buffer of the pub/sub stream itself is also bounded
+        client.ping::<()>(PingOptions::default()).await?;
+    }
+
+    // 1 is handled, only the newest of 2, 3 and 4 is kept in the queue of the handler
+    gate.add_permits(4);
+    assert_eq!(Some(b"1".to_vec()), receiver.next().await);
+    assert_eq!(Some(b"4".to_vec()), receiver.next().await);
+    let result = tokio::time::timeout(Duration::from_millis(100), receiver.next()).await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn payload_codecs() -> Result<()> {
+    assert_eq!(b"value".to_vec(), Utf8Codec::encode(&"value".to_owned())?);
+    assert_eq!("value", Utf8Codec::decode(b"value")?);
+    assert!(Utf8Codec::decode(b"\xff\xfe").is_err());
+
+    assert_eq!(
+        b"\xa1\x63".to_vec(),
+        RawCodec::encode(&b"\xa1\x63".to_vec())?
+    );
+    assert_eq!(b"\xa1\x63".to_vec(), RawCodec::decode(b"\xa1\x63")?);
+
+    let message = PubSubMessage {
+        pattern: vec![],
+        channel: b"mychannel".to_vec(),
+        payload: b"mymessage".to_vec(),
+    };
+    assert_eq!("mymessage", message.payload_as::<Utf8Codec>()?);
+    let message = message.into_typed::<Utf8Codec>()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!("mymessage", message.payload);
+
+    Ok(())
+}
+
+#[cfg(all(feature = "json", feature = "msgpack", feature = "cbor"))]
+#[test]
+fn serde_payload_codecs() -> Result<()> {
+    use crate::resp::{Cbor, Json, MsgPack};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: u32,
+        item: String,
+    }
+
+    let order = Order {
+        id: 12,
+        item: "book".to_owned(),
+    };
+    let payload = Json::<Order>::encode(&order)?;
+    assert_eq!(br#"{"id":12,"item":"book"}"#.to_vec(), payload);
+    assert_eq!(order, Json::<Order>::decode(&payload)?);
+    assert!(Json::<Order>::decode(b"{}").is_err());
+
+    let payload = MsgPack::<Order>::encode(&order)?;
+    assert_eq!(order, MsgPack::<Order>::decode(&payload)?);
+    assert!(MsgPack::<Order>::decode(b"\xc1").is_err());
+
+    let payload = Cbor::<Order>::encode(&order)?;
+    assert_eq!(order, Cbor::<Order>::decode(&payload)?);
+    assert!(Cbor::<Order>::decode(b"\xff").is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn typed_pubsub() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    let mut pub_sub_stream = pub_sub_client
+        .subscribe("mychannel")
+        .await?
+        .typed::<Utf8Codec>();
+    pub_sub_stream.psubscribe("otherchannel*").await?;
+
+    regular_client
+        .publish_as::<Utf8Codec>("mychannel", "mymessage".to_owned())?
+        .await?;
+    regular_client.publish("otherchannel1", b"\xff").await?;
+
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"mychannel".to_vec(), message.channel);
+    assert_eq!("mymessage", message.payload);
+
+    // invalid payloads are reported without closing the stream
+    assert!(pub_sub_stream.next().await.unwrap().is_err());
+
+    regular_client
+        .publish_as::<Utf8Codec>("otherchannel2", "othermessage".to_owned())?
+        .await?;
+    let message = pub_sub_stream.next().await.unwrap()?;
+    assert_eq!(b"otherchannel*".to_vec(), message.pattern);
+    assert_eq!("othermessage", message.payload);
+
+    pub_sub_stream.close().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_router() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    let (sender, mut receiver) = mpsc::unbounded::<(String, String)>();
+    let mut router = PubSubRouter::new(&pub_sub_client, 1);
+
+    let orders_sender = sender.clone();
+    router
+        .route("orders.*", move |message| {
+            let sender = orders_sender.clone();
+            async move {
+                let _ = sender.unbounded_send((
+                    "orders".to_owned(),
+                    String::from_utf8(message.payload).unwrap(),
+                ));
+            }
+        })
+        .await?;
+
+    let users_sender = sender.clone();
+    router
+        .route("users", move |message| {
+            let sender = users_sender.clone();
+            async move {
+                let _ = sender.unbounded_send((
+                    "users".to_owned(),
+                    String::from_utf8(message.payload).unwrap(),
+                ));
+            }
+        })
+        .await?;
+
+    assert!(router.route("users", |_| async {}).await.is_err());
+
+    regular_client.publish("orders.created", "order1").await?;
+    regular_client.publish("users", "user1").await?;
+    regular_client.publish("orders.deleted", "order2").await?;
+
+    assert_eq!(
+        Some(("orders".to_owned(), "order1".to_owned())),
+        receiver.next().await
+    );
+    assert_eq!(
+        Some(("users".to_owned(), "user1".to_owned())),
+        receiver.next().await
+    );
+    assert_eq!(
+        Some(("orders".to_owned(), "order2".to_owned())),
+        receiver.next().await
+    );
+
+    router.remove("orders.*").await?;
+    assert!(router.remove("orders.*").await.is_err());
+
+    let num_receivers = regular_client.publish("orders.created", "order3").await?;
+    assert_eq!(0, num_receivers);
+    regular_client.publish("users", "user2").await?;
+    assert_eq!(
+        Some(("users".to_owned(), "user2".to_owned())),
+        receiver.next().await
+    );
+
+    router.close().await?;
+
+    let num_receivers = regular_client.publish("users", "user3").await?;
+    assert_eq!(0, num_receivers);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn pubsub_router_concurrency() -> Result<()> {
+    let pub_sub_client = get_test_client().await?;
+    let regular_client = get_test_client().await?;
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let (sender, mut receiver) = mpsc::unbounded::<()>();
+
+    let mut router = PubSubRouter::new(&pub_sub_client, 2);
+    let (r, m) = (running.clone(), max_running.clone());
+    router
+        .route("mychannel", move |_message| {
+            let (running, max_running, sender) = (r.clone(), m.clone(), sender.clone());
+            async move {
+                let num_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(num_running, Ordering::SeqCst);
+                sleep(Duration::from_millis(50)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                let _ = sender.unbounded_send(());
+            }
+        })
+        .await?;
+
+    for i in 0..6 {
+        regular_client.publish("mychannel", i).await?;
+    }
+
+    for _ in 0..6 {
+        receiver.next().await;
+    }
+
+    assert_eq!(2, max_running.load(Ordering::SeqCst));
+
+    router.close().await?;
+
+    Ok(())
+}
+
 // #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 // #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 // #[serial]
@@ -0,0 +1,254 @@
+use crate::{
+    client::{Client, IntoConfig, TrafficEventKind, TrafficRecorder, TrafficRecording},
+    commands::{ConnectionCommands, StringCommands},
+    mock::{MockServer, ReplayServer},
+    resp::{BulkReader, Value},
+    tests::log_try_init,
+    Error, RedisError, RedisErrorKind, Result,
+};
+use futures_util::StreamExt;
+use serial_test::serial;
+use std::{io::Cursor, path::PathBuf, time::Duration};
+
+/// Scenario run against the mock server, then against the replay server
+async fn scenario(uri: String, traffic_recorder: Option<TrafficRecorder>) -> Result<Vec<String>> {
+    let mut config = uri.into_config()?;
+    config.traffic_recorder = traffic_recorder;
+    let client = Client::connect(config).await?;
+    let mut invalidations = client.create_client_tracking_invalidation_stream()?;
+
+    client.set("key", "value").await?;
+    let value1: String = client.get("key").await?;
+
+    // push message sent by the server after the reply
+    let keys = invalidations.next().await.unwrap_or_default();
+
+    // the server closes the connection, the command is sent again after the reconnection
+    let value2: String = client.get("key").retry_on_error(true).await?;
+
+    client.close().await?;
+    Ok(vec![value1, keys.join(","), value2])
+}
+
+fn recording_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustis-{name}-{}.resp", std::process::id()))
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn record_and_replay() -> Result<()> {
+    log_try_init();
+    let path = recording_path("record_and_replay");
+
+    // record
+    let server = MockServer::start().await?;
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    server
+        .on("GET", "key")
+        .once()
+        .reply(Value::BulkString(b"value".to_vec()));
+    server.on("GET", "key").once().disconnect();
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(b"value".to_vec()));
+
+    let recorder = TrafficRecorder::create(&path)?;
+    let client_task = tokio::spawn(scenario(server.uri(), Some(recorder.clone())));
+
+    // wait for the first GET to send a push message
+    while !server
+        .received()
+        .iter()
+        .any(|command| command.name == "GET")
+    {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    server.push(Value::Push(vec![
+        Value::BulkString(b"invalidate".to_vec()),
+        Value::Array(vec![Value::BulkString(b"key".to_vec())]),
+    ]));
+
+    let recorded_results = client_task.await.unwrap()?;
+    assert_eq!(vec!["value", "key", "value"], recorded_results);
+    recorder.flush()?;
+
+    // load
+    let recording = TrafficRecording::load(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(vec![server.addr().to_string()], recording.connections());
+
+    let kinds = recording
+        .events()
+        .iter()
+        .map(|event| event.kind)
+        .collect::<Vec<_>>();
+    let count = |kind| kinds.iter().filter(|k| **k == kind).count();
+    assert_eq!(TrafficEventKind::Connect, kinds[0]);
+    assert_eq!(2, count(TrafficEventKind::Connect));
+    assert_eq!(1, count(TrafficEventKind::Push));
+    assert_eq!(1, count(TrafficEventKind::Disconnect));
+    // no reply to the GET command which closed the connection
+    assert_eq!(
+        count(TrafficEventKind::Command),
+        count(TrafficEventKind::Reply) + 1
+    );
+
+    // replay
+    let recording = recording.filter_connection(&server.addr().to_string());
+    drop(server);
+    let replay_server = ReplayServer::start(recording).await?;
+    let replayed_results = scenario(replay_server.uri(), None).await?;
+
+    assert_eq!(recorded_results, replayed_results);
+    assert_eq!(0, replay_server.remaining_sessions());
+    assert!(replay_server.mismatches().is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn replay_mismatch() -> Result<()> {
+    log_try_init();
+    let path = recording_path("replay_mismatch");
+
+    let server = MockServer::start().await?;
+    let recorder = TrafficRecorder::create(&path)?;
+    let mut config = server.uri().into_config()?;
+    config.traffic_recorder = Some(recorder.clone());
+    let client = Client::connect(config).await?;
+    client.ping::<()>(Default::default()).await?;
+    client.close().await?;
+    recorder.flush()?;
+
+    let recording = TrafficRecording::load(&path)?;
+    std::fs::remove_file(&path)?;
+
+    let replay_server = ReplayServer::start_realtime(recording).await?;
+    let client = Client::connect(replay_server.uri()).await?;
+    // a different command receives the recorded reply
+    let pong: String = client
+        .send(crate::resp::cmd("ECHO").arg("PONG"), None)
+        .await?
+        .to()?;
+    assert_eq!("PONG", pong);
+
+    let mismatches = replay_server.mismatches();
+    assert_eq!(1, mismatches.len());
+    assert_eq!(b"*1\r\n$4\r\nPING\r\n", &mismatches[0].expected[..]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn replay_after_session_end() -> Result<()> {
+    log_try_init();
+    let path = recording_path("replay_after_session_end");
+
+    let server = MockServer::start().await?;
+    let recorder = TrafficRecorder::create(&path)?;
+    let mut config = server.uri().into_config()?;
+    config.traffic_recorder = Some(recorder.clone());
+    let client = Client::connect(config).await?;
+    client.ping::<()>(Default::default()).await?;
+    recorder.flush()?;
+
+    let recording = TrafficRecording::load(&path)?;
+    std::fs::remove_file(&path)?;
+
+    let replay_server = ReplayServer::start(recording).await?;
+    let client = Client::connect(replay_server.uri()).await?;
+    client.ping::<()>(Default::default()).await?;
+
+    // the command is not part of the recording
+    let result: Result<String> = client.get("key").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Err,
+            ..
+        }))
+    ));
+
+    let mismatches = replay_server.mismatches();
+    assert_eq!(1, mismatches.len());
+    assert!(mismatches[0].expected.is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn record_without_credentials() -> Result<()> {
+    log_try_init();
+    let path = recording_path("record_without_credentials");
+
+    let server = MockServer::start().await?;
+    let recorder = TrafficRecorder::create(&path)?;
+    let mut config = format!("redis://user:secret@{}", server.addr()).into_config()?;
+    config.traffic_recorder = Some(recorder.clone());
+    let client = Client::connect(config).await?;
+    client.ping::<()>(Default::default()).await?;
+    client.close().await?;
+    recorder.flush()?;
+
+    let content = std::fs::read(&path)?;
+    let recording = TrafficRecording::load(&path)?;
+    std::fs::remove_file(&path)?;
+    assert!(memchr::memmem::find(&content, b"secret").is_none());
+    assert!(memchr::memmem::find(&content, b"<redacted>").is_some());
+
+    // any password matches the redacted one
+    let replay_server = ReplayServer::start(recording).await?;
+    let client = Client::connect(format!("redis://user:other@{}", replay_server.addr())).await?;
+    client.ping::<()>(Default::default()).await?;
+    client.close().await?;
+
+    assert!(replay_server.mismatches().is_empty());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn record_streamed_argument() -> Result<()> {
+    log_try_init();
+    let path = recording_path("record_streamed_argument");
+    let reader = |content: &[u8]| BulkReader::new(Cursor::new(content.to_vec()), content.len());
+
+    let server = MockServer::start().await?;
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    let recorder = TrafficRecorder::create(&path)?;
+    let mut config = server.uri().into_config()?;
+    config.traffic_recorder = Some(recorder.clone());
+    let client = Client::connect(config).await?;
+    client.set("key", reader(b"content")).await?;
+    client.close().await?;
+    recorder.flush()?;
+
+    let content = std::fs::read(&path)?;
+    let recording = TrafficRecording::load(&path)?;
+    std::fs::remove_file(&path)?;
+    assert!(memchr::memmem::find(&content, b"content").is_none());
+    assert!(memchr::memmem::find(&content, b"<streamed>").is_some());
+
+    // any content matches the streamed argument
+    let replay_server = ReplayServer::start(recording).await?;
+    let client = Client::connect(replay_server.uri()).await?;
+    client.set("key", reader(b"other content")).await?;
+    client.close().await?;
+
+    assert!(replay_server.mismatches().is_empty());
+
+    Ok(())
+}
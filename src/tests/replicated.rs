@@ -0,0 +1,189 @@
+use crate::{
+    client::{BatchPreparedCommand, Client},
+    commands::{GenericCommands, StringCommands},
+    tests::{get_default_host, log_try_init},
+    Result,
+};
+#[cfg(feature = "mock-server")]
+use crate::{mock::MockServer, resp::Value, Error, RedisError, RedisErrorKind};
+use serial_test::serial;
+
+async fn get_replicated_test_client() -> Result<Client> {
+    log_try_init();
+    let host = get_default_host();
+    Client::connect(format!("redis+replicated://{host}:6381,{host}:6382")).await
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn unreachable() -> Result<()> {
+    log_try_init();
+    let result = Client::connect("redis+replicated://127.0.0.1:1234,127.0.0.1:5678").await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn read_write_splitting() -> Result<()> {
+    let client = get_replicated_test_client().await?;
+
+    client.set("key", "value").await?;
+    client.wait(1, 1000).await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    // replies keep the order of the commands, whatever the node they come from
+    let mut pipeline = client.create_pipeline();
+    pipeline.set("key", "value2").forget();
+    pipeline.wait(1, 1000).forget();
+    pipeline.get::<_, ()>("key").queue();
+    pipeline.set("key2", "value3").forget();
+    pipeline.get::<_, ()>("key").queue();
+    let (value1, value2): (String, String) = pipeline.execute().await?;
+    assert_eq!("value2", value1);
+    assert_eq!("value2", value2);
+
+    // transactions are sent to the master
+    let mut transaction = client.create_transaction();
+    transaction.set("key", "value4").forget();
+    transaction.get::<_, ()>("key").queue();
+    let value: String = transaction.execute().await?;
+    assert_eq!("value4", value);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn readonly_error() -> Result<()> {
+    log_try_init();
+    let server = MockServer::start().await?;
+    server.on_any("ROLE").reply(Value::Array(vec![
+        Value::BulkString(b"master".to_vec()),
+        Value::Integer(0),
+        Value::Array(Vec::new()),
+    ]));
+    server.on_any("COMMAND").reply(Value::Array(Vec::new()));
+    server.on("SET", ["key", "value"]).once().error(
+        RedisErrorKind::Readonly,
+        "You can't write against a read only replica.",
+    );
+    server
+        .on("SET", ["key", "value"])
+        .reply(Value::SimpleString("OK".to_owned()));
+
+    let addr = server.addr();
+    let client =
+        Client::connect(format!("redis+replicated://{}:{}", addr.ip(), addr.port())).await?;
+
+    let mut reconnect_receiver = client.on_reconnect();
+
+    // the error is delivered to the caller of the command
+    let result = client.set("key", "value").await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::Readonly,
+            ..
+        }))
+    ));
+
+    // then the topology is discovered again
+    reconnect_receiver.recv().await.unwrap();
+    client.set("key", "value").await?;
+    let num_roles = server
+        .received()
+        .iter()
+        .filter(|command| command.name == "ROLE")
+        .count();
+    assert_eq!(2, num_roles);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn read_your_writes_in_pipeline() -> Result<()> {
+    log_try_init();
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+    let command_info = |name: &str, arity: i64, flag: &str| {
+        Value::Array(vec![
+            str(name),
+            Value::Integer(arity),
+            Value::Array(vec![str(flag)]),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Array(Vec::new()),
+            Value::Array(Vec::new()),
+            Value::Array(Vec::new()),
+            Value::Array(Vec::new()),
+        ])
+    };
+
+    let master = MockServer::start().await?;
+    let replica = MockServer::start().await?;
+    for server in [&master, &replica] {
+        server.on_any("COMMAND").reply(Value::Array(vec![
+            command_info("get", 2, "readonly"),
+            command_info("set", -3, "write"),
+        ]));
+    }
+    master.on_any("ROLE").reply(Value::Array(vec![
+        str("master"),
+        Value::Integer(0),
+        Value::Array(vec![Value::Array(vec![
+            str("127.0.0.1"),
+            str(&replica.addr().port().to_string()),
+            str("0"),
+        ])]),
+    ]));
+    replica.on_any("ROLE").reply(Value::Array(vec![
+        str("slave"),
+        str("127.0.0.1"),
+        Value::Integer(master.addr().port().into()),
+        str("connected"),
+        Value::Integer(0),
+    ]));
+    master
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    master.on_any("GET").reply(str("value2"));
+    replica.on_any("GET").reply(str("value1"));
+
+    let client = Client::connect(format!(
+        "redis+replicated://127.0.0.1:{},127.0.0.1:{}",
+        master.addr().port(),
+        replica.addr().port()
+    ))
+    .await?;
+
+    // the read following the write of the pipeline is sent to the master
+    let mut pipeline = client.create_pipeline();
+    pipeline.get::<_, ()>("key").queue();
+    pipeline.set("key", "value2").forget();
+    pipeline.get::<_, ()>("key").queue();
+    let (value1, value2): (String, String) = pipeline.execute().await?;
+    assert_eq!("value1", value1);
+    assert_eq!("value2", value2);
+
+    let count_gets = |server: &MockServer| {
+        server
+            .received()
+            .iter()
+            .filter(|command| command.name == "GET")
+            .count()
+    };
+    assert_eq!(1, count_gets(&replica));
+    assert_eq!(1, count_gets(&master));
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::{
-    resp::RespDeserializer, tests::log_try_init, Error, RedisError, RedisErrorKind, Result,
+    resp::{BulkBytes, RespBatchDeserializer, RespBuf, RespDeserializer, Value},
+    tests::log_try_init,
+    Error, RedisError, RedisErrorKind, Result,
 };
 use serde::Deserialize;
 use smallvec::SmallVec;
@@ -567,3 +569,177 @@ fn array_chunks() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn bulk_bytes() -> Result<()> {
+    let resp_buf = RespBuf::from_slice(b"*3\r\n$5\r\nhello\r\n+world\r\n_\r\n");
+
+    let values: (BulkBytes, BulkBytes, Option<BulkBytes>) = resp_buf.to()?;
+    assert_eq!(b"hello", values.0.as_bytes());
+    assert_eq!(b"world", values.1.as_bytes());
+    assert_eq!(None, values.2);
+
+    // shares the buffer of the RESP buffer
+    let range = resp_buf.as_bytes().as_ptr_range();
+    assert!(range.contains(&values.0.as_ptr()));
+    assert!(range.contains(&values.1.as_ptr()));
+
+    // copied from any other source
+    let value: BulkBytes = deserialize("$5\r\nhello\r\n")?;
+    assert_eq!(b"hello", value.as_bytes());
+
+    // shares the buffers of a pipeline
+    let resp_bufs = vec![
+        RespBuf::from_slice(b"$5\r\nhello\r\n"),
+        RespBuf::from_slice(b"$5\r\nworld\r\n"),
+    ];
+    let values: (BulkBytes, BulkBytes) =
+        <(BulkBytes, BulkBytes)>::deserialize(&RespBatchDeserializer::new(&resp_bufs))?;
+    assert_eq!(b"hello", values.0.as_bytes());
+    assert_eq!(b"world", values.1.as_bytes());
+    assert!(resp_bufs[0]
+        .as_bytes()
+        .as_ptr_range()
+        .contains(&values.0.as_ptr()));
+    assert!(resp_bufs[1]
+        .as_bytes()
+        .as_ptr_range()
+        .contains(&values.1.as_ptr()));
+
+    Ok(())
+}
+
+#[test]
+fn borrowed_from_resp_buf() -> Result<()> {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        data: &'a [u8],
+    }
+
+    let resp_buf =
+        RespBuf::from_slice(b"%2\r\n$4\r\nname\r\n$3\r\nfoo\r\n$4\r\ndata\r\n$3\r\nbar\r\n");
+    let value: Borrowed = resp_buf.to()?;
+    assert_eq!("foo", value.name);
+    assert_eq!(b"bar", value.data);
+
+    Ok(())
+}
+
+#[test]
+fn big_number() -> Result<()> {
+    let result: String = deserialize("(3492890328409238509324850943850943825024385\r\n")?;
+    assert_eq!("3492890328409238509324850943850943825024385", result);
+
+    let result: i64 = deserialize("(12\r\n")?;
+    assert_eq!(12, result);
+
+    let result: f64 = deserialize("(12\r\n")?;
+    assert_eq!(12.0, result);
+
+    let result: i128 = deserialize("(-170141183460469231731687303715884105728\r\n")?;
+    assert_eq!(i128::MIN, result);
+
+    let result: u128 = deserialize("(340282366920938463463374607431768211455\r\n")?;
+    assert_eq!(u128::MAX, result);
+
+    // no overflow nor loss of precision
+    let result = deserialize::<i64>("(3492890328409238509324850943850943825024385\r\n");
+    assert!(matches!(result, Err(Error::Client(e)) if e.contains("does not fit in i64")));
+
+    let result = deserialize::<f64>("(9007199254740993\r\n");
+    assert!(matches!(result, Err(Error::Client(e)) if e.contains("losing precision")));
+
+    let result: Value = deserialize("(3492890328409238509324850943850943825024385\r\n")?;
+    assert_eq!(
+        Value::BigNumber("3492890328409238509324850943850943825024385".to_owned()),
+        result
+    );
+
+    let result: Vec<Value> = deserialize("*1\r\n(3492890328409238509324850943850943825024385\r\n")?;
+    assert_eq!(
+        vec![Value::BigNumber(
+            "3492890328409238509324850943850943825024385".to_owned()
+        )],
+        result
+    );
+
+    // self-describing consumers get a string
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Number {
+        Integer(i64),
+        String(String),
+    }
+
+    let result: Number = deserialize("(3492890328409238509324850943850943825024385\r\n")?;
+    assert_eq!(
+        Number::String("3492890328409238509324850943850943825024385".to_owned()),
+        result
+    );
+
+    Ok(())
+}
+
+#[test]
+fn attributes() -> Result<()> {
+    let resp = "|1\r\n+key-popularity\r\n%2\r\n$1\r\na\r\n,0.1923\r\n$1\r\nb\r\n,0.0012\r\n*2\r\n:2039123\r\n:9543892\r\n";
+
+    // ignored by default
+    let result: Vec<i64> = deserialize(resp)?;
+    assert_eq!(vec![2039123, 9543892], result);
+
+    let result: Value = deserialize(resp)?;
+    assert_eq!(
+        Value::Array(vec![Value::Integer(2039123), Value::Integer(9543892)]),
+        result
+    );
+
+    // nested attributes
+    let result: Vec<i64> = deserialize("*2\r\n:1\r\n|1\r\n+ttl\r\n:3600\r\n:2\r\n")?;
+    assert_eq!(vec![1, 2], result);
+
+    // kept on demand
+    let mut deserializer = RespDeserializer::new(resp.as_bytes()).keep_attributes();
+    let result = Value::deserialize(&mut deserializer)?;
+    let Value::Attribute { attributes, value } = result else {
+        panic!("Unexpected value {result:?}");
+    };
+    assert_eq!(
+        Value::Array(vec![Value::Integer(2039123), Value::Integer(9543892)]),
+        *value
+    );
+    assert!(attributes.contains_key(&Value::SimpleString("key-popularity".to_owned())));
+
+    // read explicitly
+    let resp_buf = RespBuf::from_slice(resp.as_bytes());
+    let attributes: Option<HashMap<String, HashMap<String, f64>>> = resp_buf.attributes()?;
+    assert_eq!(
+        Some(0.1923),
+        attributes.unwrap()["key-popularity"].get("a").copied()
+    );
+    let value: Vec<i64> = resp_buf.to()?;
+    assert_eq!(vec![2039123, 9543892], value);
+
+    let resp_buf = RespBuf::from_slice(b":12\r\n");
+    let attributes: Option<HashMap<String, String>> = resp_buf.attributes()?;
+    assert_eq!(None, attributes);
+
+    Ok(())
+}
+
+#[test]
+fn streamed_values_are_skipped() -> Result<()> {
+    let resp = "$?\r\n;4\r\nHell\r\n;5\r\no wor\r\n;1\r\nd\r\n;0\r\n";
+    let mut deserializer = RespDeserializer::new(resp.as_bytes());
+    serde::de::IgnoredAny::deserialize(&mut deserializer)?;
+    assert_eq!(resp.len(), deserializer.get_pos());
+
+    let resp = "*?\r\n:1\r\n%?\r\n+a\r\n:2\r\n.\r\n.\r\n";
+    let mut deserializer = RespDeserializer::new(resp.as_bytes());
+    serde::de::IgnoredAny::deserialize(&mut deserializer)?;
+    assert_eq!(resp.len(), deserializer.get_pos());
+
+    Ok(())
+}
@@ -261,3 +261,39 @@ fn _enum() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn streamed() -> Result<()> {
+    log_try_init();
+
+    struct Streamed(Vec<i64>);
+
+    impl Serialize for Streamed {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            use serde::ser::SerializeSeq;
+            let mut seq = serializer.serialize_seq(None)?;
+            for e in &self.0 {
+                seq.serialize_element(e)?;
+            }
+            seq.end()
+        }
+    }
+
+    let result = serialize(Streamed(vec![1, 2]))?;
+    assert_eq!("*?\r\n:1\r\n:2\r\n.\r\n", result);
+
+    let result = serialize((Streamed(vec![1]), [3]))?;
+    assert_eq!("*2\r\n*?\r\n:1\r\n.\r\n*1\r\n:3\r\n", result);
+
+    let mut serializer = RespSerializer::new();
+    serializer.serialize_streamed_bytes([b"Hell".as_slice(), b"", b"o"]);
+    assert_eq!(
+        b"$?\r\n;4\r\nHell\r\n;1\r\no\r\n;0\r\n",
+        serializer.get_output().as_ref()
+    );
+
+    Ok(())
+}
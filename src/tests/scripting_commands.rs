@@ -1,8 +1,8 @@
 use crate::{
-    client::ClientPreparedCommand,
+    client::{BatchPreparedCommand, ClientPreparedCommand},
     commands::{
-        CallBuilder, FlushingMode, FunctionListOptions, LibraryInfo, ScriptingCommands,
-        ServerCommands, StringCommands,
+        CallBuilder, FlushingMode, FunctionLibrary, FunctionLibraryManager, FunctionListOptions,
+        LibraryFunction, LibraryInfo, Script, ScriptingCommands, ServerCommands, StringCommands,
     },
     error::{Error, RedisErrorKind},
     sleep, spawn,
@@ -105,6 +105,199 @@ async fn evalsha() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn script_sha1() {
+    static SCRIPT: Script = Script::new("");
+    assert_eq!("da39a3ee5e6b4b0d3255bfef95601890afd80709", SCRIPT.sha1());
+
+    let script = Script::from("return ARGV[1]".to_owned());
+    assert_eq!("return ARGV[1]", script.source());
+    assert_eq!(40, script.sha1().len());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn script() -> Result<()> {
+    static SCRIPT: Script = Script::new("return redis.call('GET', KEYS[1])..ARGV[1]");
+
+    let client = get_test_client().await?;
+    client.script_flush(FlushingMode::Sync).await?;
+    client.set("key", "hello").await?;
+
+    // falls back to EVAL
+    let result: String = SCRIPT
+        .call()
+        .keys("key")
+        .args(" world")
+        .invoke(&client)
+        .await?;
+    assert_eq!("hello world", result);
+
+    let exists: Vec<bool> = client.script_exists(SCRIPT.sha1()).await?;
+    assert_eq!(vec![true], exists);
+
+    // EVALSHA
+    let result: String = SCRIPT
+        .call()
+        .keys("key")
+        .args("!")
+        .readonly()
+        .invoke(&client)
+        .await?;
+    assert_eq!("hello!", result);
+
+    // no keys, no args
+    let result: i64 = Script::new("return 42").call().invoke(&client).await?;
+    assert_eq!(42, result);
+
+    client.script_flush(FlushingMode::Sync).await?;
+
+    let mut pipeline = client.create_pipeline();
+    SCRIPT
+        .call()
+        .keys("key")
+        .args("?")
+        .in_pipeline::<String>(&mut pipeline)
+        .queue();
+    let result: String = pipeline.execute().await?;
+    assert_eq!("hello?", result);
+
+    client.script_flush(FlushingMode::Sync).await?;
+
+    let mut transaction = client.create_transaction();
+    SCRIPT
+        .call()
+        .keys("key")
+        .args(".")
+        .in_transaction::<String>(&mut transaction)
+        .queue();
+    let result: String = transaction.execute().await?;
+    assert_eq!("hello.", result);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn script_in_pipeline() -> Result<()> {
+    use crate::{client::Client, mock::MockServer, resp::Value};
+
+    static SCRIPT: Script = Script::new("return KEYS[1]..ARGV[1]");
+
+    let server = MockServer::start().await?;
+    server
+        .on("SCRIPT", ["LOAD", SCRIPT.source()])
+        .reply(Value::BulkString(SCRIPT.sha1().as_bytes().to_vec()));
+    server
+        .on(
+            "EVALSHA",
+            [SCRIPT.sha1(), "2", "key1", "key2", "arg1", "arg2"],
+        )
+        .reply(Value::BulkString(b"key1arg1".to_vec()));
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(b"value".to_vec()));
+
+    let client = Client::connect(server.uri()).await?;
+
+    // keys and args are written after numkeys, whatever the order of the calls
+    let mut pipeline = client.create_pipeline();
+    SCRIPT
+        .call()
+        .args("arg1")
+        .keys("key1")
+        .keys("key2")
+        .args("arg2")
+        .in_pipeline::<String>(&mut pipeline)
+        .queue();
+    pipeline.get::<_, String>("key").queue();
+    let (script_result, get_result): (String, String) = pipeline.execute().await?;
+    assert_eq!("key1arg1", script_result);
+    assert_eq!("value", get_result);
+
+    // the script is loaded ahead of its invocation, in the same batch
+    let names = server
+        .received()
+        .into_iter()
+        .filter(|command| matches!(command.name, "EVALSHA" | "EVAL" | "GET" | "SCRIPT"))
+        .map(|command| command.name)
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["SCRIPT", "EVALSHA", "GET"], names);
+
+    Ok(())
+}
+
+static MYLIB_V1: FunctionLibrary = FunctionLibrary::new(
+    "#!lua name=mylib\nredis.register_function('myfunc', function(keys, args) return 'v1' end)",
+    "1",
+);
+static MYLIB_V2: FunctionLibrary = FunctionLibrary::new(
+    r#"#!lua name=mylib
+redis.register_function('myfunc', function(keys, args) return 'v2' end)
+redis.register_function{
+    function_name='myget',
+    callback=function(keys, args) return redis.call('GET', keys[1])..args[1] end,
+    flags={ 'no-writes' }
+}"#,
+    "2",
+);
+static MYFUNC_V1: LibraryFunction<String> = MYLIB_V1.function("myfunc");
+static MYFUNC_V2: LibraryFunction<String> = MYLIB_V2.function("myfunc");
+static MYGET: LibraryFunction<String> = MYLIB_V2.readonly_function("myget");
+
+#[test]
+fn function_library_name() {
+    assert_eq!(Some("mylib"), MYLIB_V1.name());
+    assert_eq!("1", MYLIB_V1.version());
+    assert_eq!(None, FunctionLibrary::new("return 1", "1").name());
+    assert_eq!("myfunc", MYFUNC_V1.name());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn function_library() -> Result<()> {
+    let client = get_test_client().await?;
+    client.function_flush(FlushingMode::Sync).await?;
+    client.set("key", "hello").await?;
+
+    assert!(MYLIB_V1.deploy(&client).await?);
+    assert!(!MYLIB_V1.deploy(&client).await?);
+    assert_eq!("v1", MYFUNC_V1.call().invoke(&client).await?);
+
+    // new version replaces the loaded one
+    let manager = FunctionLibraryManager::deploy(&client, [&MYLIB_V2]).await?;
+    assert_eq!(1, manager.libraries().len());
+    assert_eq!("v2", MYFUNC_V2.call().invoke(&client).await?);
+    assert_eq!(
+        "hello world",
+        MYGET
+            .call()
+            .keys("key")
+            .args(" world")
+            .invoke(&client)
+            .await?
+    );
+
+    // missing library is deployed on invocation
+    client.function_flush(FlushingMode::Sync).await?;
+    assert_eq!("v2", MYFUNC_V2.call().invoke(&client).await?);
+
+    client.function_flush(FlushingMode::Sync).await?;
+    manager.redeploy().await?;
+    let libraries: Vec<LibraryInfo> = client
+        .function_list(FunctionListOptions::default().library_name_pattern("mylib"))
+        .await?;
+    assert_eq!(1, libraries.len());
+
+    client.function_flush(FlushingMode::Sync).await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -384,3 +577,59 @@ async fn script_kill() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn library_function_redeploy() -> Result<()> {
+    use crate::{client::Client, mock::MockServer, resp::Value};
+
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+    let count_received = |server: &MockServer, name: &str| {
+        server
+            .received()
+            .iter()
+            .filter(|command| command.name == name)
+            .count()
+    };
+
+    let server = MockServer::start().await?;
+    server
+        .on("FCALL", ["myfunc", "1", "key", "arg"])
+        .once()
+        .error(RedisErrorKind::Err, "Function not found");
+    server
+        .on("FCALL", ["myfunc", "1", "key", "arg"])
+        .once()
+        .reply(str("v1"));
+    server.on_any("FUNCTION").once().reply(str("mylib"));
+
+    let client = Client::connect(server.uri()).await?;
+
+    // the library is loaded, without checking the loaded version, and the function invoked again
+    let result = MYFUNC_V1
+        .call()
+        .args("arg")
+        .keys("key")
+        .invoke(&client)
+        .await?;
+    assert_eq!("v1", result);
+    assert_eq!(2, count_received(&server, "FCALL"));
+    assert_eq!(1, count_received(&server, "FUNCTION"));
+
+    // other errors are returned as is
+    server.reset();
+    server
+        .on_any("FCALL")
+        .error(RedisErrorKind::Err, "Function error");
+
+    let result = MYFUNC_V1.call().invoke(&client).await;
+    assert!(
+        matches!(result, Err(Error::Redis(e)) if e.kind == RedisErrorKind::Err && e.description == "Function error")
+    );
+    assert_eq!(1, count_received(&server, "FCALL"));
+    assert_eq!(0, count_received(&server, "FUNCTION"));
+
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use crate::{
-    client::Client,
-    commands::{ConnectionCommands, SentinelCommands, StringCommands},
+    client::{BatchPreparedCommand, Client, SentinelEvent, SentinelInstanceDetails},
+    commands::{ConnectionCommands, GenericCommands, SentinelCommands, StringCommands},
     network::sleep,
-    tests::{get_sentinel_master_test_client, get_sentinel_test_client, log_try_init},
+    tests::{
+        get_sentinel_master_test_client, get_sentinel_master_test_uri, get_sentinel_test_client,
+        log_try_init,
+    },
     Result,
 };
+use futures_util::StreamExt;
 use serial_test::serial;
 use std::{collections::HashMap, time::Duration};
 
@@ -52,6 +56,76 @@ async fn connection_with_failures() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn read_from_replicas() -> Result<()> {
+    log_try_init();
+    let client = Client::connect(format!(
+        "{}?read_from_replicas=round_robin",
+        get_sentinel_master_test_uri()
+    ))
+    .await?;
+
+    client.set("key", "value").await?;
+    client.wait(1, 1000).await?;
+
+    // read-only commands are sent to replicas, replies keep the order of the commands,
+    // and reads following a write in the pipeline are sent to the master to read it
+    let mut pipeline = client.create_pipeline();
+    pipeline.get::<_, ()>("key").queue();
+    pipeline.set("key", "value2").forget();
+    pipeline.wait(1, 1000).forget();
+    pipeline.get::<_, ()>("key").queue();
+    let (value1, value2): (String, String) = pipeline.execute().await?;
+    assert_eq!("value", value1);
+    assert_eq!("value2", value2);
+
+    // transactions are sent to the master
+    let mut transaction = client.create_transaction();
+    transaction.set("key", "value3").forget();
+    transaction.get::<_, ()>("key").queue();
+    let value: String = transaction.execute().await?;
+    assert_eq!("value3", value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn read_from_lowest_lag_replica() -> Result<()> {
+    log_try_init();
+    let client = Client::connect(format!(
+        "{}?read_from_replicas=lowest_lag",
+        get_sentinel_master_test_uri()
+    ))
+    .await?;
+
+    client.set("key", "value").await?;
+    client.wait(1, 1000).await?;
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+
+    // replication offsets are refreshed in-band on the next write:
+    // the replies of these internal commands must not be mixed with the replies of the pipeline
+    // and the read following the write of the pipeline is sent to the master
+    sleep(Duration::from_millis(1100)).await;
+    let mut pipeline = client.create_pipeline();
+    pipeline.get::<_, ()>("key").queue();
+    pipeline.set("key", "value2").forget();
+    pipeline.wait(1, 1000).forget();
+    pipeline.get::<_, ()>("key").queue();
+    let (value1, value2): (String, String) = pipeline.execute().await?;
+    assert_eq!("value", value1);
+    assert_eq!("value2", value2);
+
+    let value: String = client.get("key").await?;
+    assert_eq!("value2", value);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
@@ -248,6 +322,166 @@ async fn sentinel_sentinels() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sentinel_event_parsing() {
+    let event = SentinelEvent::parse(
+        b"+switch-master",
+        b"myservice 127.0.0.1 6381 127.0.0.1 6382",
+    );
+    assert_eq!(
+        Some(SentinelEvent::SwitchMaster {
+            master_name: "myservice".to_owned(),
+            old_address: ("127.0.0.1".to_owned(), 6381),
+            new_address: ("127.0.0.1".to_owned(), 6382),
+        }),
+        event
+    );
+
+    let event = SentinelEvent::parse(b"-odown", b"master myservice 127.0.0.1 6381");
+    assert_eq!(
+        Some(SentinelEvent::ObjectivelyDownCleared(
+            SentinelInstanceDetails {
+                instance_type: "master".to_owned(),
+                name: "myservice".to_owned(),
+                host: "127.0.0.1".to_owned(),
+                port: 6381,
+                master: None,
+            }
+        )),
+        event
+    );
+
+    let event = SentinelEvent::parse(
+        b"+sdown",
+        b"slave 127.0.0.1:6382 127.0.0.1 6382 @ myservice 127.0.0.1 6381",
+    )
+    .unwrap();
+    assert_eq!("myservice", event.master_name());
+    assert_eq!(
+        SentinelEvent::SubjectivelyDown(SentinelInstanceDetails {
+            instance_type: "slave".to_owned(),
+            name: "127.0.0.1:6382".to_owned(),
+            host: "127.0.0.1".to_owned(),
+            port: 6382,
+            master: Some(("myservice".to_owned(), "127.0.0.1".to_owned(), 6381)),
+        }),
+        event
+    );
+
+    assert_eq!(None, SentinelEvent::parse(b"+switch-master", b"myservice"));
+    assert_eq!(None, SentinelEvent::parse(b"+tilt", b""));
+}
+
+/// test proactive switch to the new master after a failover
+/// failover changes the topology used by other tests
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+#[ignore]
+async fn switch_master() -> Result<()> {
+    let client = get_sentinel_master_test_client().await?;
+    let mut sentinel_events = client.create_sentinel_event_stream()?;
+    client.set("key", "value").await?;
+
+    let sentinel_client = get_sentinel_test_client().await?;
+    sentinel_client.sentinel_failover("myservice").await?;
+
+    loop {
+        if let Some(SentinelEvent::SwitchMaster { master_name, .. }) = sentinel_events.next().await
+        {
+            assert_eq!("myservice", master_name);
+            break;
+        }
+    }
+
+    let value: String = client.get("key").retry_on_error(true).await?;
+    assert_eq!("value", value);
+
+    Ok(())
+}
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn refresh_sentinel_instances() -> Result<()> {
+    use crate::{mock::MockServer, resp::Value};
+
+    log_try_init();
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+
+    let master = MockServer::start().await?;
+    master.on_any("ROLE").reply(Value::Array(vec![
+        str("master"),
+        Value::Integer(0),
+        Value::Array(Vec::new()),
+    ]));
+    master.on_any("GET").reply(str("value"));
+    let master_addr = || {
+        Value::Array(vec![
+            str(&master.addr().ip().to_string()),
+            str(&master.addr().port().to_string()),
+        ])
+    };
+
+    let sentinel1 = MockServer::start().await?;
+    let sentinel2 = MockServer::start().await?;
+    for sentinel in [&sentinel1, &sentinel2] {
+        sentinel
+            .on("SENTINEL", ["GET-MASTER-ADDR-BY-NAME", "myservice"])
+            .reply(master_addr());
+    }
+
+    // sentinel1 only knows sentinel2
+    let sentinel2_info = [
+        ("name", "sentinel2".to_owned()),
+        ("ip", sentinel2.addr().ip().to_string()),
+        ("port", sentinel2.addr().port().to_string()),
+        ("runid", "runid".to_owned()),
+        ("flags", "sentinel".to_owned()),
+        ("link-pending-commands", "0".to_owned()),
+        ("link-refcount", "1".to_owned()),
+        ("last-ping-sent", "0".to_owned()),
+        ("last-ok-ping-reply", "0".to_owned()),
+        ("last-ping-reply", "0".to_owned()),
+        ("down-after-milliseconds", "30000".to_owned()),
+        ("last-hello-message", "0".to_owned()),
+        ("voted-leader", "?".to_owned()),
+        ("voted-leader-epoch", "0".to_owned()),
+    ];
+    sentinel1
+        .on("SENTINEL", ["SENTINELS", "myservice"])
+        .reply(Value::Array(vec![Value::Map(
+            sentinel2_info
+                .iter()
+                .map(|(field, value)| (str(field), str(value)))
+                .collect(),
+        )]));
+
+    let client =
+        Client::connect(format!("redis+sentinel://{}/myservice", sentinel1.addr())).await?;
+
+    // sentinel1 goes away: the client reconnects through sentinel2
+    let mut on_reconnect = client.on_reconnect();
+    drop(sentinel1);
+    master.disconnect_all();
+    tokio::time::timeout(Duration::from_secs(5), on_reconnect.recv())
+        .await
+        .expect("reconnection through sentinel2")
+        .unwrap();
+
+    let value: String = client.get("key").await?;
+    assert_eq!("value", value);
+    assert!(sentinel2
+        .received()
+        .iter()
+        .any(|command| command.name == "SENTINEL"
+            && command.args.first().map(|arg| arg.as_ref())
+                == Some(&b"GET-MASTER-ADDR-BY-NAME"[..])));
+
+    Ok(())
+}
+
 /// test reconnection to replica when master is stopped
 /// master stop is not automated but must be done manually
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
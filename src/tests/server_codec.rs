@@ -0,0 +1,221 @@
+use crate::{
+    client::ReplyLimits,
+    resp::{Command, RespVersion, ServerCodec, ServerCommand, Value, KNOWN_COMMANDS},
+    Error, RedisError, RedisErrorKind, Result,
+};
+use bytes::BytesMut;
+use std::{collections::HashMap, fs, path::Path};
+use tokio_util::codec::{Decoder, Encoder};
+
+fn decode(codec: &mut ServerCodec, buf: &mut BytesMut) -> Result<Option<Command>> {
+    match codec.decode(buf)? {
+        Some(ServerCommand::Known(command)) => Ok(Some(command)),
+        Some(command) => panic!("Unexpected unknown command {command:?}"),
+        None => Ok(None),
+    }
+}
+
+fn encode(codec: &mut ServerCodec, value: &Value) -> Result<String> {
+    let mut buf = BytesMut::new();
+    codec.encode(value, &mut buf)?;
+    Ok(String::from_utf8(buf.to_vec())?)
+}
+
+#[test]
+fn decode_command() -> Result<()> {
+    let mut codec = ServerCodec::default();
+
+    let mut buf: BytesMut = "*3\r\n$3\r\nset\r\n$3\r\nkey\r\n$5\r\nvalue\r\n".into();
+    let command = decode(&mut codec, &mut buf)?.unwrap();
+    assert_eq!("SET", command.name);
+    assert_eq!(2, command.args.len());
+    let args: Vec<&[u8]> = command.args.iter().map(|a| a.as_ref()).collect();
+    assert_eq!(vec![b"key".as_slice(), b"value".as_slice()], args);
+    assert!(buf.is_empty());
+
+    // received in several parts
+    let mut buf: BytesMut = "*2\r\n$3\r\nGET\r\n$3\r\nk".into();
+    assert!(decode(&mut codec, &mut buf)?.is_none());
+    buf.extend_from_slice(b"ey\r\n*1\r\n$4\r\nPING\r\n");
+    let command = decode(&mut codec, &mut buf)?.unwrap();
+    assert_eq!("GET", command.name);
+    let command = decode(&mut codec, &mut buf)?.unwrap();
+    assert_eq!("PING", command.name);
+    assert!(command.args.is_empty());
+    assert!(decode(&mut codec, &mut buf)?.is_none());
+
+    let mut buf: BytesMut = "*0\r\n".into();
+    assert!(matches!(codec.decode(&mut buf), Err(Error::Protocol(_))));
+
+    Ok(())
+}
+
+#[test]
+fn decode_inline_command() -> Result<()> {
+    let mut codec = ServerCodec::default();
+
+    let mut buf: BytesMut = "\r\nset key  value\r\nping\n".into();
+    let command = decode(&mut codec, &mut buf)?.unwrap();
+    assert_eq!("SET", command.name);
+    let args: Vec<&[u8]> = command.args.iter().map(|a| a.as_ref()).collect();
+    assert_eq!(vec![b"key".as_slice(), b"value".as_slice()], args);
+
+    let command = decode(&mut codec, &mut buf)?.unwrap();
+    assert_eq!("PING", command.name);
+
+    let mut buf: BytesMut = "get key".into();
+    assert!(decode(&mut codec, &mut buf)?.is_none());
+    buf.extend_from_slice(b"\r\n");
+    assert_eq!("GET", decode(&mut codec, &mut buf)?.unwrap().name);
+
+    Ok(())
+}
+
+#[test]
+fn decode_unknown_command() -> Result<()> {
+    let mut codec = ServerCodec::default();
+
+    let mut buf: BytesMut = "*2\r\n$9\r\nmymodule.\r\n$3\r\narg\r\nmymodule.\r\n".into();
+    for _ in 0..2 {
+        let Some(ServerCommand::Unknown { name, .. }) = codec.decode(&mut buf)? else {
+            panic!("Expected unknown command");
+        };
+        assert_eq!("MYMODULE.", name);
+    }
+    assert!(buf.is_empty());
+
+    codec.register_command("MYMODULE.");
+    let mut buf: BytesMut = "*1\r\n$9\r\nmymodule.\r\nmymodule.\r\n".into();
+    assert_eq!("MYMODULE.", decode(&mut codec, &mut buf)?.unwrap().name);
+    assert_eq!("MYMODULE.", decode(&mut codec, &mut buf)?.unwrap().name);
+
+    Ok(())
+}
+
+/// All the commands built with `cmd("...")` in the sources must be decoded
+#[test]
+fn known_commands() -> Result<()> {
+    fn visit(dir: &Path, names: &mut Vec<String>) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                // tests use fake commands on purpose
+                if !path.ends_with("tests") {
+                    visit(&path, names)?;
+                }
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                let source = fs::read_to_string(&path)?;
+                for part in source.split("cmd(\"").skip(1) {
+                    if let Some(end) = part.find('"') {
+                        names.push(part[..end].to_owned());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    assert!(KNOWN_COMMANDS.windows(2).all(|w| w[0] < w[1]));
+
+    let mut names = Vec::new();
+    visit(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut names,
+    )?;
+    assert!(!names.is_empty());
+    for name in names {
+        assert!(
+            KNOWN_COMMANDS.binary_search(&name.as_str()).is_ok(),
+            "{name} is missing from KNOWN_COMMANDS"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn decode_with_limits() {
+    let limits = ReplyLimits {
+        max_bulk_size: 4,
+        ..Default::default()
+    };
+    let mut codec = ServerCodec::with_limits(RespVersion::Resp2, limits);
+
+    let mut buf: BytesMut = "*2\r\n$3\r\nGET\r\n$5\r\nmykey\r\n".into();
+    assert!(matches!(codec.decode(&mut buf), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn decode_absurd_lengths() {
+    let mut codec = ServerCodec::default();
+
+    let mut buf: BytesMut = "*1\r\n%9223372036854775808\r\n".into();
+    assert!(matches!(codec.decode(&mut buf), Err(Error::Protocol(_))));
+}
+
+#[test]
+fn encode_reply() -> Result<()> {
+    let mut resp2 = ServerCodec::new(RespVersion::Resp2);
+    let mut resp3 = ServerCodec::new(RespVersion::Resp3);
+
+    let value = Value::SimpleString("OK".to_owned());
+    assert_eq!("+OK\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("+OK\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Error(RedisError {
+        kind: RedisErrorKind::Err,
+        description: "unknown command".to_owned(),
+    });
+    assert_eq!("-ERR unknown command\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("-ERR unknown command\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Nil;
+    assert_eq!("$-1\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("_\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Boolean(true);
+    assert_eq!(":1\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("#t\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Double(1.5);
+    assert_eq!("$3\r\n1.5\r\n", encode(&mut resp2, &value)?);
+    assert_eq!(",1.5\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::BigNumber("12345678901234567890".to_owned());
+    assert_eq!(
+        "$20\r\n12345678901234567890\r\n",
+        encode(&mut resp2, &value)?
+    );
+    assert_eq!("(12345678901234567890\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Map(HashMap::from([(
+        Value::BulkString(b"field".to_vec()),
+        Value::Integer(1),
+    )]));
+    assert_eq!("*2\r\n$5\r\nfield\r\n:1\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("%1\r\n$5\r\nfield\r\n:1\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Set(vec![Value::Integer(1)]);
+    assert_eq!("*1\r\n:1\r\n", encode(&mut resp2, &value)?);
+    assert_eq!("~1\r\n:1\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Push(vec![Value::BulkString(b"message".to_vec())]);
+    assert_eq!("*1\r\n$7\r\nmessage\r\n", encode(&mut resp2, &value)?);
+    assert_eq!(">1\r\n$7\r\nmessage\r\n", encode(&mut resp3, &value)?);
+
+    let value = Value::Attribute {
+        attributes: HashMap::from([(Value::SimpleString("ttl".to_owned()), Value::Integer(10))]),
+        value: Box::new(Value::Array(vec![Value::Integer(1)])),
+    };
+    assert_eq!("*1\r\n:1\r\n", encode(&mut resp2, &value)?);
+    assert_eq!(
+        "|1\r\n+ttl\r\n:10\r\n*1\r\n:1\r\n",
+        encode(&mut resp3, &value)?
+    );
+
+    // switched after HELLO 3
+    resp2.set_version(RespVersion::Resp3);
+    assert_eq!("_\r\n", encode(&mut resp2, &Value::Nil)?);
+
+    Ok(())
+}
@@ -837,7 +837,7 @@ async fn xadd_ignore_result() -> Result<()> {
     client.flushdb(FlushingMode::Sync).await?;
 
     client
-        .xadd(
+        .xadd::<_, _, _, _, _, ()>(
             "mystream",
             "123456-0",
             [("name", "John"), ("surname", "Doe")],
@@ -1,8 +1,9 @@
 use crate::{
     commands::{
-        GenericCommands, GetExOptions, LcsMatch, SetCondition, SetExpiration, StringCommands,
+        GenericCommands, GetExOptions, LcsMatch, ListCommands, SetCondition, SetExpiration,
+        StringCommands,
     },
-    resp::Value,
+    resp::{BulkBytes, Value},
     tests::get_test_client,
     Error, RedisError, RedisErrorKind, Result,
 };
@@ -112,6 +113,45 @@ async fn get_and_set() -> Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn get_zero_copy() -> Result<()> {
+    let client = get_test_client().await?;
+
+    // cleanup
+    client.del("key").await?;
+
+    let value: Option<BulkBytes> = client.get("key").await?;
+    assert_eq!(None, value);
+
+    client.set("key", vec![42u8; 100_000]).await?;
+    let value: BulkBytes = client.get("key").await?;
+    assert_eq!(vec![42u8; 100_000], value.as_bytes());
+
+    let resp_buf = client.get::<_, ()>("key").into_resp_buf().await?;
+    let value: &[u8] = resp_buf.to()?;
+    assert_eq!(100_000, value.len());
+
+    client.set("key", "value").await?;
+    let resp_buf = client.get::<_, ()>("key").into_resp_buf().await?;
+    let value: &str = resp_buf.to()?;
+    assert_eq!("value", value);
+
+    let result = client.lpush("key", "value").into_resp_buf().await;
+    assert!(matches!(
+        result,
+        Err(Error::Redis(RedisError {
+            kind: RedisErrorKind::WrongType,
+            description: _
+        }))
+    ));
+
+    client.close().await?;
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[serial]
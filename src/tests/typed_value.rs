@@ -0,0 +1,217 @@
+use crate::{
+    resp::{CommandArgs, SingleArg, Value},
+    Result,
+};
+use serde::de::DeserializeOwned;
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "json", feature = "mock-server"))]
+use serial_test::serial;
+
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[cfg(any(feature = "json", feature = "msgpack", feature = "cbor"))]
+fn point() -> Point {
+    Point {
+        x: 1,
+        y: -2,
+        label: Some("origin".to_owned()),
+    }
+}
+
+fn encode(arg: impl SingleArg) -> Vec<u8> {
+    let mut args = CommandArgs::default();
+    args.arg(arg);
+    assert_eq!(1, args.len());
+    args.into_iter().next().unwrap().to_vec()
+}
+
+fn decode<T: DeserializeOwned>(bytes: Vec<u8>) -> Result<T> {
+    Value::BulkString(bytes).into()
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() -> Result<()> {
+    use crate::resp::Json;
+
+    let bytes = encode(Json::new(point())?);
+    assert_eq!(br#"{"x":1,"y":-2,"label":"origin"}"#.to_vec(), bytes);
+
+    let deserialized: Json<Point> = decode(bytes.clone())?;
+    assert_eq!(&bytes, deserialized.encoded());
+    assert_eq!(point(), deserialized.into_inner());
+
+    // JSON object keys must be strings
+    let result = Json::new(std::collections::HashMap::from([((1, 2), 3)]));
+    assert!(result.is_err());
+
+    let result: Result<Json<Point>> = decode(b"not json".to_vec());
+    assert!(result.is_err());
+
+    let value: Option<Json<Point>> = Value::Nil.into()?;
+    assert!(value.is_none());
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack() -> Result<()> {
+    use crate::resp::MsgPack;
+
+    let bytes = encode(MsgPack::new(point())?);
+    let deserialized: MsgPack<Point> = decode(bytes)?;
+    assert_eq!(point(), deserialized.into_inner());
+
+    let values = vec![MsgPack::new(1u32)?, MsgPack::new(2u32)?];
+    let mut args = CommandArgs::default();
+    args.arg(values);
+    assert_eq!(2, args.len());
+
+    Ok(())
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn cbor() -> Result<()> {
+    use crate::resp::Cbor;
+    use std::collections::BTreeMap;
+
+    let map = BTreeMap::from([("foo".to_owned(), "bar".to_owned())]);
+    let bytes = encode(Cbor::new(&map)?);
+    assert_eq!(b"\xa1\x63\x66\x6F\x6F\x63\x62\x61\x72".to_vec(), bytes);
+
+    let deserialized: Cbor<BTreeMap<String, String>> = decode(bytes)?;
+    assert_eq!(map, deserialized.into_inner());
+
+    let deserialized: Cbor<Point> = decode(encode(Cbor::new(point())?))?;
+    assert_eq!(point(), deserialized.into_inner());
+
+    Ok(())
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4() -> Result<()> {
+    use crate::resp::{Compressed, Lz4};
+
+    let text = "Lorem ipsum ".repeat(100);
+    let bytes = encode(Compressed::<Lz4, _>::new(text.clone())?);
+    assert!(bytes.len() < text.len());
+
+    let deserialized: Compressed<Lz4, String> = decode(bytes)?;
+    assert_eq!(text, *deserialized);
+
+    let result: Result<Compressed<Lz4, String>> = decode(b"not lz4".to_vec());
+    assert!(result.is_err());
+
+    // the prepended size is not trusted beyond the maximum LZ4 ratio
+    let result: Result<Compressed<Lz4, String>> = decode(b"\xff\xff\xff\xff\x00".to_vec());
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd() -> Result<()> {
+    use crate::resp::{Compressed, Zstd};
+
+    let data = vec![7u8; 10_000];
+    let bytes = encode(Compressed::<Zstd, _>::new(data.clone())?);
+    assert!(bytes.len() < data.len());
+
+    let deserialized: Compressed<Zstd, crate::resp::BulkString> = decode(bytes)?;
+    assert_eq!(data, deserialized.as_bytes());
+
+    let result: Result<Compressed<Zstd, String>> = decode(b"not zstd".to_vec());
+    assert!(result.is_err());
+
+    // the decompressed size is bounded, whatever the ratio
+    let bytes = encode(Compressed::<Zstd, _>::new(data.clone())?);
+    assert_eq!(data, crate::resp::zstd_decompress(&bytes, 10_000)?);
+    assert!(crate::resp::zstd_decompress(&bytes, 9_999).is_err());
+
+    Ok(())
+}
+
+#[cfg(all(feature = "json", feature = "lz4"))]
+#[test]
+fn compressed_json() -> Result<()> {
+    use crate::resp::{Compressed, Json, Lz4};
+
+    let points = vec![point(); 100];
+    let bytes = encode(Compressed::<Lz4, _>::new(Json::new(&points)?)?);
+    assert!(bytes.len() < encode(Json::new(&points)?).len());
+
+    let deserialized: Compressed<Lz4, Json<Vec<Point>>> = decode(bytes)?;
+    assert_eq!(points, deserialized.into_inner().into_inner());
+
+    Ok(())
+}
+
+#[cfg(all(feature = "json", feature = "mock-server"))]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[serial]
+async fn commands() -> Result<()> {
+    use crate::{
+        client::Client,
+        commands::{HashCommands, ListCommands, StringCommands},
+        mock::MockServer,
+        resp::Json,
+    };
+
+    let server = MockServer::start().await?;
+    let json = br#"{"x":1,"y":-2,"label":"origin"}"#.to_vec();
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    server.on_any("HSET").reply(Value::Integer(1));
+    server.on_any("LPUSH").reply(Value::Integer(2));
+    server
+        .on("GET", "key")
+        .reply(Value::BulkString(json.clone()));
+    server.on("GET", "missing").reply(Value::Nil);
+    server
+        .on("HGET", ("hash", "field"))
+        .reply(Value::BulkString(json.clone()));
+    server
+        .on("LRANGE", ("list", 0, -1))
+        .reply(Value::Array(vec![
+            Value::BulkString(json.clone()),
+            Value::BulkString(json.clone()),
+        ]));
+
+    let client = Client::connect(server.uri()).await?;
+    let value = Json::new(point())?;
+    client.set("key", value.clone()).await?;
+    client.hset("hash", ("field", value.clone())).await?;
+    client.lpush("list", [value.clone(), value.clone()]).await?;
+
+    for command in server.received().iter().filter(|c| c.name != "HELLO") {
+        let last_arg = command.args.iter().last().unwrap();
+        assert_eq!(&json, last_arg);
+    }
+
+    let value: Json<Point> = client.get("key").await?;
+    assert_eq!(point(), *value);
+    let value: Option<Json<Point>> = client.get("missing").await?;
+    assert!(value.is_none());
+    let value: Json<Point> = client.hget("hash", "field").await?;
+    assert_eq!(point(), *value);
+    let values: Vec<Json<Point>> = client.lrange("list", 0, -1).await?;
+    assert_eq!(
+        vec![point(), point()],
+        values.into_iter().map(Json::into_inner).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
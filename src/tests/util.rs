@@ -2,6 +2,8 @@ use crate::{
     client::{Client, Config, IntoConfig},
     Result,
 };
+#[cfg(feature = "mock-server")]
+use crate::{mock::MockServer, resp::Value};
 #[cfg(feature = "tls")]
 use native_tls::Certificate;
 
@@ -141,6 +143,98 @@ pub(crate) async fn get_redis_stack_test_client() -> Result<Client> {
     Client::connect(format!("redis://{}:{}", get_default_host(), 8000)).await
 }
 
+/// Mock server replying to `GET` and `SET`
+#[cfg(feature = "mock-server")]
+pub(crate) async fn start_get_set_server() -> Result<MockServer> {
+    let server = MockServer::start().await?;
+    server
+        .on_any("GET")
+        .reply(Value::BulkString(b"value".to_vec()));
+    server
+        .on_any("SET")
+        .reply(Value::SimpleString("OK".to_owned()));
+    Ok(server)
+}
+
+/// Mock server replying to `GET` and `SET`, seen as a cluster of a single node owning all the hash slots
+#[cfg(feature = "mock-server")]
+pub(crate) async fn start_get_set_cluster_server() -> Result<MockServer> {
+    let server = start_get_set_server().await?;
+
+    let str = |s: &str| Value::BulkString(s.as_bytes().to_vec());
+    let map = |entries: Vec<(&str, Value)>| {
+        Value::Map(entries.into_iter().map(|(k, v)| (str(k), v)).collect())
+    };
+
+    let node = map(vec![
+        ("id", str("node1")),
+        ("endpoint", str("127.0.0.1")),
+        ("ip", str("127.0.0.1")),
+        ("port", Value::Integer(server.addr().port().into())),
+        ("role", str("master")),
+        ("replication-offset", Value::Integer(0)),
+        ("health", str("online")),
+    ]);
+    let shard = map(vec![
+        (
+            "slots",
+            Value::Array(vec![Value::Integer(0), Value::Integer(16383)]),
+        ),
+        ("nodes", Value::Array(vec![node])),
+    ]);
+    server
+        .on("CLUSTER", "SHARDS")
+        .reply(Value::Array(vec![shard]));
+
+    // the key is the first argument
+    let key_specification = || {
+        map(vec![
+            (
+                "begin_search",
+                map(vec![
+                    ("type", str("index")),
+                    ("spec", map(vec![("index", Value::Integer(1))])),
+                ]),
+            ),
+            (
+                "find_keys",
+                map(vec![
+                    ("type", str("range")),
+                    (
+                        "spec",
+                        map(vec![
+                            ("lastkey", Value::Integer(0)),
+                            ("keystep", Value::Integer(1)),
+                            ("limit", Value::Integer(0)),
+                        ]),
+                    ),
+                ]),
+            ),
+            ("flags", Value::Array(Vec::new())),
+        ])
+    };
+    let command_info = |name: &str, arity: i64| {
+        Value::Array(vec![
+            str(name),
+            Value::Integer(arity),
+            Value::Array(Vec::new()),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Array(Vec::new()),
+            Value::Array(Vec::new()),
+            Value::Array(vec![key_specification()]),
+            Value::Array(Vec::new()),
+        ])
+    };
+    server.on_any("COMMAND").reply(Value::Array(vec![
+        command_info("get", 2),
+        command_info("set", -3),
+    ]));
+
+    Ok(server)
+}
+
 pub fn log_try_init() {
     let _ = env_logger::builder()
         .format_target(false)
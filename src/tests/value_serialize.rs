@@ -1,9 +1,9 @@
 use super::log_try_init;
 use crate::{
-    resp::{RespBuf, RespSerializer, Value},
+    resp::{RespBuf, RespDeserializer, RespSerializer, Value},
     RedisError, RedisErrorKind, Result,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 fn serialize(value: Value) -> Result<RespBuf> {
     let mut serializer = RespSerializer::new();
@@ -78,3 +78,45 @@ fn array() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn big_number() -> Result<()> {
+    log_try_init();
+
+    let resp_buf = serialize(Value::BigNumber(
+        "3492890328409238509324850943850943825024385".to_owned(),
+    ))?;
+    log::debug!("resp_buf: {resp_buf}");
+    assert_eq!(
+        b"(3492890328409238509324850943850943825024385\r\n",
+        resp_buf.as_bytes()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn attribute() -> Result<()> {
+    log_try_init();
+
+    let value = Value::Attribute {
+        attributes: [(Value::SimpleString("ttl".to_owned()), Value::Integer(3600))].into(),
+        value: Box::new(Value::Integer(12)),
+    };
+    let resp_buf = serialize(value)?;
+    log::debug!("resp_buf: {resp_buf}");
+    assert_eq!(b"|1\r\n+ttl\r\n:3600\r\n:12\r\n", resp_buf.as_bytes());
+
+    // round trip
+    let mut deserializer = RespDeserializer::new(resp_buf.as_bytes()).keep_attributes();
+    let value = Value::deserialize(&mut deserializer)?;
+    assert_eq!(
+        Value::Attribute {
+            attributes: [(Value::SimpleString("ttl".to_owned()), Value::Integer(3600),)].into(),
+            value: Box::new(Value::Integer(12)),
+        },
+        value
+    );
+
+    Ok(())
+}